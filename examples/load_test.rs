@@ -0,0 +1,232 @@
+//! Generate a synthetic stream of integration events and report latency and throughput
+//!
+//! Usage:
+//!
+//! ```text
+//! cargo run --example load_test -- [--stub | --endpoint <url>] <service key> \
+//!     [events per second] [duration secs]
+//! ```
+//!
+//! Events are a randomized mix of triggers for new incidents and resolves of incidents previously
+//! triggered by this run. By default they're sent to PagerDuty, so point this at a service in a
+//! sandbox account; it will create real incidents. With `--endpoint` they're sent to a relay or
+//! other server instead, and with `--stub` to a local server accepting every event, which
+//! measures the client alone.
+extern crate hyper;
+extern crate pagerduty;
+
+use std::env;
+use std::io;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hyper::server::{Listening, Request, Response as HttpResponse, Server};
+
+use pagerduty::AuthToken;
+use pagerduty::integration::{self, ResolveEvent, Response, TriggerEvent};
+
+/// Fraction of events which trigger a new incident while others are still open
+const TRIGGER_RATIO: f64 = 0.6;
+
+/// What the stub server answers every event with
+const STUB_RESPONSE: &[u8] =
+    br#"{"status": "success", "message": "Event processed", "incident_key": "stub"}"#;
+
+/// Minimal xorshift generator; the load doesn't need to be cryptographically random
+struct Rng(u64);
+
+impl Rng {
+    fn seeded() -> Rng {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        Rng(now.as_secs() ^ (now.subsec_nanos() as u64) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    latencies: Vec<Duration>,
+    triggers: usize,
+    resolves: usize,
+    success: usize,
+    rejected: usize,
+    throttled: usize,
+    server_errors: usize,
+    failures: usize,
+}
+
+impl Report {
+    fn record(&mut self, latency: Duration, result: pagerduty::Result<Response>) {
+        self.latencies.push(latency);
+        match result {
+            Ok(Response::Success(_)) => self.success += 1,
+            Ok(Response::BadRequest(_)) => self.rejected += 1,
+            Ok(Response::Throttled { .. }) => self.throttled += 1,
+            Ok(Response::InternalServerError(_)) => self.server_errors += 1,
+            Err(_) => self.failures += 1,
+        }
+    }
+
+    fn print(&mut self, elapsed: Duration) {
+        let sent = self.latencies.len();
+        let secs = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9;
+
+        println!("sent {} events in {:.1}s ({:.2} events/s)", sent, secs, sent as f64 / secs);
+        println!("  triggers: {}, resolves: {}", self.triggers, self.resolves);
+        println!("  success: {}, bad request: {}, throttled: {}, server error: {}, failed: {}",
+                 self.success, self.rejected, self.throttled, self.server_errors, self.failures);
+
+        if sent == 0 {
+            return;
+        }
+
+        self.latencies.sort();
+        let millis = |d: Duration| d.as_secs() as f64 * 1e3 + d.subsec_nanos() as f64 / 1e6;
+        let percentile = |p: f64| millis(self.latencies[((sent - 1) as f64 * p) as usize]);
+        let total: f64 = self.latencies.iter().map(|&d| millis(d)).sum();
+
+        println!("  latency ms: min {:.1}, mean {:.1}, p50 {:.1}, p99 {:.1}, max {:.1}",
+                 millis(self.latencies[0]),
+                 total / sent as f64,
+                 percentile(0.5),
+                 percentile(0.99),
+                 millis(self.latencies[sent - 1]));
+    }
+}
+
+fn usage() -> ! {
+    println!("usage: load_test [--stub | --endpoint <url>] <service key> [events per second] \
+              [duration secs]");
+    process::exit(1);
+}
+
+/// Start a server on a local port which accepts every event, as PagerDuty would
+fn stub_server() -> hyper::Result<Listening> {
+    Server::http("127.0.0.1:0").and_then(|server| {
+        server.handle(|mut req: Request, res: HttpResponse| {
+            let _ = io::copy(&mut req, &mut io::sink());
+            let _ = res.send(STUB_RESPONSE);
+        })
+    })
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let mut stub = None;
+    let flag = args.first().cloned().unwrap_or_default();
+    let endpoint = match &flag[..] {
+        "--stub" => {
+            args.remove(0);
+            let listening = stub_server().unwrap_or_else(|err| {
+                println!("failed to start stub server: {}", err);
+                process::exit(1);
+            });
+            let endpoint = format!("http://{}/", listening.socket);
+            stub = Some(listening);
+            Some(endpoint)
+        },
+        "--endpoint" if args.len() > 1 => {
+            args.remove(0);
+            Some(args.remove(0))
+        },
+        "--endpoint" => usage(),
+        _ => None,
+    };
+
+    if args.is_empty() {
+        usage();
+    }
+
+    let service_key = &args[0][..];
+    let rate: f64 = args.get(1).map_or(Ok(1.0), |s| s.parse()).unwrap_or_else(|_| usage());
+    let duration: u64 = args.get(2).map_or(Ok(10), |s| s.parse()).unwrap_or_else(|_| usage());
+
+    if !rate.is_finite() || rate <= 0.0 {
+        usage();
+    }
+
+    let interval = Duration::from_nanos((1e9 / rate) as u64);
+    let deadline = Duration::from_secs(duration);
+
+    // The integration API authenticates with the service key; the token is unused.
+    let token = AuthToken::new("");
+    let mut rng = Rng::seeded();
+    let mut open: Vec<String> = Vec::new();
+    let mut report = Report::default();
+    let mut serial = 0;
+
+    let start = Instant::now();
+    while start.elapsed() < deadline {
+        let tick = Instant::now();
+
+        let trigger = open.is_empty() || rng.next_f64() < TRIGGER_RATIO;
+        let sent = Instant::now();
+        let result = if trigger {
+            serial += 1;
+            let incident_key = format!("load-test-{}-{}", rng.next(), serial);
+            let description = format!("Synthetic load test event {}", serial);
+            let mut event = TriggerEvent::new(service_key, &description[..])
+                .set_incident_key(&incident_key[..])
+                .set_client("pagerduty-rs load_test");
+            if let Some(ref endpoint) = endpoint {
+                event = event.set_endpoint(&endpoint[..]);
+            }
+
+            report.triggers += 1;
+            let result = integration::trigger(&token, &event);
+
+            // Only incidents which were opened can be resolved later
+            if let Ok(Response::Success(_)) = result {
+                open.push(incident_key);
+            }
+            result
+        } else {
+            let index = rng.below(open.len());
+            let incident_key = open.swap_remove(index);
+            let mut event = ResolveEvent::new(service_key, &incident_key[..]);
+            if let Some(ref endpoint) = endpoint {
+                event = event.set_endpoint(&endpoint[..]);
+            }
+
+            report.resolves += 1;
+            integration::resolve(&token, &event)
+        };
+        report.record(sent.elapsed(), result);
+
+        let spent = tick.elapsed();
+        if spent < interval {
+            thread::sleep(interval - spent);
+        }
+    }
+
+    // Leave the sandbox tidy
+    for incident_key in open.drain(..) {
+        let mut event = ResolveEvent::new(service_key, &incident_key[..]);
+        if let Some(ref endpoint) = endpoint {
+            event = event.set_endpoint(&endpoint[..]);
+        }
+        let _ = integration::resolve(&token, &event);
+    }
+
+    report.print(start.elapsed());
+
+    if let Some(mut listening) = stub {
+        let _ = listening.close();
+    }
+}
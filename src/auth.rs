@@ -1,10 +1,35 @@
 use std::borrow::Cow;
+use std::collections::btree_map::{self, BTreeMap};
+use std::env::{self, VarError};
+use std::fmt;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The environment variable read by [`AuthToken::from_env`](struct.AuthToken.html#method.from_env)
+pub const TOKEN_VAR: &str = "PAGERDUTY_TOKEN";
+
+/// The environment variable read by [`routing_key_from_env`](fn.routing_key_from_env.html)
+pub const ROUTING_KEY_VAR: &str = "PAGERDUTY_ROUTING_KEY";
 
 /// A token used to authorize requests to PagerDuty.
 ///
 /// The AuthToken is expected to be created with a String or &str passed to `AuthToken::new`. Since
 /// AuthToken uses a Cow internally, no extra allocations occur.
 ///
+/// The token is sent in the `Authorization` header in the format of its
+/// [`AuthScheme`](enum.AuthScheme.html), which is the REST API's `Token token=<key>` unless
+/// created with `AuthToken::with_scheme`. The Events and Integration APIs authorize requests with
+/// the routing key in their body instead, so the same token can be used for every API.
+///
+/// CLI tools and services configured by their environment can read the token from the
+/// `PAGERDUTY_TOKEN` variable with `AuthToken::from_env`, and the routing key of events from
+/// `PAGERDUTY_ROUTING_KEY` with [`routing_key_from_env`](fn.routing_key_from_env.html).
+///
+/// The token is redacted when formatted with `Debug` or `Display`, so it doesn't leak into logs.
+/// With the `zeroize` feature, owned tokens are also overwritten in memory when dropped. Tokens
+/// borrowed from a string are left to the owner of the string.
+///
 /// # Example
 ///
 /// ```
@@ -16,17 +41,218 @@ use std::borrow::Cow;
 /// // Owned version may be desired in some cases
 /// let owned_token = AuthToken::new(String::from("token"));
 /// ```
-pub struct AuthToken<'a>(Cow<'a, str>);
+pub struct AuthToken<'a> {
+    token: Cow<'a, str>,
+    scheme: AuthScheme,
+}
+
+impl<'a> fmt::Debug for AuthToken<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AuthToken")
+            .field("token", &"[REDACTED]")
+            .field("scheme", &self.scheme)
+            .finish()
+    }
+}
+
+impl<'a> fmt::Display for AuthToken<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<'a> Drop for AuthToken<'a> {
+    fn drop(&mut self) {
+        if let Cow::Owned(ref mut token) = self.token {
+            token.zeroize();
+        }
+    }
+}
+
+/// The format of the `Authorization` header sent with a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// `Token token=<key>`, the format of REST API keys
+    Token,
+
+    /// `Bearer <token>`, the format of [OAuth](oauth/index.html) access tokens
+    Bearer,
+
+    /// The token as is, for gateways which expect their own format
+    Raw,
+}
 
 impl<'a> AuthToken<'a> {
+    /// Create a token sent in the format of REST API keys, `Token token=<key>`
+    ///
+    /// Only the key is passed. Before version 0.3 the token was sent as is, so callers which
+    /// included the `Token token=` prefix themselves must now drop it, or use
+    /// `AuthToken::with_scheme` with `AuthScheme::Raw`.
     pub fn new<T>(raw_token: T) -> AuthToken<'a>
         where T: Into<Cow<'a, str>>
     {
-        AuthToken(raw_token.into())
+        AuthToken::with_scheme(raw_token, AuthScheme::Token)
+    }
+
+    /// Create a token from the REST API key in the `PAGERDUTY_TOKEN` environment variable
+    ///
+    /// This library has no client object holding credentials, since every request is given the
+    /// token to authorize with. This and [`routing_key_from_env`](fn.routing_key_from_env.html)
+    /// stand in for a `Client::from_env`.
+    pub fn from_env() -> Result<AuthToken<'static>, EnvError> {
+        read_var(TOKEN_VAR).map(AuthToken::new)
+    }
+
+    /// Create a token sent in the format of `scheme`
+    pub fn with_scheme<T>(raw_token: T, scheme: AuthScheme) -> AuthToken<'a>
+        where T: Into<Cow<'a, str>>
+    {
+        AuthToken {
+            token: raw_token.into(),
+            scheme,
+        }
+    }
+
+    /// The format of the `Authorization` header sent with the token
+    pub fn scheme(&self) -> AuthScheme {
+        self.scheme
     }
 
     pub fn to_header(&self) -> ::hyper::header::Authorization<String> {
-        ::hyper::header::Authorization(self.0.as_ref().to_owned())
+        let value = match self.scheme {
+            AuthScheme::Token => format!("Token token={}", self.token),
+            AuthScheme::Bearer => format!("Bearer {}", self.token),
+            AuthScheme::Raw => self.token.as_ref().to_owned(),
+        };
+
+        ::hyper::header::Authorization(value)
+    }
+}
+
+/// Tokens of several PagerDuty accounts, by name
+///
+/// Every request function takes the token to authorize with, so one process can act on any number
+/// of accounts. `Accounts` keeps their tokens in one place for services managing several tenants.
+///
+/// # Example
+///
+/// ```no_run
+/// use pagerduty::{Accounts, AuthToken};
+/// use pagerduty::rest::services::{self, ListServices};
+///
+/// let accounts = Accounts::new()
+///     .add("acme", AuthToken::new("acme token"))
+///     .add("initech", AuthToken::new("initech token"));
+///
+/// for (name, token) in accounts.iter() {
+///     let page = services::list(token, &ListServices::new()).unwrap().success();
+///     println!("{}: {:?} services", name, page.map(|page| page.items.len()));
+/// }
+///
+/// let acme = accounts.get("acme").unwrap();
+/// services::list(acme, &ListServices::new()).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct Accounts {
+    tokens: BTreeMap<String, AuthToken<'static>>,
+}
+
+impl Accounts {
+    /// Create an empty set of accounts
+    pub fn new() -> Accounts {
+        Accounts::default()
+    }
+
+    /// Add account `name` authorized with `token`
+    pub fn add<N>(mut self, name: N, token: AuthToken<'static>) -> Self
+        where N: Into<String>
+    {
+        self.insert(name, token);
+        self
+    }
+
+    /// Add account `name` authorized with `token`, returning its previous token if any
+    pub fn insert<N>(&mut self, name: N, token: AuthToken<'static>) -> Option<AuthToken<'static>>
+        where N: Into<String>
+    {
+        self.tokens.insert(name.into(), token)
+    }
+
+    /// Remove account `name`, returning its token
+    pub fn remove(&mut self, name: &str) -> Option<AuthToken<'static>> {
+        self.tokens.remove(name)
+    }
+
+    /// The token of account `name`
+    pub fn get(&self, name: &str) -> Option<&AuthToken<'static>> {
+        self.tokens.get(name)
+    }
+
+    /// The names and tokens of the accounts, ordered by name
+    pub fn iter(&self) -> btree_map::Iter<'_, String, AuthToken<'static>> {
+        self.tokens.iter()
+    }
+
+    /// The number of accounts
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether there are no accounts
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+/// Read the routing key of events from the `PAGERDUTY_ROUTING_KEY` environment variable
+pub fn routing_key_from_env() -> Result<String, EnvError> {
+    read_var(ROUTING_KEY_VAR)
+}
+
+fn read_var(name: &'static str) -> Result<String, EnvError> {
+    read_var_with(name, |name| env::var(name))
+}
+
+/// Read variable `name` with `get`, which looks variables up like `env::var`
+fn read_var_with<F>(name: &'static str, get: F) -> Result<String, EnvError>
+    where F: FnOnce(&str) -> Result<String, VarError>
+{
+    match get(name) {
+        Ok(ref value) if value.is_empty() => Err(EnvError::Missing(name)),
+        Ok(value) => Ok(value),
+        Err(VarError::NotPresent) => Err(EnvError::Missing(name)),
+        Err(VarError::NotUnicode(_)) => Err(EnvError::NotUnicode(name)),
+    }
+}
+
+/// Error reading credentials from the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvError {
+    /// The variable with this name isn't set, or is empty
+    Missing(&'static str),
+
+    /// The value of the variable with this name isn't valid unicode
+    NotUnicode(&'static str),
+}
+
+impl ::std::error::Error for EnvError {
+    fn description(&self) -> &str {
+        match *self {
+            EnvError::Missing(_) => "Environment variable not set",
+            EnvError::NotUnicode(_) => "Environment variable not unicode",
+        }
+    }
+}
+
+impl ::std::fmt::Display for EnvError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            EnvError::Missing(name) => write!(f, "Environment variable {} is not set", name),
+            EnvError::NotUnicode(name) => {
+                write!(f, "Environment variable {} is not valid unicode", name)
+            },
+        }
     }
 }
 
@@ -43,4 +269,48 @@ mod tests {
     fn make_auth_token_with_owned_string() {
         AuthToken::new(String::from("token"));
     }
+
+    #[test]
+    fn tokens_by_account() {
+        let mut accounts = Accounts::new()
+            .add("initech", AuthToken::new("b"))
+            .add("acme", AuthToken::new("a"));
+
+        let names: Vec<&str> = accounts.iter().map(|(name, _)| &name[..]).collect();
+        assert_eq!(names, vec!["acme", "initech"]);
+
+        assert_eq!(accounts.get("acme").unwrap().to_header().0, "Token token=a");
+        assert!(accounts.insert("acme", AuthToken::new("c")).is_some());
+        assert_eq!(accounts.get("acme").unwrap().to_header().0, "Token token=c");
+
+        assert!(accounts.remove("initech").is_some());
+        assert!(accounts.get("initech").is_none());
+        assert_eq!(accounts.len(), 1);
+    }
+
+    #[test]
+    fn credentials_from_env() {
+        let missing = Err(EnvError::Missing("PAGERDUTY_ROUTING_KEY"));
+
+        assert_eq!(read_var_with(ROUTING_KEY_VAR, |_| Err(VarError::NotPresent)), missing);
+        assert_eq!(read_var_with(ROUTING_KEY_VAR, |_| Ok(String::new())), missing);
+        assert_eq!(read_var_with(ROUTING_KEY_VAR, |name| {
+            assert_eq!(name, "PAGERDUTY_ROUTING_KEY");
+            Ok("R0UT1NGK3Y".to_owned())
+        }), Ok("R0UT1NGK3Y".to_owned()));
+    }
+
+    #[test]
+    fn token_is_redacted() {
+        let token = AuthToken::new(String::from("s3cr3t"));
+
+        assert!(!format!("{:?}", token).contains("s3cr3t"));
+        assert_eq!(token.to_string(), "[REDACTED]");
+    }
+
+    #[test]
+    fn header_follows_scheme() {
+        assert_eq!(AuthToken::new("abc").to_header().0, "Token token=abc");
+        assert_eq!(AuthToken::with_scheme("abc", AuthScheme::Raw).to_header().0, "abc");
+    }
 }
@@ -0,0 +1,143 @@
+//! What this build of the library supports
+//!
+//! Optional parts of the library are compiled in with Cargo features. Applications assembled
+//! from several crates can check them with [`capabilities`](fn.capabilities.html) at startup,
+//! rather than finding out when a `chrono` timestamp or wiped secret turns out to be missing.
+
+use std::fmt;
+
+use auth::{AuthScheme, AuthToken};
+
+/// An optional Cargo feature of this library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// `chrono`: times as `chrono::DateTime`; see the [`timestamp`](timestamp/index.html) module
+    Chrono,
+
+    /// `server`: a hyper handler receiving webhooks; see the
+    /// [`webhooks`](webhooks/index.html) module
+    Server,
+
+    /// `zeroize`: tokens and OAuth secrets are overwritten in memory when dropped
+    Zeroize,
+}
+
+impl Feature {
+    /// Every optional feature
+    pub fn all() -> &'static [Feature] {
+        const ALL: &[Feature] = &[Feature::Chrono, Feature::Server, Feature::Zeroize];
+        ALL
+    }
+
+    /// The name of the feature in `Cargo.toml`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Feature::Chrono => "chrono",
+            Feature::Server => "server",
+            Feature::Zeroize => "zeroize",
+        }
+    }
+
+    /// Whether the feature was compiled in
+    pub fn is_enabled(&self) -> bool {
+        match *self {
+            Feature::Chrono => cfg!(feature = "chrono"),
+            Feature::Server => cfg!(feature = "server"),
+            Feature::Zeroize => cfg!(feature = "zeroize"),
+        }
+    }
+}
+
+/// The features compiled in, and how a token authenticates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The optional features which were compiled in
+    pub features: Vec<Feature>,
+
+    /// The format of the `Authorization` header sent with the token
+    pub auth_scheme: AuthScheme,
+}
+
+impl Capabilities {
+    /// Whether `feature` was compiled in
+    pub fn has(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Check that every one of `features` was compiled in
+    ///
+    /// The error names the first missing feature, so it can be reported as is.
+    pub fn require(&self, features: &[Feature]) -> Result<(), MissingFeature> {
+        match features.iter().find(|feature| !self.has(**feature)) {
+            Some(feature) => Err(MissingFeature(*feature)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Report the features compiled in, and the scheme `auth` is sent with
+///
+/// This library has no client object, since every request is given the token to authorize with;
+/// the token stands in for the client here.
+///
+/// # Example
+///
+/// ```
+/// use pagerduty::{capabilities, AuthToken, Feature};
+///
+/// let capabilities = capabilities(&AuthToken::new("abc"));
+/// if let Err(err) = capabilities.require(&[Feature::Chrono]) {
+///     println!("{}", err);
+/// }
+/// ```
+pub fn capabilities(auth: &AuthToken) -> Capabilities {
+    Capabilities {
+        features: Feature::all().iter().cloned().filter(Feature::is_enabled).collect(),
+        auth_scheme: auth.scheme(),
+    }
+}
+
+/// A feature required by the application wasn't compiled in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingFeature(pub Feature);
+
+impl ::std::error::Error for MissingFeature {
+    fn description(&self) -> &str {
+        "Required feature not enabled"
+    }
+}
+
+impl fmt::Display for MissingFeature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The `{}` feature of pagerduty is not enabled", self.0.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use auth::{AuthScheme, AuthToken};
+    use super::{capabilities, Feature, MissingFeature};
+
+    #[test]
+    fn report_compiled_features() {
+        let token = AuthToken::with_scheme("abc", AuthScheme::Bearer);
+        let capabilities = capabilities(&token);
+
+        assert_eq!(capabilities.auth_scheme, AuthScheme::Bearer);
+        assert_eq!(capabilities.has(Feature::Chrono), cfg!(feature = "chrono"));
+        assert_eq!(capabilities.has(Feature::Server), cfg!(feature = "server"));
+        assert_eq!(capabilities.has(Feature::Zeroize), cfg!(feature = "zeroize"));
+        assert_eq!(capabilities.require(&[]), Ok(()));
+
+        let result = capabilities.require(Feature::all());
+        match Feature::all().iter().find(|feature| !feature.is_enabled()) {
+            Some(&feature) => {
+                assert_eq!(result, Err(MissingFeature(feature)));
+                assert_eq!(MissingFeature(feature).to_string(),
+                           format!("The `{}` feature of pagerduty is not enabled",
+                                   feature.name()));
+            },
+            None => assert_eq!(result, Ok(())),
+        }
+    }
+}
@@ -0,0 +1,591 @@
+//! Events API v2
+//!
+//! Version 2 of the events API is the successor of the
+//! [integration API](../integration/index.html). Events are sent to an integration's routing key,
+//! and carry a structured payload with a summary, source, and severity which PagerDuty uses to
+//! create and group alerts.
+//!
+//! # Migrating from the integration API
+//!
+//! Integration API events can be converted into their v2 counterparts with `From`, so call sites
+//! can be migrated one at a time:
+//!
+//! ```no_run
+//! # use pagerduty::AuthToken;
+//! use pagerduty::{events_v2, integration};
+//!
+//! let event = integration::TriggerEvent::new("routing key", "The server is on fire")
+//!     .set_client("Sample Monitoring Service");
+//!
+//! let event = events_v2::TriggerEvent::from(event)
+//!     .set_severity(events_v2::Severity::Critical);
+//!
+//! events_v2::trigger(&AuthToken::new(""), &event).unwrap();
+//! ```
+//!
+//! # Response codes and Retry Logic
+//!
+//! | Result           | Description                                                  | Retry?                       |
+//! |------------------|--------------------------------------------------------------|------------------------------|
+//! | 202              | Accepted - The event has been accepted by PagerDuty.         | No                           |
+//! | 400              | Bad Request - Check that the JSON is valid.                  | No                           |
+//! | 429              | Too Many Requests - Too many API calls at a time.            | Yes - retry after some time. |
+//! | 5xx              | Internal Server Error - PagerDuty experienced an error.      | Yes - retry after some time. |
+//! | Networking Error | Error while trying to communicate with PagerDuty servers.    | Yes - retry after some time. |
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde::Serialize;
+use serde_json::{from_str, to_string, to_value};
+
+use AuthToken;
+use integration::Details;
+use request::{self, Requestable, ServerError};
+use timestamp::Timestamp;
+
+pub use integration::DetailsFormat;
+
+/// URL to which v2 events are sent, unless overridden with `set_endpoint`
+const ENQUEUE_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// The perceived severity of the status the event is describing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+/// Event to report a new or ongoing problem.
+///
+/// When PagerDuty receives a trigger event, it will either create a new alert, or add the event
+/// to an existing alert with the same `dedup_key`.
+#[derive(Debug, Serialize)]
+pub struct TriggerEvent<'a> {
+    routing_key: Cow<'a, str>,
+
+    event_action: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    dedup_key: Option<Cow<'a, str>>,
+
+    payload: Payload<'a>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    client: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    client_url: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    links: Vec<Link<'a>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    images: Vec<Image<'a>>,
+
+    #[serde(skip_serializing)]
+    endpoint: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Serialize)]
+struct Payload<'a> {
+    summary: Cow<'a, str>,
+
+    source: Cow<'a, str>,
+
+    severity: Severity,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    timestamp: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    component: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    group: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    class: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    custom_details: Option<Details>,
+
+    #[serde(skip_serializing)]
+    details_format: DetailsFormat,
+}
+
+impl<'a> TriggerEvent<'a> {
+    /// Create a new trigger event payload
+    ///
+    /// * **routing_key**: The integration key of an Events API v2 integration.
+    ///
+    /// * **summary**: A brief text summary of the event, used to generate the summaries/titles
+    ///   of any associated alerts. The maximum length is 1024 characters.
+    ///
+    /// * **source**: The unique location of the affected system, preferably a hostname or FQDN.
+    pub fn new<S>(routing_key: S, summary: S, source: S, severity: Severity) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        TriggerEvent {
+            routing_key: routing_key.into(),
+            event_action: "trigger",
+            dedup_key: None,
+            payload: Payload {
+                summary: summary.into(),
+                source: source.into(),
+                severity,
+                timestamp: None,
+                component: None,
+                group: None,
+                class: None,
+                custom_details: None,
+                details_format: DetailsFormat::Structured,
+            },
+            client: None,
+            client_url: None,
+            links: Vec::new(),
+            images: Vec::new(),
+            endpoint: None,
+        }
+    }
+
+    /// Set dedup_key
+    ///
+    /// Deduplication key for correlating triggers and resolves. Events with the same dedup_key
+    /// are grouped into the same alert while it is open.
+    pub fn set_dedup_key<S>(mut self, dedup_key: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.dedup_key = Some(dedup_key.into());
+        self
+    }
+
+    /// Set the event's severity
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.payload.severity = severity;
+        self
+    }
+
+    /// The event's severity
+    pub fn severity(&self) -> Severity {
+        self.payload.severity
+    }
+
+    /// The broken component of the affected system, if one was set
+    pub fn component(&self) -> Option<&str> {
+        self.payload.component.as_deref()
+    }
+
+    /// Set the time at which the emitting tool detected or generated the event, in ISO 8601
+    pub fn set_timestamp<S>(mut self, timestamp: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.payload.timestamp = Some(timestamp.into_timestamp());
+        self
+    }
+
+    /// Set the part or component of the affected system that is broken
+    pub fn set_component<S>(mut self, component: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.payload.component = Some(component.into());
+        self
+    }
+
+    /// Set a cluster or grouping of sources, for example "prod-datapipe"
+    pub fn set_group<S>(mut self, group: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.payload.group = Some(group.into());
+        self
+    }
+
+    /// Set the class/type of the event, for example "High CPU"
+    pub fn set_class<S>(mut self, class: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.payload.class = Some(class.into());
+        self
+    }
+
+    /// Set event's client
+    ///
+    /// The name of the monitoring client that is triggering this event.
+    pub fn set_client<S>(mut self, client: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.client = Some(client.into());
+        self
+    }
+
+    /// Set event's client_url
+    ///
+    /// The URL of the monitoring client that is triggering this event.
+    pub fn set_client_url<S>(mut self, client_url: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.client_url = Some(client_url.into());
+        self
+    }
+
+    /// Set custom details
+    ///
+    /// Additional details about the event and affected system. Anything that implements
+    /// `Serialize` can be used; for an example, please see the similar
+    /// [`TriggerEvent::set_details`](../integration/struct.TriggerEvent.html#method.set_details)
+    /// of the integration API.
+    ///
+    /// # Panics
+    /// Panics if `serde_json::to_value` on details type returns an error.
+    pub fn set_custom_details<T>(mut self, details: &T) -> Self
+        where T: ?Sized + Serialize
+    {
+        let details = to_value(details).unwrap();
+        self.payload.custom_details = Some(Details::new(details, self.payload.details_format));
+        self
+    }
+
+    /// Set how custom details are encoded in the request
+    ///
+    /// Custom details are sent as a nested JSON object by default, and as a string containing the
+    /// JSON with `DetailsFormat::EscapedString`.
+    pub fn set_details_format(mut self, format: DetailsFormat) -> Self {
+        self.payload.details_format = format;
+        if let Some(ref mut details) = self.payload.custom_details {
+            details.format = format;
+        }
+        self
+    }
+
+    /// Attach a link to the alert
+    pub fn add_link(mut self, link: Link<'a>) -> Self {
+        self.links.push(link);
+        self
+    }
+
+    /// Attach an image to the alert
+    pub fn add_image(mut self, image: Image<'a>) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Send this event to `endpoint` rather than PagerDuty
+    ///
+    /// For events which must go through an event proxy, such as one deployed on premises. Other
+    /// events are unaffected, so each event can be routed individually.
+    pub fn set_endpoint<S>(mut self, endpoint: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+}
+
+/// A link attached to an alert
+#[derive(Debug, Serialize)]
+pub struct Link<'a> {
+    href: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    text: Option<Cow<'a, str>>,
+}
+
+impl<'a> Link<'a> {
+    /// Create a link to `href`, optionally described by `text`
+    pub fn new<S>(href: S, text: Option<S>) -> Link<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Link {
+            href: href.into(),
+            text: text.map(|s| s.into()),
+        }
+    }
+}
+
+/// An image attached to an alert
+#[derive(Debug, Serialize)]
+pub struct Image<'a> {
+    src: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    href: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    alt: Option<Cow<'a, str>>,
+}
+
+impl<'a> Image<'a> {
+    /// Create an image with source `src`, which must be served via HTTPS
+    pub fn new<S>(src: S, href: Option<S>, alt: Option<S>) -> Image<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Image {
+            src: src.into(),
+            href: href.map(|s| s.into()),
+            alt: alt.map(|s| s.into()),
+        }
+    }
+}
+
+macro_rules! shared_event_type {
+    { $(#[$attr:meta])* name => $name:ident; event_action => $event_action:expr } => {
+
+        $(#[$attr])*
+        #[derive(Debug, Serialize)]
+        pub struct $name<'a> {
+            routing_key: Cow<'a, str>,
+            event_action: &'static str,
+            dedup_key: Cow<'a, str>,
+
+            #[serde(skip_serializing)]
+            endpoint: Option<Cow<'a, str>>,
+        }
+
+        impl<'a> $name<'a> {
+            /// Create a new event
+            ///
+            /// * **routing_key**: The integration key of an Events API v2 integration.
+            ///
+            /// * **dedup_key**: Identifies the alert to act on. This should be the `dedup_key`
+            ///   of the trigger event that opened the alert.
+            pub fn new<S>(routing_key: S, dedup_key: S) -> Self
+                where S: Into<Cow<'a, str>>
+            {
+                $name {
+                    routing_key: routing_key.into(),
+                    event_action: $event_action,
+                    dedup_key: dedup_key.into(),
+                    endpoint: None,
+                }
+            }
+
+            /// Send this event to `endpoint` rather than PagerDuty
+            ///
+            /// See the similar method of
+            /// [`TriggerEvent`](struct.TriggerEvent.html#method.set_endpoint).
+            pub fn set_endpoint<S>(mut self, endpoint: S) -> Self
+                where S: Into<Cow<'a, str>>
+            {
+                self.endpoint = Some(endpoint.into());
+                self
+            }
+        }
+
+        impl<'a> Requestable for $name<'a> {
+            type Response = Response;
+
+            fn url<'b>(&'b self) -> Cow<'b, str> {
+                match self.endpoint {
+                    Some(ref endpoint) => endpoint.as_ref().into(),
+                    None => ENQUEUE_URL.into(),
+                }
+            }
+
+            fn body(&self) -> String {
+                to_string(&self).unwrap()
+            }
+
+            fn method(&self) -> Method {
+                Method::Post
+            }
+
+            fn get_response(status: StatusCode,
+                            headers: &Headers,
+                            body: &str) -> request::Result<Response> {
+                Response::get_response(status, headers, body)
+            }
+        }
+    }
+}
+
+shared_event_type! {
+    /// Cause the referenced alert to enter the resolved state.
+    name => ResolveEvent; event_action => "resolve"
+}
+
+shared_event_type! {
+    /// Cause the referenced alert to enter the acknowledged state.
+    name => AcknowledgeEvent; event_action => "acknowledge"
+}
+
+/// Response types from the v2 events API
+pub mod response {
+    /// If the request is invalid, PagerDuty will respond with HTTP code 400 and this object
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    pub struct BadRequest {
+        /// invalid event
+        pub status: String,
+
+        /// A description of the problem
+        pub message: String,
+
+        /// An array of specific error messages
+        #[serde(default)]
+        pub errors: Vec<String>,
+    }
+
+    /// If the event was accepted, PagerDuty will respond with HTTP code 202 and this object.
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    pub struct Success {
+        /// The string _"success"_
+        pub status: String,
+
+        /// Event processed
+        pub message: String,
+
+        /// The key of the alert that will be affected by the request.
+        pub dedup_key: String,
+    }
+}
+
+/// A Response from the v2 events API
+///
+/// A union of all possible responses for the v2 events API.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Response {
+    Success(response::Success),
+    BadRequest(response::BadRequest),
+
+    /// Too many events were sent; retry later, after `retry_after` if PagerDuty specified it
+    Throttled { retry_after: Option<Duration> },
+
+    InternalServerError(ServerError),
+}
+
+impl Response {
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response> {
+        // Server errors may come with an HTML body; classify them before any parsing happens.
+        if status.is_server_error() {
+            return Ok(Response::InternalServerError(ServerError::new(status, body)));
+        }
+
+        match status {
+            StatusCode::Accepted => {
+                let res: response::Success = from_str(body)?;
+                Ok(Response::Success(res))
+            },
+            StatusCode::BadRequest => {
+                let res: response::BadRequest = from_str(body)?;
+                Ok(Response::BadRequest(res))
+            },
+            StatusCode::TooManyRequests => {
+                Ok(Response::Throttled { retry_after: request::retry_after(headers) })
+            },
+            _ => Err(request::Error::UnexpectedApiResponse),
+        }
+    }
+}
+
+impl<'a> Requestable for TriggerEvent<'a> {
+    type Response = Response;
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        match self.endpoint {
+            Some(ref endpoint) => endpoint.as_ref().into(),
+            None => ENQUEUE_URL.into(),
+        }
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response> {
+        Response::get_response(status, headers, body)
+    }
+}
+
+/// Send a TriggerEvent request
+pub fn trigger(auth: &AuthToken, event: &TriggerEvent) -> request::Result<Response> {
+    request::perform(auth, event)
+}
+
+/// Send a ResolveEvent request
+pub fn resolve(auth: &AuthToken, event: &ResolveEvent) -> request::Result<Response> {
+    request::perform(auth, event)
+}
+
+/// Send an AcknowledgeEvent request
+pub fn acknowledge(auth: &AuthToken, event: &AcknowledgeEvent) -> request::Result<Response> {
+    request::perform(auth, event)
+}
+
+#[cfg(test)]
+mod tests {
+    use request::Requestable;
+    use super::{DetailsFormat, Image, Link, ResolveEvent, Severity, TriggerEvent};
+
+    use serde_json::{from_str, to_string, Value as Json};
+
+    #[test]
+    fn trigger_event_to_json() {
+        let expected: Json = from_str(stringify!({
+            "routing_key": "the routing key",
+            "event_action": "trigger",
+            "dedup_key": "KEY123",
+            "payload": {
+                "summary": "Houston, we have a problem",
+                "source": "db01.example.com",
+                "severity": "warning",
+                "component": "postgres"
+            },
+            "links": [{ "href": "https://www.example.com", "text": "a link" }],
+            "images": [{ "src": "https://www.example.com/graph.png" }]
+        })).expect("expected is valid json");
+
+        let event = TriggerEvent::new("the routing key",
+                                      "Houston, we have a problem",
+                                      "db01.example.com",
+                                      Severity::Warning)
+                        .set_dedup_key("KEY123")
+                        .set_component("postgres")
+                        .add_link(Link::new("https://www.example.com", Some("a link")))
+                        .add_image(Image::new("https://www.example.com/graph.png", None, None));
+
+        let json_string = to_string(&event).unwrap();
+        let actual: Json = from_str(&json_string).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn endpoint_override() {
+        let direct = ResolveEvent::new("the routing key", "KEY123");
+        let proxied = ResolveEvent::new("the routing key", "KEY123")
+                          .set_endpoint("https://pd-proxy.internal/v2/enqueue");
+
+        assert_eq!(direct.url(), "https://events.pagerduty.com/v2/enqueue");
+        assert_eq!(proxied.url(), "https://pd-proxy.internal/v2/enqueue");
+        assert_eq!(proxied.body(), direct.body());
+    }
+
+    #[test]
+    fn escaped_custom_details() {
+        let details: Json = from_str(r#"{"free space": "1%"}"#).unwrap();
+        let event = TriggerEvent::new("the routing key", "Disk full", "db01", Severity::Warning)
+                        .set_custom_details(&details)
+                        .set_details_format(DetailsFormat::EscapedString);
+
+        let actual: Json = from_str(&event.body()).unwrap();
+        assert_eq!(actual["payload"]["custom_details"], Json::String(details.to_string()));
+
+        let event = event.set_details_format(DetailsFormat::Structured);
+        let actual: Json = from_str(&event.body()).unwrap();
+        assert_eq!(actual["payload"]["custom_details"], details);
+    }
+}
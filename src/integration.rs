@@ -26,7 +26,8 @@
 //! reduce our throttle dynamically.
 //!
 //! If each of the events your monitoring system is sending is important, be sure to retry on a 403
-//! response code, preferably with a back off.
+//! response code, preferably with a back off. These responses are reported as
+//! `Response::Throttled`, the same variant the REST API uses for its 429 responses.
 //!
 //! # Response codes and Retry Logic
 //!
@@ -46,16 +47,21 @@
 //!
 
 use std::borrow::Cow;
+use std::time::Duration;
 
 use hyper::header::Headers;
 use hyper::method::Method;
 use hyper::status::StatusCode;
 
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 use serde_json::{from_str, to_string, to_value, Value as Json};
 
 use AuthToken;
-use request::{self, Requestable};
+use events_v2;
+use request::{self, Requestable, ServerError};
+
+/// URL events are sent to, unless overridden with `set_endpoint`
+const EVENT_URL: &str = "https://events.pagerduty.com/generic/2010-04-15/create_event.json";
 
 /// Event to report a new or ongoing problem.
 ///
@@ -79,10 +85,16 @@ pub struct TriggerEvent<'a> {
     client_url: Option<Cow<'a, str>>,
 
     #[serde(skip_serializing_if="Option::is_none")]
-    details: Option<Json>,
+    details: Option<Details>,
+
+    #[serde(skip_serializing)]
+    details_format: DetailsFormat,
 
     #[serde(skip_serializing_if="Vec::is_empty")]
     contexts: Vec<Context<'a>>,
+
+    #[serde(skip_serializing)]
+    endpoint: Option<Cow<'a, str>>,
 }
 
 impl<'a> TriggerEvent<'a> {
@@ -106,7 +118,9 @@ impl<'a> TriggerEvent<'a> {
             client: None,
             client_url: None,
             details: None,
+            details_format: DetailsFormat::Structured,
             contexts: Vec::new(),
+            endpoint: None,
         }
     }
 
@@ -176,10 +190,23 @@ impl<'a> TriggerEvent<'a> {
     /// ```
     /// # Panics
     /// Panics if `serde_json::to_value` on details type returns an error.
-    pub fn set_details<T: ?Sized>(mut self, details: &T) -> Self
-        where T: Serialize
+    pub fn set_details<T>(mut self, details: &T) -> Self
+        where T: ?Sized + Serialize
     {
-        self.details = Some(to_value(details).unwrap());
+        self.details = Some(Details::new(to_value(details).unwrap(), self.details_format));
+        self
+    }
+
+    /// Set how details are encoded in the request
+    ///
+    /// Details are sent as a nested JSON object by default. Some tools consuming incident data
+    /// expect them as a single string containing the JSON instead, which
+    /// `DetailsFormat::EscapedString` produces.
+    pub fn set_details_format(mut self, format: DetailsFormat) -> Self {
+        self.details_format = format;
+        if let Some(ref mut details) = self.details {
+            details.format = format;
+        }
         self
     }
 
@@ -194,6 +221,107 @@ impl<'a> TriggerEvent<'a> {
         self.contexts.push(context);
         self
     }
+
+    /// Send this event to `endpoint` rather than PagerDuty
+    ///
+    /// For events which must go through an event proxy, such as one deployed on premises. Other
+    /// events are unaffected, so each event can be routed individually.
+    pub fn set_endpoint<S>(mut self, endpoint: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+}
+
+/// How event details are encoded in the request body
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailsFormat {
+    /// Details are embedded as a JSON object; this is the default
+    Structured,
+
+    /// Details are encoded as JSON text, and embedded as a string
+    EscapedString,
+}
+
+/// Event details along with the format they should be sent in
+///
+/// This is shared with the custom details of Events API v2 triggers.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Details {
+    pub value: Json,
+    pub format: DetailsFormat,
+}
+
+impl Details {
+    pub fn new(value: Json, format: DetailsFormat) -> Details {
+        Details {
+            value,
+            format,
+        }
+    }
+}
+
+impl Serialize for Details {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match self.format {
+            DetailsFormat::Structured => self.value.serialize(serializer),
+            DetailsFormat::EscapedString => serializer.serialize_str(&self.value.to_string()),
+        }
+    }
+}
+
+/// Convert to the equivalent Events API v2 trigger
+///
+/// `service_key` becomes the routing key, `description` the summary, `incident_key` the dedup key,
+/// `details` the custom details (keeping the chosen `DetailsFormat`), and contexts become links
+/// and images. Version 2 events also require a source and severity which have no v1 equivalent;
+/// the source is taken from `client` (or `"unknown"` if unset) and the severity is
+/// `Severity::Error`. Both can be changed on the converted event. An endpoint set with
+/// `set_endpoint` is not carried over, since v2 events are sent to a different path.
+impl<'a> From<TriggerEvent<'a>> for events_v2::TriggerEvent<'a> {
+    fn from(event: TriggerEvent<'a>) -> events_v2::TriggerEvent<'a> {
+        let source = event.client.clone().unwrap_or(Cow::Borrowed("unknown"));
+        let mut converted = events_v2::TriggerEvent::new(event.service_key,
+                                                         event.description,
+                                                         source,
+                                                         events_v2::Severity::Error);
+
+        if let Some(incident_key) = event.incident_key {
+            converted = converted.set_dedup_key(incident_key);
+        }
+
+        if let Some(client) = event.client {
+            converted = converted.set_client(client);
+        }
+
+        if let Some(client_url) = event.client_url {
+            converted = converted.set_client_url(client_url);
+        }
+
+        converted = converted.set_details_format(event.details_format);
+        if let Some(ref details) = event.details {
+            converted = converted.set_custom_details(&details.value);
+        }
+
+        for context in event.contexts {
+            converted = match context.context_type {
+                "image" => {
+                    let src = context.src.unwrap_or(Cow::Borrowed(""));
+                    converted.add_image(events_v2::Image::new(src, context.href, context.alt))
+                },
+                _ => {
+                    let href = context.href.unwrap_or(Cow::Borrowed(""));
+                    converted.add_link(events_v2::Link::new(href, context.text))
+                },
+            };
+        }
+
+        converted
+    }
 }
 
 /// An informational asset attached to the incident
@@ -270,19 +398,25 @@ macro_rules! shared_event_type {
             description: Option<Cow<'a, str>>,
 
             #[serde(skip_serializing_if="Option::is_none")]
-            details: Option<Json>,
+            details: Option<Details>,
+
+            #[serde(skip_serializing)]
+            details_format: DetailsFormat,
+
+            #[serde(skip_serializing)]
+            endpoint: Option<Cow<'a, str>>,
         }
 
         impl<'a> $name<'a> {
             /// Create a new event
             ///
             /// * **service_key**: The GUID of one of your "Events API" services. This is the
-            /// "service key" listed on a Generic API's service detail page.
+            ///   "service key" listed on a Generic API's service detail page.
             ///
             /// * **incident_key**: Identifies the incident to resolve. This should be the
-            /// `incident_key` you received back when the incident was first opened by a trigger
-            /// event. Resolve events referencing resolved or nonexistent incidents will be
-            /// discarded.
+            ///   `incident_key` you received back when the incident was first opened by a trigger
+            ///   event. Resolve events referencing resolved or nonexistent incidents will be
+            ///   discarded.
             pub fn new<S>(service_key: S, incident_key: S) -> Self
                 where S: Into<Cow<'a, str>>
             {
@@ -292,6 +426,8 @@ macro_rules! shared_event_type {
                     incident_key: incident_key.into(),
                     description: None,
                     details: None,
+                    details_format: DetailsFormat::Structured,
+                    endpoint: None,
                 }
             }
 
@@ -305,10 +441,23 @@ macro_rules! shared_event_type {
             ///
             /// # Panics
             /// Panics if `serde_json::to_value` on details type returns an error.
-            pub fn set_details<T: ?Sized>(mut self, details: &T) -> Self
-                where T: Serialize
+            pub fn set_details<T>(mut self, details: &T) -> Self
+                where T: ?Sized + Serialize
             {
-                self.details = Some(to_value(details).unwrap());
+                let details = to_value(details).unwrap();
+                self.details = Some(Details::new(details, self.details_format));
+                self
+            }
+
+            /// Set how details are encoded in the request
+            ///
+            /// See the similar method of
+            /// [`TriggerEvent`](struct.TriggerEvent.html#method.set_details_format).
+            pub fn set_details_format(mut self, format: DetailsFormat) -> Self {
+                self.details_format = format;
+                if let Some(ref mut details) = self.details {
+                    details.format = format;
+                }
                 self
             }
 
@@ -319,11 +468,29 @@ macro_rules! shared_event_type {
                 self.description = Some(description.into());
                 self
             }
+
+            /// Send this event to `endpoint` rather than PagerDuty
+            ///
+            /// See the similar method of
+            /// [`TriggerEvent`](struct.TriggerEvent.html#method.set_endpoint).
+            pub fn set_endpoint<S>(mut self, endpoint: S) -> Self
+                where S: Into<Cow<'a, str>>
+            {
+                self.endpoint = Some(endpoint.into());
+                self
+            }
         }
 
         impl<'a> Requestable for $name<'a> {
             type Response = Response;
 
+            fn url<'b>(&'b self) -> Cow<'b, str> {
+                match self.endpoint {
+                    Some(ref endpoint) => endpoint.as_ref().into(),
+                    None => EVENT_URL.into(),
+                }
+            }
+
             fn body(&self) -> String {
                 to_string(&self).unwrap()
             }
@@ -398,33 +565,35 @@ pub mod response {
 pub enum Response {
     Success(response::Success),
     BadRequest(response::BadRequest),
-    Forbidden,
-    InternalServerError,
+
+    /// Too many events were sent; retry later, after `retry_after` if PagerDuty specified it
+    Throttled { retry_after: Option<Duration> },
+
+    InternalServerError(ServerError),
 }
 
 impl Response {
     fn get_response(status: StatusCode,
-                    _headers: &Headers,
+                    headers: &Headers,
                     body: &str) -> request::Result<Response> {
+        // Server errors may come with an HTML body; classify them before any parsing happens.
+        if status.is_server_error() {
+            return Ok(Response::InternalServerError(ServerError::new(status, body)));
+        }
+
         match status {
             StatusCode::Ok => {
-                let res: response::Success = try!(from_str(body));
+                let res: response::Success = from_str(body)?;
                 Ok(Response::Success(res))
             },
             StatusCode::BadRequest => {
-                let res: response::BadRequest = try!(from_str(body));
+                let res: response::BadRequest = from_str(body)?;
                 Ok(Response::BadRequest(res))
             },
             StatusCode::Forbidden => {
-                Ok(Response::Forbidden)
+                Ok(Response::Throttled { retry_after: request::retry_after(headers) })
             },
-            _ => {
-                if status.is_server_error() {
-                    Ok(Response::InternalServerError)
-                } else {
-                    Err(request::Error::UnexpectedApiResponse)
-                }
-            }
+            _ => Err(request::Error::UnexpectedApiResponse),
         }
     }
 }
@@ -432,6 +601,13 @@ impl Response {
 impl<'a> Requestable for TriggerEvent<'a> {
     type Response = Response;
 
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        match self.endpoint {
+            Some(ref endpoint) => endpoint.as_ref().into(),
+            None => EVENT_URL.into(),
+        }
+    }
+
     fn body(&self) -> String {
         to_string(&self).unwrap()
     }
@@ -465,10 +641,28 @@ pub fn acknowledge(auth: &AuthToken, event: &AcknowledgeEvent) -> request::Resul
 
 #[cfg(test)]
 mod tests {
-    use super::{TriggerEvent, Context};
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use events_v2;
+    use super::{TriggerEvent, Context, DetailsFormat, ResolveEvent, Response};
 
     use serde_json::{from_str, to_string, Value as Json};
 
+    #[test]
+    fn html_server_error_is_not_parsed() {
+        let body = "<html><body><h1>502 Bad Gateway</h1></body></html>";
+        let response = Response::get_response(StatusCode::BadGateway, &Headers::new(), body);
+
+        match response {
+            Ok(Response::InternalServerError(err)) => {
+                assert_eq!(err.status, StatusCode::BadGateway);
+                assert_eq!(err.body_snippet, body);
+            },
+            other => panic!("expected server error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn context_to_json() {
         let expected: Json = from_str(stringify!({
@@ -499,6 +693,61 @@ mod tests {
     }
 
 
+    #[test]
+    fn details_as_escaped_string_to_json() {
+        let expected: Json = from_str(stringify!({
+            "event_type": "resolve",
+            "service_key": "the service key",
+            "incident_key": "KEY123",
+            "details": "{\"count\":1}"
+        })).expect("expected is valid json");
+
+        let mut details = ::std::collections::BTreeMap::new();
+        details.insert("count", 1);
+
+        let event = ResolveEvent::new("the service key", "KEY123")
+                        .set_details(&details)
+                        .set_details_format(DetailsFormat::EscapedString);
+        let actual: Json = from_str(&to_string(&event).unwrap()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn trigger_event_to_events_v2() {
+        let expected: Json = from_str(stringify!({
+            "routing_key": "the service key",
+            "event_action": "trigger",
+            "dedup_key": "KEY123",
+            "client": "Nagios",
+            "payload": {
+                "summary": "Houston, we have a problem",
+                "source": "Nagios",
+                "severity": "error",
+                "custom_details": { "ping time": "1500ms" }
+            },
+            "links": [{ "href": "https://www.example.com", "text": "a link" }],
+            "images": [{ "src": "https://www.example.com/graph.png" }]
+        })).expect("expected is valid json");
+
+        let mut details = ::std::collections::BTreeMap::new();
+        details.insert("ping time", "1500ms");
+
+        let event = TriggerEvent::new("the service key", "Houston, we have a problem")
+                        .set_incident_key("KEY123")
+                        .set_client("Nagios")
+                        .set_details(&details)
+                        .add_context(Context::link("https://www.example.com", "a link"))
+                        .add_context(Context::image("https://www.example.com/graph.png",
+                                                    None,
+                                                    None));
+
+        let converted = events_v2::TriggerEvent::from(event);
+        let actual: Json = from_str(&to_string(&converted).unwrap()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn trigger_event_with_contexts_to_json() {
         #[derive(Debug, Serialize)]
@@ -542,6 +791,7 @@ mod tests {
     }
 }
 
+#[cfg(test)]
 mod live_tests {
     use AuthToken;
 
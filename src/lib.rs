@@ -13,23 +13,41 @@
 //! The following APIs are **supported**
 //!
 //! * Integration API
+//! * Events API v2
+//! * Webhooks (V2 and V3), and metrics of the incidents they report
+//! * OAuth, with automatic refreshing of access tokens, and scoped app tokens
+//! * REST API (partial)
+//!     * Analytics of incidents and responders
+//!     * Audit records of users, teams, schedules, escalation policies, and services
+//!     * Automation actions and runners, and invoking actions on incidents
+//!     * Business services, their subscribers, and impact
+//!     * Custom fields of incidents, and their options
+//!     * Escalation policies
+//!     * Event orchestrations, with router and unrouted rules
+//!     * Extensions and extension schemas
+//!     * Incidents and updating them, their alerts, notes, status updates, and past incidents,
+//!       and gathering them into postmortem bundles
+//!     * Incident workflows, their triggers, and starting them
+//!     * Licenses and their allocations
+//!     * Log entries of the account or of an incident
+//!     * Notification subscriptions of users and teams
+//!     * On-calls, and previewing who an event would notify
+//!     * Priorities, and assigning them by the severity and component of events
+//!     * Response plays, and running them
+//!     * Rulesets and event rules
+//!     * Schedules and overrides
+//!     * Services and their integrations
+//!     * Status dashboards and their impacted business services
+//!     * Tags of users, teams, and escalation policies
+//!     * Team membership
+//!     * Users, contact methods, notification rules, and sessions
+//!     * Webhook subscriptions, and testing and re-enabling them
 //!
 //! The following APIs are **unsupported**
 //!
-//! * Alerts
-//! * Escalation Policies
-//! * Incidents
-//! * Log Entries
 //! * Maintenance Windows
 //! * Reports
-//! * Schedules
-//! * Services
-//! * Users
-//! * Teams
-//!
-//! Additionally, the following features are unsupported
-//!
-//! * Webhooks
+//! * Teams, other than managing their membership
 //!
 //! If you are interested in using this library and the feature you want is not yet implemented,
 //! please file an issue on this project's repository. Features will be implemented on a
@@ -39,25 +57,47 @@
 //!
 //! There are a few things to know that might ease getting started with this library.
 //!
+//! * Most applications can get everything they need with `use pagerduty::prelude::*`.
 //! * Request types store string values as `Cow<'a, str>`, and setters for these properties accept
-//! `Into<Cow<'a, str>>` to keep the API ergononmic.
+//!   `Into<Cow<'a, str>>` to keep the API ergononmic.
+//! * Setters taking a time accept an ISO 8601 string, or a `chrono::DateTime` with the `chrono`
+//!   feature. See the `timestamp` module.
+//! * The optional features compiled in can be checked at startup with `capabilities`.
 //! * Parts of the API (specifically, integration event `set_details`) let you provide arbitrary
-//! data with the request. Any structured type that implements `Serialize` can be used in these
-//! cases. There's currently no enforcement of the _structured_ part of that contract. If you do not
-//! uphold that constaint, you will probably get a `BadRequest` response.
+//!   data with the request. Any structured type that implements `Serialize` can be used in these
+//!   cases. There's currently no enforcement of the _structured_ part of that contract. If you do
+//!   not uphold that constaint, you will probably get a `BadRequest` response.
 //!
 #[macro_use]
 extern crate serde_derive;
 
+extern crate hmac;
 extern crate hyper;
 extern crate serde;
 extern crate serde_json;
+extern crate sha2;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "zeroize")]
+extern crate zeroize;
 
+pub mod events_v2;
 pub mod integration;
+pub mod oauth;
+pub mod prelude;
+pub mod rest;
+pub mod timestamp;
+pub mod webhooks;
 
 mod auth;
 pub use auth::*;
 
+mod capabilities;
+pub use capabilities::{capabilities, Capabilities, Feature, MissingFeature};
+
 mod request;
 
-pub use request::{Result, Error, Requestable};
+pub use request::{Result, Error, Requestable, ServerError};
+pub use request::{perform, perform_with_limits, Limits, WithHeaders};
+pub use request::{set_deprecation_handler, DeprecationNotice};
+pub use request::{quota, Quota, RateLimit};
@@ -0,0 +1,570 @@
+//! OAuth 2.0
+//!
+//! Apps which access the accounts of other PagerDuty customers are authorized with OAuth. The
+//! customer is sent to the [`authorize_url`](struct.App.html#method.authorize_url) of the app,
+//! and PagerDuty redirects them back to the app's redirect URI with a code. The code is exchanged
+//! for an access token with [`exchange_code`](struct.App.html#method.exchange_code).
+//!
+//! Access tokens expire, and are renewed with the refresh token granted along with them.
+//! [`RefreshingToken`](struct.RefreshingToken.html) does this automatically, and hands out the
+//! access token as an [`AuthToken`](../struct.AuthToken.html) usable with every REST request.
+//!
+//! Server-to-server integrations use scoped app tokens instead, which an app requests for its own
+//! account with [`client_credentials`](struct.App.html#method.client_credentials). App tokens
+//! aren't refreshed but requested again when they expire, which a `RefreshingToken` created with
+//! `RefreshingToken::from_client_credentials` does automatically.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use pagerduty::oauth::{self, App, RefreshingToken};
+//! use pagerduty::rest::services::{self, ListServices};
+//!
+//! let app = App::new("client id", "client secret", "https://example.com/pagerduty/callback");
+//! println!("Authorize the app at {}", app.authorize_url(&["read"], "random state"));
+//!
+//! // Once PagerDuty redirects back to the app with a code
+//! # let code = "";
+//! let grant = match app.exchange_code(code).unwrap() {
+//!     oauth::Response::Success(grant) => grant,
+//!     other => panic!("Failed to exchange code: {:?}", other),
+//! };
+//!
+//! let mut token = RefreshingToken::new(app, grant);
+//! if let oauth::Response::Success(auth) = token.auth_token().unwrap() {
+//!     services::list(&auth, &ListServices::new()).unwrap();
+//! }
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::mem;
+use std::time::{Duration, Instant};
+
+use hyper::header::{ContentType, Headers};
+use hyper::method::Method;
+use hyper::status::StatusCode;
+use serde_json;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use {AuthScheme, AuthToken};
+use request::{self, Limits, Requestable, ServerError};
+use rest::Query;
+
+/// The URL customers authorize apps at
+pub const AUTHORIZE_URL: &str = "https://identity.pagerduty.com/oauth/authorize";
+
+/// The URL tokens are requested from
+pub const TOKEN_URL: &str = "https://identity.pagerduty.com/oauth/token";
+
+/// How long before it expires an access token is refreshed, in seconds
+const REFRESH_MARGIN: u64 = 60;
+
+/// An OAuth app registered with PagerDuty
+///
+/// With the `zeroize` feature, the client secret is overwritten in memory when dropped.
+#[derive(Clone)]
+pub struct App {
+    client_id: String,
+    client_secret: String,
+    redirect_uri: Option<String>,
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for App {
+    fn drop(&mut self) {
+        self.client_secret.zeroize();
+    }
+}
+
+impl App {
+    /// Create an app with the credentials PagerDuty issued for it
+    ///
+    /// `redirect_uri` must be one of the redirect URLs registered for the app.
+    pub fn new<I, S, R>(client_id: I, client_secret: S, redirect_uri: R) -> App
+        where I: Into<String>,
+              S: Into<String>,
+              R: Into<String>
+    {
+        App {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: Some(redirect_uri.into()),
+        }
+    }
+
+    /// Create an app which only requests scoped app tokens, and so has no redirect URI
+    pub fn without_redirect<I, S>(client_id: I, client_secret: S) -> App
+        where I: Into<String>,
+              S: Into<String>
+    {
+        App {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            redirect_uri: None,
+        }
+    }
+
+    /// The app's client ID
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// The URL to send a customer to for them to authorize the app
+    ///
+    /// `scopes` are the scopes requested, such as `"read"` or `"incidents.write"`. `state` is
+    /// passed back to the redirect URI unchanged, and should be checked there to guard against
+    /// forged redirects.
+    pub fn authorize_url<I, S>(&self, scopes: I, state: &str) -> String
+        where I: IntoIterator<Item = S>,
+              S: AsRef<str>
+    {
+        format!("{}?{}", AUTHORIZE_URL, form(&[("client_id", &self.client_id),
+                                               ("redirect_uri", self.redirect_uri()),
+                                               ("response_type", "code"),
+                                               ("scope", &join_scopes(scopes)),
+                                               ("state", state)]))
+    }
+
+    /// Exchange the code PagerDuty redirected back with for an access token
+    pub fn exchange_code(&self, code: &str) -> request::Result<Response<TokenGrant>> {
+        request_token(form(&[("grant_type", "authorization_code"),
+                             ("client_id", &self.client_id),
+                             ("client_secret", &self.client_secret),
+                             ("redirect_uri", self.redirect_uri()),
+                             ("code", code)]))
+    }
+
+    /// Exchange a refresh token for a new access token
+    pub fn refresh(&self, refresh_token: &str) -> request::Result<Response<TokenGrant>> {
+        request_token(form(&[("grant_type", "refresh_token"),
+                             ("client_id", &self.client_id),
+                             ("client_secret", &self.client_secret),
+                             ("refresh_token", refresh_token)]))
+    }
+
+    /// Request a scoped app token for the app's own account
+    ///
+    /// `scopes` must include the [`account_scope`](fn.account_scope.html) of the account, along
+    /// with the scopes of the endpoints used, such as `"incidents.read"`.
+    pub fn client_credentials<I, S>(&self, scopes: I) -> request::Result<Response<TokenGrant>>
+        where I: IntoIterator<Item = S>,
+              S: AsRef<str>
+    {
+        self.request_app_token(&join_scopes(scopes))
+    }
+
+    fn request_app_token(&self, scope: &str) -> request::Result<Response<TokenGrant>> {
+        request_token(form(&[("grant_type", "client_credentials"),
+                             ("client_id", &self.client_id),
+                             ("client_secret", &self.client_secret),
+                             ("scope", scope)]))
+    }
+
+    fn redirect_uri(&self) -> &str {
+        self.redirect_uri.as_ref().map(|uri| &uri[..]).unwrap_or("")
+    }
+}
+
+/// The scope granting an app token access to account `subdomain` in service region `region`
+///
+/// For example, `account_scope("us", "acme")` is `as_account-us.acme` for the account at
+/// `acme.pagerduty.com`. The region is `eu` for accounts hosted in the EU.
+pub fn account_scope(region: &str, subdomain: &str) -> String {
+    format!("as_account-{}.{}", region, subdomain)
+}
+
+/// Tokens granted to an app
+///
+/// The tokens are redacted when formatted with `Debug`. With the `zeroize` feature, they're also
+/// overwritten in memory when dropped.
+#[derive(Clone, Deserialize, PartialEq, Eq)]
+pub struct TokenGrant {
+    /// The token to authorize requests with
+    pub access_token: String,
+
+    /// The kind of token, which is always `bearer`
+    #[serde(default)]
+    pub token_type: Option<String>,
+
+    /// The number of seconds until the access token expires
+    #[serde(default)]
+    pub expires_in: Option<u64>,
+
+    /// The token to request a new access token with once this one expires
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+
+    /// The scopes granted, separated by spaces
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+impl fmt::Debug for TokenGrant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TokenGrant")
+            .field("access_token", &"[REDACTED]")
+            .field("token_type", &self.token_type)
+            .field("expires_in", &self.expires_in)
+            .field("refresh_token", &self.refresh_token.as_ref().map(|_| "[REDACTED]"))
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for TokenGrant {
+    fn drop(&mut self) {
+        self.access_token.zeroize();
+        self.refresh_token.zeroize();
+    }
+}
+
+/// A response from the token endpoint
+#[derive(Debug, PartialEq, Eq)]
+pub enum Response<T> {
+    Success(T),
+
+    /// The request was refused, such as because the code or refresh token has expired
+    Rejected(OAuthError),
+
+    InternalServerError(ServerError),
+}
+
+impl<T> Response<T> {
+    /// Transform the value contained in a `Success` response
+    pub fn map<U, F>(self, f: F) -> Response<U>
+        where F: FnOnce(T) -> U
+    {
+        match self {
+            Response::Success(value) => Response::Success(f(value)),
+            Response::Rejected(err) => Response::Rejected(err),
+            Response::InternalServerError(err) => Response::InternalServerError(err),
+        }
+    }
+
+    /// Get the success value, if any
+    pub fn success(self) -> Option<T> {
+        match self {
+            Response::Success(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// The reason a token request was refused
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct OAuthError {
+    /// The error code, such as `invalid_grant`
+    pub error: String,
+
+    /// A human-readable description of the error
+    #[serde(default)]
+    pub error_description: Option<String>,
+}
+
+/// An access token which is renewed when it expires
+///
+/// Tokens granted for an authorization code are renewed with their refresh token. The tokens can
+/// be saved with [`access_token`](#method.access_token) and
+/// [`refresh_token`](#method.refresh_token), and restored by creating a `TokenGrant` from them.
+/// Scoped app tokens are requested again with the same scopes.
+///
+/// With the `zeroize` feature, the tokens are overwritten in memory when replaced or dropped.
+pub struct RefreshingToken {
+    app: App,
+    access_token: String,
+    renewal: Renewal,
+    expires_at: Option<Instant>,
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for RefreshingToken {
+    fn drop(&mut self) {
+        self.access_token.zeroize();
+        if let Renewal::RefreshToken(ref mut refresh_token) = self.renewal {
+            refresh_token.zeroize();
+        }
+    }
+}
+
+/// How an access token is renewed
+enum Renewal {
+    RefreshToken(Option<String>),
+
+    /// Request a new app token with these scopes
+    ClientCredentials(String),
+}
+
+impl RefreshingToken {
+    /// Create a token from the tokens granted to `app` for an authorization code
+    pub fn new(app: App, grant: TokenGrant) -> RefreshingToken {
+        let mut token = RefreshingToken {
+            app,
+            access_token: String::new(),
+            renewal: Renewal::RefreshToken(None),
+            expires_at: None,
+        };
+
+        token.update(grant);
+        token
+    }
+
+    /// Request a scoped app token for `app`, which is requested again when it expires
+    ///
+    /// See [`App::client_credentials`](struct.App.html#method.client_credentials) for `scopes`.
+    pub fn from_client_credentials<I, S>(app: App,
+                                         scopes: I) -> request::Result<Response<RefreshingToken>>
+        where I: IntoIterator<Item = S>,
+              S: AsRef<str>
+    {
+        let scope = join_scopes(scopes);
+        let res = app.request_app_token(&scope)?;
+
+        Ok(res.map(|grant| {
+            let mut token = RefreshingToken {
+                app,
+                access_token: String::new(),
+                renewal: Renewal::ClientCredentials(scope),
+                expires_at: None,
+            };
+
+            token.update(grant);
+            token
+        }))
+    }
+
+    /// The current access token
+    pub fn access_token(&self) -> &str {
+        &self.access_token
+    }
+
+    /// The current refresh token, if one was granted
+    pub fn refresh_token(&self) -> Option<&str> {
+        match self.renewal {
+            Renewal::RefreshToken(Some(ref token)) => Some(token),
+            _ => None,
+        }
+    }
+
+    /// Whether the access token has expired, or is about to
+    pub fn is_expired(&self) -> bool {
+        match self.expires_at {
+            Some(expires_at) => Instant::now() + Duration::from_secs(REFRESH_MARGIN) >= expires_at,
+            None => false,
+        }
+    }
+
+    /// Request a new access token, with the refresh token or the app's credentials
+    ///
+    /// This is rejected with `invalid_grant` if no refresh token was granted.
+    pub fn refresh(&mut self) -> request::Result<Response<()>> {
+        let res = match self.renewal {
+            Renewal::RefreshToken(Some(ref refresh_token)) => {
+                self.app.refresh(refresh_token)?
+            },
+            Renewal::ClientCredentials(ref scope) => self.app.request_app_token(scope)?,
+            Renewal::RefreshToken(None) => {
+                return Ok(Response::Rejected(OAuthError {
+                    error: "invalid_grant".to_owned(),
+                    error_description: Some("No refresh token was granted".to_owned()),
+                }));
+            },
+        };
+
+        Ok(res.map(|grant| self.update(grant)))
+    }
+
+    /// Get an `AuthToken` for the access token, renewing it first if it has expired
+    ///
+    /// Expired tokens without a refresh token are returned as is.
+    pub fn auth_token(&mut self) -> request::Result<Response<AuthToken<'static>>> {
+        let renewable = match self.renewal {
+            Renewal::RefreshToken(ref token) => token.is_some(),
+            Renewal::ClientCredentials(_) => true,
+        };
+
+        if self.is_expired() && renewable {
+            match self.refresh()? {
+                Response::Success(()) => (),
+                Response::Rejected(err) => return Ok(Response::Rejected(err)),
+                Response::InternalServerError(err) => {
+                    return Ok(Response::InternalServerError(err));
+                },
+            }
+        }
+
+        let token = AuthToken::with_scheme(self.access_token.clone(), AuthScheme::Bearer);
+        Ok(Response::Success(token))
+    }
+
+    fn update(&mut self, mut grant: TokenGrant) {
+        wipe(&mut self.access_token);
+        self.access_token = mem::take(&mut grant.access_token);
+        self.expires_at = grant.expires_in.map(|secs| Instant::now() + Duration::from_secs(secs));
+
+        // A new refresh token is only granted when the old one is replaced
+        if let Renewal::RefreshToken(ref mut refresh_token) = self.renewal {
+            if grant.refresh_token.is_some() {
+                if let Some(ref mut old) = *refresh_token {
+                    wipe(old);
+                }
+                *refresh_token = grant.refresh_token.take();
+            }
+        }
+    }
+}
+
+/// Overwrite a token about to be replaced, with the `zeroize` feature
+#[cfg(feature = "zeroize")]
+fn wipe(token: &mut String) {
+    token.zeroize();
+}
+
+#[cfg(not(feature = "zeroize"))]
+fn wipe(_token: &mut String) {}
+
+fn join_scopes<I, S>(scopes: I) -> String
+    where I: IntoIterator<Item = S>,
+          S: AsRef<str>
+{
+    let scopes: Vec<String> = scopes.into_iter().map(|s| s.as_ref().to_owned()).collect();
+    scopes.join(" ")
+}
+
+/// Encode `params` as an `application/x-www-form-urlencoded` string
+fn form(params: &[(&str, &str)]) -> String {
+    let mut query = Query::new();
+
+    for &(key, value) in params {
+        query.push(key, value);
+    }
+
+    query.as_str().to_owned()
+}
+
+/// A request to the token endpoint, with its form encoded parameters
+#[derive(Debug)]
+struct TokenRequest {
+    form: String,
+}
+
+impl Requestable for TokenRequest {
+    type Response = Response<TokenGrant>;
+
+    fn headers(&self) -> Headers {
+        let mut headers = Headers::new();
+        headers.set(ContentType::form_url_encoded());
+        headers
+    }
+
+    fn url<'a>(&'a self) -> Cow<'a, str> {
+        TOKEN_URL.into()
+    }
+
+    fn body(&self) -> String {
+        self.form.clone()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    _headers: &Headers,
+                    body: &str) -> request::Result<Response<TokenGrant>> {
+        get_token_response(status, body)
+    }
+}
+
+fn request_token(form: String) -> request::Result<Response<TokenGrant>> {
+    request::perform_unauthorized(&TokenRequest { form }, &Limits::default())
+}
+
+fn get_token_response(status: StatusCode, body: &str) -> request::Result<Response<TokenGrant>> {
+    if status.is_success() {
+        return Ok(Response::Success(serde_json::from_str(body)?));
+    }
+
+    if status.is_server_error() {
+        return Ok(Response::InternalServerError(ServerError::new(status, body)));
+    }
+
+    match serde_json::from_str(body) {
+        Ok(err) => Ok(Response::Rejected(err)),
+        Err(_) => Err(request::Error::UnexpectedApiResponse),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::{ContentType, Headers};
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use super::{account_scope, form, get_token_response, App, RefreshingToken, Response,
+                TokenRequest};
+
+    #[test]
+    fn token_request() {
+        let request = TokenRequest {
+            form: form(&[("grant_type", "refresh_token"), ("refresh_token", "abc")]),
+        };
+
+        assert_eq!(request.url(), "https://identity.pagerduty.com/oauth/token");
+        assert_eq!(request.body(), "grant_type=refresh_token&refresh_token=abc");
+        assert_eq!(request.headers().get::<ContentType>(),
+                   Some(&ContentType::form_url_encoded()));
+
+        let res = TokenRequest::get_response(StatusCode::InternalServerError,
+                                             &Headers::new(),
+                                             "<html>")
+            .unwrap();
+        assert!(res.success().is_none());
+    }
+
+    #[test]
+    fn authorize_url() {
+        let app = App::new("abc", "secret", "https://example.com/callback");
+
+        assert_eq!(app.authorize_url(["read", "incidents.write"], "xyz"),
+                   "https://identity.pagerduty.com/oauth/authorize?client_id=abc\
+                    &redirect_uri=https%3A%2F%2Fexample.com%2Fcallback&response_type=code\
+                    &scope=read%20incidents.write&state=xyz");
+
+        assert_eq!(account_scope("eu", "acme"), "as_account-eu.acme");
+    }
+
+    #[test]
+    fn token_responses() {
+        let body = stringify!({
+            "access_token": "pdus+_0XBPWQQ_abc",
+            "token_type": "bearer",
+            "expires_in": 0,
+            "refresh_token": "pdus+_1XBPWQQ_def",
+            "scope": "read"
+        });
+
+        let grant = get_token_response(StatusCode::Ok, body).unwrap().success().unwrap();
+        assert_eq!(grant.expires_in, Some(0));
+
+        let body = r#"{"error":"invalid_grant","error_description":"Code has expired"}"#;
+        match get_token_response(StatusCode::BadRequest, body).unwrap() {
+            Response::Rejected(ref err) if err.error == "invalid_grant" => (),
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        let app = App::new("abc", "secret", "https://example.com/callback");
+        let mut grant = grant;
+        grant.refresh_token = None;
+
+        let mut token = RefreshingToken::new(app, grant);
+        assert!(token.is_expired());
+
+        // Without a refresh token, the expired token is returned as is
+        let auth = token.auth_token().unwrap().success().unwrap();
+        assert_eq!(auth.to_header().0, "Bearer pdus+_0XBPWQQ_abc");
+    }
+}
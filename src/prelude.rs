@@ -0,0 +1,27 @@
+//! Commonly used items, for glob importing
+//!
+//! The prelude brings in the types most applications need to send events and make REST requests,
+//! along with the traits providing request builder methods such as `set_limit`.
+//!
+//! ```no_run
+//! use pagerduty::prelude::*;
+//!
+//! let auth = AuthToken::new("token");
+//! let event = TriggerEvent::new("routing key", "The server is on fire", "db-1",
+//!                               Severity::Critical);
+//!
+//! events_v2::trigger(&auth, &event).unwrap();
+//!
+//! let services = rest::services::ListServices::new().set_limit(10);
+//! rest::services::list(&auth, &services).unwrap();
+//! ```
+//!
+//! Only version 2 of the events API is included, since its event types share names with those of
+//! the [integration API](../integration/index.html).
+
+pub use AuthToken;
+pub use request::Requestable;
+pub use events_v2::{self, AcknowledgeEvent, ResolveEvent, Severity, TriggerEvent};
+pub use rest::{self, ListRequest, Page, Reference};
+pub use rest::incidents::{AlertStatus, IncidentStatus};
+pub use rest::services::ServiceStatus;
@@ -4,6 +4,9 @@
 //! reference the all but ubiquitous hyper::header module.
 use std::borrow::Cow;
 use std::io::{self, Read};
+use std::str;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use hyper::header::{self, Headers, UserAgent};
 use hyper::method::Method;
@@ -38,6 +41,89 @@ pub trait Requestable {
 
     /// HTTP Method for current request
     fn method(&self) -> Method;
+
+    /// Send extra headers along with this request
+    ///
+    /// See [`WithHeaders`](struct.WithHeaders.html).
+    fn with_headers(&self, headers: Headers) -> WithHeaders<'_, Self>
+        where Self: Sized
+    {
+        WithHeaders::new(self, headers)
+    }
+}
+
+/// A request with extra headers attached
+///
+/// Useful when outbound traffic goes through a gateway which requires its own headers, for
+/// example to authenticate. The extra headers are added to those of the wrapped request, replacing
+/// any of the same name. `Authorization` and `User-Agent` are always set by
+/// [`perform`](fn.perform.html) and can't be overridden.
+///
+/// Since the free functions of each API module only accept their own request types, send these
+/// with [`perform`](fn.perform.html).
+///
+/// # Example
+///
+/// ```no_run
+/// # extern crate hyper;
+/// # extern crate pagerduty;
+/// use hyper::header::Headers;
+/// use pagerduty::{AuthToken, Requestable};
+/// use pagerduty::integration::TriggerEvent;
+///
+/// # fn main() {
+///
+/// let auth = AuthToken::new("your_token");
+/// let event = TriggerEvent::new("your_service_key", "Disk is full");
+///
+/// let mut headers = Headers::new();
+/// headers.set_raw("X-Gateway-Auth", vec![b"secret".to_vec()]);
+///
+/// pagerduty::perform(&auth, &event.with_headers(headers)).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct WithHeaders<'a, R: 'a> {
+    inner: &'a R,
+    headers: Headers,
+}
+
+impl<'a, R: Requestable> WithHeaders<'a, R> {
+    /// Attach `headers` to `inner`
+    pub fn new(inner: &'a R, headers: Headers) -> WithHeaders<'a, R> {
+        WithHeaders {
+            inner,
+            headers,
+        }
+    }
+}
+
+impl<'a, R: Requestable> Requestable for WithHeaders<'a, R> {
+    type Response = R::Response;
+
+    fn headers(&self) -> Headers {
+        let mut headers = self.inner.headers();
+        headers.extend(self.headers.iter());
+        headers
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        self.inner.url()
+    }
+
+    fn body(&self) -> String {
+        self.inner.body()
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> Result<R::Response> {
+        R::get_response(status, headers, body)
+    }
+
+    fn method(&self) -> Method {
+        self.inner.method()
+    }
 }
 
 /// Possible errors making an HTTP request and processing the response
@@ -56,25 +142,47 @@ pub enum Error {
     ///
     /// The response parser is built to the PagerDuty API specification, so this shouldn't come up
     /// as long as their API doesn't device from the spec.
-    UnexpectedApiResponse
+    UnexpectedApiResponse,
+
+    /// The request body is larger than allowed by [`Limits`](struct.Limits.html); it wasn't sent
+    RequestTooLarge {
+        /// Size of the request body in bytes
+        size: usize,
+
+        /// The configured maximum
+        limit: usize,
+    },
+
+    /// The response body is larger than allowed by [`Limits`](struct.Limits.html)
+    ///
+    /// Reading stops once the limit is exceeded, so the actual size isn't known.
+    ResponseTooLarge {
+        /// The configured maximum
+        limit: usize,
+    },
 }
 
 impl ::std::error::Error for Error {
-    fn cause(&self) -> Option<&::std::error::Error> {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
         match *self {
             Error::Http(ref err) => Some(err),
             Error::Deserialize(ref err) => Some(err),
             Error::ReadResponse(ref err) => Some(err),
             Error::UnexpectedApiResponse => None,
+            Error::RequestTooLarge { .. } => None,
+            Error::ResponseTooLarge { .. } => None,
         }
     }
 
+    #[allow(deprecated)]
     fn description(&self) -> &str {
         match *self {
             Error::Http(ref err) => err.description(),
             Error::Deserialize(ref err) => err.description(),
             Error::ReadResponse(ref err) => err.description(),
             Error::UnexpectedApiResponse => "Unexpected API response",
+            Error::RequestTooLarge { .. } => "Request body too large",
+            Error::ResponseTooLarge { .. } => "Response body too large",
         }
     }
 }
@@ -92,6 +200,12 @@ impl ::std::fmt::Display for Error {
                 write!(f, "Error reading response body: {}", err)
             },
             Error::UnexpectedApiResponse => write!(f, "Unexpected API response"),
+            Error::RequestTooLarge { size, limit } => {
+                write!(f, "Request body of {} bytes exceeds limit of {} bytes", size, limit)
+            },
+            Error::ResponseTooLarge { limit } => {
+                write!(f, "Response body exceeds limit of {} bytes", limit)
+            },
         }
     }
 }
@@ -117,28 +231,423 @@ impl From<io::Error> for Error {
 /// A result from making a request
 pub type Result<T> = ::std::result::Result<T, Error>;
 
+/// Read the delay requested by a `Retry-After` header, if present
+///
+/// Only the delay-seconds form of the header is understood, which is the form PagerDuty sends.
+pub fn retry_after(headers: &Headers) -> Option<Duration> {
+    headers.get_raw("Retry-After")
+        .and_then(|values| values.first())
+        .and_then(|value| str::from_utf8(value).ok())
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// Notice that PagerDuty is planning to remove an endpoint
+///
+/// Built from the `Deprecation` and `Sunset` headers of a response. Register a handler with
+/// [`set_deprecation_handler`](fn.set_deprecation_handler.html) to receive these, for example to
+/// log them or count them in metrics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecationNotice {
+    /// The URL of the request whose response carried the notice
+    pub url: String,
+
+    /// Value of the `Deprecation` header; either `true` or the date of deprecation
+    pub deprecation: Option<String>,
+
+    /// Value of the `Sunset` header; the date after which the endpoint may stop responding
+    pub sunset: Option<String>,
+
+    /// Value of the `Link` header, which may point to documentation about the deprecation
+    pub link: Option<String>,
+}
+
+impl DeprecationNotice {
+    /// Read a notice from response headers, if the response carried one
+    pub fn from_headers(url: &str, headers: &Headers) -> Option<DeprecationNotice> {
+        let header = |name: &str| {
+            headers.get_raw(name)
+                .and_then(|values| values.first())
+                .and_then(|value| str::from_utf8(value).ok())
+                .map(|value| value.trim().to_owned())
+        };
+
+        let deprecation = header("Deprecation");
+        let sunset = header("Sunset");
+
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+
+        Some(DeprecationNotice {
+            url: url.to_owned(),
+            deprecation,
+            sunset,
+            link: header("Link"),
+        })
+    }
+}
+
+type DeprecationHandler = Box<dyn Fn(&DeprecationNotice) + Send + Sync>;
+
+static DEPRECATION_HANDLER: RwLock<Option<DeprecationHandler>> = RwLock::new(None);
+
+/// Set the function called whenever a response carries a deprecation notice
+///
+/// Notices are ignored until a handler is set. Setting a handler replaces any previous one.
+///
+/// # Example
+///
+/// ```
+/// pagerduty::set_deprecation_handler(|notice| {
+///     println!("warning: {} is deprecated (sunset: {:?})", notice.url, notice.sunset);
+/// });
+/// ```
+pub fn set_deprecation_handler<F>(handler: F)
+    where F: Fn(&DeprecationNotice) + Send + Sync + 'static
+{
+    let mut current = DEPRECATION_HANDLER.write().unwrap_or_else(|err| err.into_inner());
+    *current = Some(Box::new(handler));
+}
+
+fn notify_deprecation(notice: &DeprecationNotice) {
+    let handler = DEPRECATION_HANDLER.read().unwrap_or_else(|err| err.into_inner());
+    if let Some(ref handler) = *handler {
+        handler(notice);
+    }
+}
+
+/// Rate limit state reported by the most recent response from one API
+///
+/// Read from the `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers, each of
+/// which is optional.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The number of requests allowed in the current window
+    pub limit: Option<u64>,
+
+    /// The number of requests left in the current window
+    pub remaining: Option<u64>,
+
+    /// Time until the window resets, as of `observed_at`
+    pub reset: Option<Duration>,
+
+    /// When the response carrying these values was received
+    pub observed_at: Instant,
+}
+
+impl RateLimit {
+    /// Read rate limit state from response headers, if the response carried any
+    pub fn from_headers(headers: &Headers) -> Option<RateLimit> {
+        let header = |name: &str| {
+            headers.get_raw(name)
+                .and_then(|values| values.first())
+                .and_then(|value| str::from_utf8(value).ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        let limit = header("RateLimit-Limit");
+        let remaining = header("RateLimit-Remaining");
+        let reset = header("RateLimit-Reset").map(Duration::from_secs);
+
+        if limit.is_none() && remaining.is_none() && reset.is_none() {
+            return None;
+        }
+
+        Some(RateLimit {
+            limit,
+            remaining,
+            reset,
+            observed_at: Instant::now(),
+        })
+    }
+
+    /// Time until the window resets, as of now
+    pub fn reset_in(&self) -> Option<Duration> {
+        self.reset.map(|reset| {
+            let elapsed = self.observed_at.elapsed();
+            if reset > elapsed { reset - elapsed } else { Duration::from_secs(0) }
+        })
+    }
+}
+
+/// The latest rate limit state observed for each API
+///
+/// Each API is limited separately. Values are `None` until a response reporting them has been
+/// received. See [`quota`](fn.quota.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    /// The integration and events APIs
+    pub events: Option<RateLimit>,
+
+    /// The REST API
+    pub rest: Option<RateLimit>,
+}
+
+static QUOTA: Mutex<Quota> = Mutex::new(Quota { events: None, rest: None });
+
+/// The rate limit state most recently reported by PagerDuty, for all requests in this process
+///
+/// Useful for spreading bulk work across a rate limit window instead of running into
+/// `Throttled` responses.
+pub fn quota() -> Quota {
+    *QUOTA.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn record_rate_limit(url: &str, headers: &Headers) {
+    if let Some(rate_limit) = RateLimit::from_headers(headers) {
+        let mut quota = QUOTA.lock().unwrap_or_else(|err| err.into_inner());
+        if url.starts_with(::rest::BASE_URL) {
+            quota.rest = Some(rate_limit);
+        } else if url.starts_with(::oauth::TOKEN_URL) {
+            // The token endpoint is rate limited separately from either API
+        } else {
+            quota.events = Some(rate_limit);
+        }
+    }
+}
+
+/// Maximum number of bytes of a server error body kept for diagnostics
+const SERVER_ERROR_SNIPPET_LEN: usize = 512;
+
+/// Details of a 5xx response
+///
+/// PagerDuty may respond to server errors with an HTML page rather than JSON, so the body is never
+/// parsed. The beginning of it is kept to help diagnose the problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerError {
+    /// The HTTP status of the response
+    pub status: StatusCode,
+
+    /// The beginning of the response body
+    pub body_snippet: String,
+}
+
+impl ServerError {
+    /// Create a ServerError, truncating `body` to a short snippet
+    pub fn new(status: StatusCode, body: &str) -> ServerError {
+        let mut end = ::std::cmp::min(body.len(), SERVER_ERROR_SNIPPET_LEN);
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        ServerError {
+            status,
+            body_snippet: body[..end].to_owned(),
+        }
+    }
+}
+
+/// Maximum sizes of request and response bodies
+///
+/// Guards against a pathologically large event, or a misbehaving proxy returning far more than
+/// PagerDuty would, using unbounded memory. Requests exceeding these fail with
+/// [`Error::RequestTooLarge`](enum.Error.html#variant.RequestTooLarge) or
+/// [`Error::ResponseTooLarge`](enum.Error.html#variant.ResponseTooLarge).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum request body size in bytes
+    pub max_request_body: usize,
+
+    /// Maximum response body size in bytes
+    pub max_response_body: usize,
+}
+
+impl Default for Limits {
+    /// 512 KiB requests, which is the largest event PagerDuty accepts, and 16 MiB responses
+    fn default() -> Limits {
+        Limits {
+            max_request_body: 512 * 1024,
+            max_response_body: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl Limits {
+    fn check_request(&self, body: &str) -> Result<()> {
+        if body.len() > self.max_request_body {
+            return Err(Error::RequestTooLarge {
+                size: body.len(),
+                limit: self.max_request_body,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_response<T: Read>(&self, response: T) -> Result<String> {
+        let mut body = Vec::new();
+        response.take(self.max_response_body as u64 + 1).read_to_end(&mut body)?;
+
+        if body.len() > self.max_response_body {
+            return Err(Error::ResponseTooLarge { limit: self.max_response_body });
+        }
+
+        String::from_utf8(body)
+            .map_err(|err| Error::ReadResponse(io::Error::new(io::ErrorKind::InvalidData, err)))
+    }
+}
+
 /// Perform an HTTP request given a Requestable
+///
+/// The free functions in each API module call this; it's exposed for requests those don't accept,
+/// such as [`WithHeaders`](struct.WithHeaders.html). Bodies are subject to the default
+/// [`Limits`](struct.Limits.html).
 pub fn perform<R>(auth: &AuthToken, requestable: &R) -> Result<R::Response>
     where R: Requestable
+{
+    perform_with_limits(auth, requestable, &Limits::default())
+}
+
+/// Perform an HTTP request given a Requestable, with custom body size limits
+pub fn perform_with_limits<R>(auth: &AuthToken,
+                              requestable: &R,
+                              limits: &Limits) -> Result<R::Response>
+    where R: Requestable
+{
+    send(Some(auth), requestable, limits)
+}
+
+/// Perform an HTTP request which isn't authorized with a token, such as for OAuth tokens
+pub fn perform_unauthorized<R>(requestable: &R, limits: &Limits) -> Result<R::Response>
+    where R: Requestable
+{
+    send(None, requestable, limits)
+}
+
+fn send<R>(auth: Option<&AuthToken>, requestable: &R, limits: &Limits) -> Result<R::Response>
+    where R: Requestable
 {
     let client = hyper::Client::new();
 
     // Get request-specific body and headers
     let body = requestable.body();
+    limits.check_request(&body)?;
+
     let mut headers = requestable.headers();
 
-    // Add default headers
-    headers.set(auth.to_header());
+    // Add default headers; bodies are JSON unless the request says otherwise
+    if let Some(auth) = auth {
+        headers.set(auth.to_header());
+    }
     headers.set(UserAgent("hyper/0.8.0 pagerduty-rs/0.1.0".to_owned()));
-    headers.set(header::ContentType::json());
+    if !headers.has::<header::ContentType>() {
+        headers.set(header::ContentType::json());
+    }
 
-    let mut res = try!(client.request(requestable.method(), requestable.url().as_ref())
+    let url = requestable.url();
+    let mut res = client.request(requestable.method(), url.as_ref())
         .headers(headers)
         .body(&body[..])
-        .send());
+        .send()?;
+
+    if let Some(notice) = DeprecationNotice::from_headers(url.as_ref(), &res.headers) {
+        notify_deprecation(&notice);
+    }
+
+    record_rate_limit(url.as_ref(), &res.headers);
+
+    let response_body = limits.read_response(&mut res)?;
+
+    R::get_response(res.status, &res.headers, &response_body[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use super::{retry_after, DeprecationNotice, Error, Limits, RateLimit, Requestable,
+                ServerError};
+    use integration::TriggerEvent;
+
+    #[test]
+    fn limits_are_enforced() {
+        let limits = Limits { max_request_body: 4, max_response_body: 4 };
+
+        assert!(limits.check_request("1234").is_ok());
+        match limits.check_request("12345") {
+            Err(Error::RequestTooLarge { size: 5, limit: 4 }) => (),
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        assert_eq!(limits.read_response(&b"1234"[..]).unwrap(), "1234");
+        match limits.read_response(&b"12345"[..]) {
+            Err(Error::ResponseTooLarge { limit: 4 }) => (),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
 
-    let mut response_body = String::new();
-    try!(res.read_to_string(&mut response_body));
+    #[test]
+    fn with_headers_adds_to_request_headers() {
+        let event = TriggerEvent::new("service_key", "description");
 
-    Ok(try!(R::get_response(res.status, &res.headers, &response_body[..])))
+        let mut extra = Headers::new();
+        extra.set_raw("X-Gateway-Auth", vec![b"secret".to_vec()]);
+        let request = event.with_headers(extra);
+
+        let headers = request.headers();
+        assert_eq!(headers.get_raw("X-Gateway-Auth"), Some(&[b"secret".to_vec()][..]));
+        assert_eq!(request.url(), event.url());
+        assert_eq!(request.body(), event.body());
+    }
+
+    #[test]
+    fn deprecation_notice_from_headers() {
+        let url = "https://events.pagerduty.com/generic/2010-04-15/create_event.json";
+        let mut headers = Headers::new();
+        assert_eq!(DeprecationNotice::from_headers(url, &headers), None);
+
+        headers.set_raw("Sunset", vec![b"Sat, 31 Dec 2033 23:59:59 GMT".to_vec()]);
+        let notice = DeprecationNotice::from_headers(url, &headers).unwrap();
+
+        assert_eq!(notice.url, url);
+        assert_eq!(notice.deprecation, None);
+        assert_eq!(notice.sunset, Some("Sat, 31 Dec 2033 23:59:59 GMT".to_owned()));
+    }
+
+    #[test]
+    fn rate_limit_from_headers() {
+        let mut headers = Headers::new();
+        assert_eq!(RateLimit::from_headers(&headers), None);
+
+        headers.set_raw("RateLimit-Limit", vec![b"960".to_vec()]);
+        headers.set_raw("RateLimit-Remaining", vec![b"959".to_vec()]);
+        headers.set_raw("RateLimit-Reset", vec![b"30".to_vec()]);
+        let rate_limit = RateLimit::from_headers(&headers).unwrap();
+
+        assert_eq!(rate_limit.limit, Some(960));
+        assert_eq!(rate_limit.remaining, Some(959));
+        assert_eq!(rate_limit.reset, Some(Duration::from_secs(30)));
+        assert!(rate_limit.reset_in().unwrap() <= Duration::from_secs(30));
+    }
+
+    #[test]
+    fn retry_after_seconds_are_parsed() {
+        let mut headers = Headers::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.set_raw("Retry-After", vec![b"30".to_vec()]);
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(30)));
+
+        headers.set_raw("Retry-After", vec![b"Wed, 21 Oct 2015 07:28:00 GMT".to_vec()]);
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn server_error_body_is_truncated() {
+        let body = format!("<html>{}</html>", "é".repeat(1000));
+        let err = ServerError::new(StatusCode::BadGateway, &body);
+
+        assert!(err.body_snippet.len() <= 512);
+        assert!(err.body_snippet.starts_with("<html>é"));
+    }
+
+    #[test]
+    fn short_server_error_body_is_kept() {
+        let err = ServerError::new(StatusCode::InternalServerError, "oops");
+        assert_eq!(err.body_snippet, "oops");
+    }
 }
@@ -0,0 +1,956 @@
+//! Analytics
+//!
+//! The analytics endpoints compute metrics over incidents, such as the mean time to acknowledge
+//! and resolve them. Requests are described by [`AnalyticsFilters`](struct.AnalyticsFilters.html)
+//! restricting the incidents considered, and are sent as `POST` bodies rather than query strings.
+//!
+//! Analytics are computed periodically rather than in real time, so recent incidents may be
+//! missing from the results.
+//!
+//! # Responders
+//!
+//! Metrics about the people handling incidents, such as how often they were interrupted outside
+//! business hours and how long they spent on call, are computed with
+//! [`responder_metrics`](fn.responder_metrics.html). These requests are described by
+//! [`ResponderFilters`](struct.ResponderFilters.html), restricting the period and responders
+//! considered.
+//!
+//! # Raw incident data
+//!
+//! The analytics of individual incidents are listed with
+//! [`raw_incidents`](fn.raw_incidents.html). These lists are paginated with cursors rather than
+//! offsets, so they are returned as a [`CursorPage`](struct.CursorPage.html), and the next page is
+//! requested by passing the page's cursor to `ListRawIncidents::set_starting_after`.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use timestamp::Timestamp;
+use super::{get_body_response, get_response, segment, Response, BASE_URL};
+use super::users::Urgency;
+
+/// Value of the `X-EARLY-ACCESS` header required by the analytics endpoints
+const ANALYTICS_EARLY_ACCESS: &str = "analytics-v2";
+
+fn analytics_headers() -> Headers {
+    let mut headers = super::headers(None);
+    headers.set_raw("X-EARLY-ACCESS", vec![ANALYTICS_EARLY_ACCESS.as_bytes().to_vec()]);
+    headers
+}
+
+/// Restrictions on the incidents analytics are computed over
+#[derive(Debug, Serialize)]
+pub struct AnalyticsFilters<'a> {
+    created_at_start: Cow<'a, str>,
+    created_at_end: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    urgency: Option<Urgency>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    major: Option<bool>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    team_ids: Vec<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    service_ids: Vec<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    priority_ids: Vec<Cow<'a, str>>,
+}
+
+impl<'a> AnalyticsFilters<'a> {
+    /// Consider incidents created between `start` and `end`, both in ISO 8601
+    pub fn new<S>(start: S, end: S) -> AnalyticsFilters<'a>
+        where S: Timestamp<'a>
+    {
+        AnalyticsFilters {
+            created_at_start: start.into_timestamp(),
+            created_at_end: end.into_timestamp(),
+            urgency: None,
+            major: None,
+            team_ids: Vec::new(),
+            service_ids: Vec::new(),
+            priority_ids: Vec::new(),
+        }
+    }
+
+    /// Only consider incidents of `urgency`
+    pub fn set_urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = Some(urgency);
+        self
+    }
+
+    /// Only consider major incidents, or only those which aren't
+    pub fn set_major(mut self, major: bool) -> Self {
+        self.major = Some(major);
+        self
+    }
+
+    /// Only consider incidents of team `team_id`; may be called more than once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Only consider incidents on service `service_id`; may be called more than once
+    pub fn add_service_id<S>(mut self, service_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.service_ids.push(service_id.into());
+        self
+    }
+
+    /// Only consider incidents with priority `priority_id`; may be called more than once
+    pub fn add_priority_id<S>(mut self, priority_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.priority_ids.push(priority_id.into());
+        self
+    }
+}
+
+/// Restrictions on the responders and period responder analytics are computed over
+#[derive(Debug, Serialize)]
+pub struct ResponderFilters<'a> {
+    date_range_start: Cow<'a, str>,
+    date_range_end: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    urgency: Option<Urgency>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    team_ids: Vec<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    responder_ids: Vec<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    priority_ids: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ResponderFilters<'a> {
+    /// Consider the period between `start` and `end`, both in ISO 8601
+    pub fn new<S>(start: S, end: S) -> ResponderFilters<'a>
+        where S: Timestamp<'a>
+    {
+        ResponderFilters {
+            date_range_start: start.into_timestamp(),
+            date_range_end: end.into_timestamp(),
+            urgency: None,
+            team_ids: Vec::new(),
+            responder_ids: Vec::new(),
+            priority_ids: Vec::new(),
+        }
+    }
+
+    /// Only consider incidents of `urgency`
+    pub fn set_urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = Some(urgency);
+        self
+    }
+
+    /// Only consider responders on team `team_id`; may be called more than once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Only consider the user `responder_id`; may be called more than once
+    pub fn add_responder_id<S>(mut self, responder_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.responder_ids.push(responder_id.into());
+        self
+    }
+
+    /// Only consider incidents with priority `priority_id`; may be called more than once
+    pub fn add_priority_id<S>(mut self, priority_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.priority_ids.push(priority_id.into());
+        self
+    }
+}
+
+/// The period metrics are aggregated over
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AggregateUnit {
+    Day,
+    Week,
+    Month,
+}
+
+/// Metrics aggregated over a set of incidents
+///
+/// Metrics which can't be computed, such as the mean time to resolve when no incident was
+/// resolved, are `None`.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct IncidentMetrics {
+    /// The service the metrics are for, when grouped by service
+    #[serde(default)]
+    pub service_id: Option<String>,
+
+    /// The name of the service the metrics are for, when grouped by service
+    #[serde(default)]
+    pub service_name: Option<String>,
+
+    /// The team the metrics are for, when grouped by team
+    #[serde(default)]
+    pub team_id: Option<String>,
+
+    /// The name of the team the metrics are for, when grouped by team
+    #[serde(default)]
+    pub team_name: Option<String>,
+
+    /// The start of the period the metrics are for, when aggregated by period
+    #[serde(default)]
+    pub range_start: Option<String>,
+
+    /// The number of incidents
+    #[serde(default)]
+    pub total_incident_count: Option<u64>,
+
+    /// The number of major incidents
+    #[serde(default)]
+    pub total_major_incidents: Option<u64>,
+
+    /// The number of incidents which escalated
+    #[serde(default)]
+    pub total_escalation_count: Option<u64>,
+
+    /// The number of notifications sent to responders
+    #[serde(default)]
+    pub total_notifications: Option<u64>,
+
+    /// The number of incidents which were acknowledged
+    #[serde(default)]
+    pub total_incidents_acknowledged: Option<u64>,
+
+    /// The mean number of seconds from an incident triggering to its first acknowledgement (MTTA)
+    #[serde(default)]
+    pub mean_seconds_to_first_ack: Option<f64>,
+
+    /// The mean number of seconds from an incident triggering to being resolved (MTTR)
+    #[serde(default)]
+    pub mean_seconds_to_resolve: Option<f64>,
+
+    /// The mean number of seconds from an incident triggering to a responder engaging with it
+    #[serde(default)]
+    pub mean_seconds_to_engage: Option<f64>,
+
+    /// The mean number of seconds from an incident triggering to a responder being mobilized
+    #[serde(default)]
+    pub mean_seconds_to_mobilize: Option<f64>,
+
+    /// The mean number of responders engaged in each incident
+    #[serde(default)]
+    pub mean_engaged_user_count: Option<f64>,
+
+    /// The total number of seconds responders spent engaged in incidents
+    #[serde(default)]
+    pub total_engaged_seconds: Option<u64>,
+}
+
+/// Metrics about a responder, or a team of responders
+///
+/// Interruptions are notifications of incidents, and are split into those during business hours,
+/// those outside them, and those while the responder was likely asleep.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ResponderMetrics {
+    /// The user the metrics are for, unless grouped by team
+    #[serde(default)]
+    pub responder_id: Option<String>,
+
+    /// The name of the user the metrics are for, unless grouped by team
+    #[serde(default)]
+    pub responder_name: Option<String>,
+
+    /// The team the metrics are for, when grouped by team
+    #[serde(default)]
+    pub team_id: Option<String>,
+
+    /// The name of the team the metrics are for, when grouped by team
+    #[serde(default)]
+    pub team_name: Option<String>,
+
+    /// The number of incidents the responder was involved in
+    #[serde(default)]
+    pub total_incident_count: Option<u64>,
+
+    /// The number of incidents the responder acknowledged
+    #[serde(default)]
+    pub total_incidents_acknowledged: Option<u64>,
+
+    /// The number of notifications sent to the responder
+    #[serde(default)]
+    pub total_notifications: Option<u64>,
+
+    /// The number of times the responder was interrupted
+    #[serde(default)]
+    pub total_interruptions: Option<u64>,
+
+    /// The number of times the responder was interrupted during business hours
+    #[serde(default)]
+    pub total_business_hour_interruptions: Option<u64>,
+
+    /// The number of times the responder was interrupted outside business hours
+    #[serde(default)]
+    pub total_off_hour_interruptions: Option<u64>,
+
+    /// The number of times the responder was interrupted during sleeping hours
+    #[serde(default)]
+    pub total_sleep_hour_interruptions: Option<u64>,
+
+    /// The total number of seconds the responder was on call
+    #[serde(default)]
+    pub total_seconds_on_call: Option<u64>,
+
+    /// The total number of seconds the responder was on call at the first escalation level
+    #[serde(default)]
+    pub total_seconds_on_call_level_1: Option<u64>,
+
+    /// The total number of seconds the responder was on call at the second escalation level
+    #[serde(default)]
+    pub total_seconds_on_call_level_2: Option<u64>,
+
+    /// The total number of seconds the responder spent engaged in incidents
+    #[serde(default)]
+    pub total_engaged_seconds: Option<u64>,
+
+    /// The mean number of seconds from the responder being notified to acknowledging an incident
+    #[serde(default)]
+    pub mean_seconds_to_first_ack: Option<f64>,
+
+    /// The mean number of seconds the responder spent engaged in each incident
+    #[serde(default)]
+    pub mean_engaged_seconds: Option<f64>,
+}
+
+/// A page of results from a cursor-paginated endpoint
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CursorPage<T> {
+    /// The results on this page
+    #[serde(rename = "data")]
+    pub items: Vec<T>,
+
+    /// The cursor of the first result on this page
+    #[serde(default)]
+    pub first: Option<String>,
+
+    /// The cursor of the last result on this page
+    #[serde(default)]
+    pub last: Option<String>,
+
+    /// Whether more results are available after this page
+    #[serde(default)]
+    pub more: bool,
+}
+
+impl<T> CursorPage<T> {
+    /// The cursor to request the next page after, or `None` if this is the last page
+    pub fn next_cursor(&self) -> Option<&str> {
+        if self.more {
+            self.last.as_ref().map(|cursor| &cursor[..])
+        } else {
+            None
+        }
+    }
+}
+
+/// The order results are returned in
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+/// The analytics of a single incident
+///
+/// Durations are `None` when they don't apply, such as the time to resolve an incident which
+/// hasn't been resolved.
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct RawIncident {
+    /// The incident's ID
+    pub id: String,
+
+    /// The incident's number, unique within the account
+    #[serde(default)]
+    pub incident_number: Option<u64>,
+
+    /// The incident's description
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The time at which the incident was created
+    pub created_at: String,
+
+    /// The time at which the incident was resolved
+    #[serde(default)]
+    pub resolved_at: Option<String>,
+
+    /// The incident's urgency
+    #[serde(default)]
+    pub urgency: Option<Urgency>,
+
+    /// Whether the incident is a major incident
+    #[serde(default)]
+    pub major: bool,
+
+    #[serde(default)]
+    pub priority_id: Option<String>,
+
+    #[serde(default)]
+    pub priority_name: Option<String>,
+
+    #[serde(default)]
+    pub service_id: Option<String>,
+
+    #[serde(default)]
+    pub service_name: Option<String>,
+
+    #[serde(default)]
+    pub team_id: Option<String>,
+
+    #[serde(default)]
+    pub team_name: Option<String>,
+
+    #[serde(default)]
+    pub escalation_policy_id: Option<String>,
+
+    /// How long after triggering the incident was first acknowledged
+    #[serde(rename = "seconds_to_first_ack", default)]
+    #[serde(deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub time_to_first_ack: Option<Duration>,
+
+    /// How long after triggering the incident was resolved
+    #[serde(rename = "seconds_to_resolve", default)]
+    #[serde(deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub time_to_resolve: Option<Duration>,
+
+    /// How long after triggering a responder engaged with the incident
+    #[serde(rename = "seconds_to_engage", default)]
+    #[serde(deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub time_to_engage: Option<Duration>,
+
+    /// How long after triggering a responder was mobilized
+    #[serde(rename = "seconds_to_mobilize", default)]
+    #[serde(deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub time_to_mobilize: Option<Duration>,
+
+    /// Total time responders spent engaged in the incident
+    #[serde(rename = "engaged_seconds", default)]
+    #[serde(deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub engaged_time: Option<Duration>,
+
+    /// The number of responders engaged in the incident
+    #[serde(default)]
+    pub engaged_user_count: Option<u64>,
+
+    /// The number of times the incident escalated
+    #[serde(default)]
+    pub escalation_count: Option<u64>,
+
+    /// The number of times the incident was assigned
+    #[serde(default)]
+    pub assignment_count: Option<u64>,
+
+    /// Interruptions of responders during business hours
+    #[serde(default)]
+    pub business_hour_interruptions: Option<u64>,
+
+    /// Interruptions of responders outside business hours
+    #[serde(default)]
+    pub off_hour_interruptions: Option<u64>,
+
+    /// Interruptions of responders during sleeping hours
+    #[serde(default)]
+    pub sleep_hour_interruptions: Option<u64>,
+}
+
+/// How incident metrics are grouped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IncidentMetricsScope {
+    All,
+    Services,
+    Teams,
+}
+
+/// Get metrics aggregated over incidents
+#[derive(Debug, Serialize)]
+pub struct GetIncidentMetrics<'a> {
+    #[serde(skip_serializing)]
+    scope: IncidentMetricsScope,
+
+    filters: AnalyticsFilters<'a>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    aggregate_unit: Option<AggregateUnit>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    time_zone: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetIncidentMetrics<'a> {
+    fn new(scope: IncidentMetricsScope, filters: AnalyticsFilters<'a>) -> GetIncidentMetrics<'a> {
+        GetIncidentMetrics {
+            scope,
+            filters,
+            aggregate_unit: None,
+            time_zone: None,
+        }
+    }
+
+    /// Create a request for metrics over all incidents matching `filters`
+    pub fn all(filters: AnalyticsFilters<'a>) -> GetIncidentMetrics<'a> {
+        GetIncidentMetrics::new(IncidentMetricsScope::All, filters)
+    }
+
+    /// Create a request for metrics over incidents matching `filters`, grouped by service
+    pub fn by_service(filters: AnalyticsFilters<'a>) -> GetIncidentMetrics<'a> {
+        GetIncidentMetrics::new(IncidentMetricsScope::Services, filters)
+    }
+
+    /// Create a request for metrics over incidents matching `filters`, grouped by team
+    pub fn by_team(filters: AnalyticsFilters<'a>) -> GetIncidentMetrics<'a> {
+        GetIncidentMetrics::new(IncidentMetricsScope::Teams, filters)
+    }
+
+    /// Additionally aggregate metrics by `unit`, yielding one result per period
+    pub fn set_aggregate_unit(mut self, unit: AggregateUnit) -> Self {
+        self.aggregate_unit = Some(unit);
+        self
+    }
+
+    /// Set the time zone periods are aggregated in, such as `America/Los_Angeles`
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetIncidentMetrics<'a> {
+    type Response = Response<Vec<IncidentMetrics>>;
+
+    fn headers(&self) -> Headers {
+        analytics_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let scope = match self.scope {
+            IncidentMetricsScope::All => "all",
+            IncidentMetricsScope::Services => "services",
+            IncidentMetricsScope::Teams => "teams",
+        };
+
+        format!("{}/analytics/metrics/incidents/{}", BASE_URL, scope).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<IncidentMetrics>>> {
+        get_response(status, headers, body, "data")
+    }
+}
+
+/// List the analytics of individual incidents
+#[derive(Debug, Serialize)]
+pub struct ListRawIncidents<'a> {
+    filters: AnalyticsFilters<'a>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    limit: Option<usize>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    starting_after: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    ending_before: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    order: Option<Order>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    time_zone: Option<Cow<'a, str>>,
+}
+
+impl<'a> ListRawIncidents<'a> {
+    /// Create a request listing the analytics of incidents matching `filters`
+    pub fn new(filters: AnalyticsFilters<'a>) -> ListRawIncidents<'a> {
+        ListRawIncidents {
+            filters,
+            limit: None,
+            starting_after: None,
+            ending_before: None,
+            order: None,
+            time_zone: None,
+        }
+    }
+
+    /// Set the number of results per page
+    pub fn set_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Return results after `cursor`, such as `CursorPage::next_cursor` of the previous page
+    pub fn set_starting_after<S>(mut self, cursor: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.starting_after = Some(cursor.into());
+        self
+    }
+
+    /// Return results before `cursor`, such as `CursorPage::first` of the following page
+    pub fn set_ending_before<S>(mut self, cursor: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.ending_before = Some(cursor.into());
+        self
+    }
+
+    /// Set whether results are in ascending or descending order of creation
+    pub fn set_order(mut self, order: Order) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Set the time zone times are returned in, such as `America/Los_Angeles`
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListRawIncidents<'a> {
+    type Response = Response<CursorPage<RawIncident>>;
+
+    fn headers(&self) -> Headers {
+        analytics_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/analytics/raw/incidents", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<CursorPage<RawIncident>>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// Get the analytics of a single incident
+#[derive(Debug)]
+pub struct GetRawIncident<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetRawIncident<'a> {
+    /// Create a request for the analytics of incident `id`
+    pub fn new<S>(id: S) -> GetRawIncident<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetRawIncident {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetRawIncident<'a> {
+    type Response = Response<RawIncident>;
+
+    fn headers(&self) -> Headers {
+        analytics_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/analytics/raw/incidents/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<RawIncident>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// Get metrics about responders
+#[derive(Debug, Serialize)]
+pub struct GetResponderMetrics<'a> {
+    #[serde(skip_serializing)]
+    by_team: bool,
+
+    filters: ResponderFilters<'a>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    time_zone: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetResponderMetrics<'a> {
+    /// Create a request for metrics about each responder matching `filters`
+    pub fn all(filters: ResponderFilters<'a>) -> GetResponderMetrics<'a> {
+        GetResponderMetrics {
+            by_team: false,
+            filters,
+            time_zone: None,
+        }
+    }
+
+    /// Create a request for metrics about responders matching `filters`, grouped by team
+    pub fn by_team(filters: ResponderFilters<'a>) -> GetResponderMetrics<'a> {
+        GetResponderMetrics {
+            by_team: true,
+            ..GetResponderMetrics::all(filters)
+        }
+    }
+
+    /// Set the time zone business and sleeping hours are determined in
+    ///
+    /// Defaults to each responder's own time zone.
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetResponderMetrics<'a> {
+    type Response = Response<Vec<ResponderMetrics>>;
+
+    fn headers(&self) -> Headers {
+        analytics_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let scope = if self.by_team { "teams" } else { "all" };
+        format!("{}/analytics/metrics/responders/{}", BASE_URL, scope).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<ResponderMetrics>>> {
+        get_response(status, headers, body, "data")
+    }
+}
+
+/// Get aggregated incident metrics
+pub fn incident_metrics(auth: &AuthToken, request: &GetIncidentMetrics)
+    -> request::Result<Response<Vec<IncidentMetrics>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get metrics about responders
+pub fn responder_metrics(auth: &AuthToken, request: &GetResponderMetrics)
+    -> request::Result<Response<Vec<ResponderMetrics>>>
+{
+    request::perform(auth, request)
+}
+
+/// List the analytics of individual incidents
+pub fn raw_incidents(auth: &AuthToken, request: &ListRawIncidents)
+    -> request::Result<Response<CursorPage<RawIncident>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get the analytics of a single incident
+pub fn raw_incident(auth: &AuthToken,
+                    request: &GetRawIncident) -> request::Result<Response<RawIncident>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use rest::users::Urgency;
+    use super::{AggregateUnit, AnalyticsFilters, GetIncidentMetrics, GetResponderMetrics,
+                ListRawIncidents, Order, ResponderFilters};
+
+    #[test]
+    fn raw_incidents_round_trip() {
+        let filters = AnalyticsFilters::new("2023-01-01T00:00:00Z", "2023-02-01T00:00:00Z");
+        let request = ListRawIncidents::new(filters)
+            .set_limit(2)
+            .set_starting_after("dXRjLTIwMjMtMDEtMDE=")
+            .set_order(Order::Asc);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual["starting_after"], "dXRjLTIwMjMtMDEtMDE=");
+        assert_eq!(actual["order"], "asc");
+        assert_eq!(actual["limit"], 2);
+
+        let body = stringify!({
+            "data": [
+                {
+                    "id": "PT4KHLK",
+                    "incident_number": 1234,
+                    "created_at": "2023-01-03T10:00:00Z",
+                    "resolved_at": "2023-01-03T11:00:00Z",
+                    "urgency": "high",
+                    "major": false,
+                    "service_id": "PIJ90N7",
+                    "seconds_to_first_ack": 120,
+                    "seconds_to_resolve": 3600,
+                    "off_hour_interruptions": 1
+                }
+            ],
+            "first": "dXRjLTIwMjMtMDEtMDM=",
+            "last": "dXRjLTIwMjMtMDEtMDQ=",
+            "more": true,
+            "limit": 2,
+            "order": "asc"
+        });
+
+        let res = ListRawIncidents::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].urgency, Some(Urgency::High));
+        assert_eq!(page.items[0].time_to_resolve, Some(Duration::from_secs(3600)));
+        assert_eq!(page.next_cursor(), Some("dXRjLTIwMjMtMDEtMDQ="));
+    }
+
+    #[test]
+    fn incident_metrics_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "filters": {
+                "created_at_start": "2023-01-01T00:00:00Z",
+                "created_at_end": "2023-02-01T00:00:00Z",
+                "urgency": "high",
+                "service_ids": ["PIJ90N7"]
+            },
+            "aggregate_unit": "week",
+            "time_zone": "Etc/UTC"
+        })).expect("expected is valid json");
+
+        let filters = AnalyticsFilters::new("2023-01-01T00:00:00Z", "2023-02-01T00:00:00Z")
+            .set_urgency(Urgency::High)
+            .add_service_id("PIJ90N7");
+        let request = GetIncidentMetrics::by_service(filters)
+            .set_aggregate_unit(AggregateUnit::Week)
+            .set_time_zone("Etc/UTC");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/analytics/metrics/incidents/services");
+
+        let body = stringify!({
+            "data": [
+                {
+                    "service_id": "PIJ90N7",
+                    "service_name": "Checkout API",
+                    "range_start": "2023-01-02T00:00:00Z",
+                    "total_incident_count": 12,
+                    "mean_seconds_to_first_ack": 245.5,
+                    "mean_seconds_to_resolve": 3600,
+                    "mean_engaged_user_count": null
+                }
+            ],
+            "aggregate_unit": "week",
+            "time_zone": "Etc/UTC"
+        });
+
+        let res = GetIncidentMetrics::get_response(StatusCode::Ok, &Headers::new(), body);
+        let metrics = res.unwrap().success().unwrap();
+
+        assert_eq!(metrics[0].total_incident_count, Some(12));
+        assert_eq!(metrics[0].mean_seconds_to_first_ack, Some(245.5));
+        assert_eq!(metrics[0].mean_seconds_to_resolve, Some(3600.0));
+        assert_eq!(metrics[0].mean_engaged_user_count, None);
+    }
+
+    #[test]
+    fn responder_metrics_by_team() {
+        let filters = ResponderFilters::new("2023-01-01T00:00:00Z", "2023-02-01T00:00:00Z")
+            .add_team_id("PQ9K7I8");
+        let request = GetResponderMetrics::by_team(filters);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/analytics/metrics/responders/teams");
+        assert_eq!(actual["filters"]["date_range_start"], "2023-01-01T00:00:00Z");
+        assert_eq!(actual["filters"]["team_ids"][0], "PQ9K7I8");
+
+        let body = stringify!({
+            "data": [
+                {
+                    "team_id": "PQ9K7I8",
+                    "team_name": "Engineering",
+                    "total_interruptions": 12,
+                    "total_business_hour_interruptions": 7,
+                    "total_off_hour_interruptions": 3,
+                    "total_sleep_hour_interruptions": 2,
+                    "total_seconds_on_call": 604800,
+                    "mean_seconds_to_first_ack": 95.5
+                }
+            ]
+        });
+
+        let res = GetResponderMetrics::get_response(StatusCode::Ok, &Headers::new(), body);
+        let metrics = res.unwrap().success().unwrap();
+
+        assert_eq!(metrics[0].responder_id, None);
+        assert_eq!(metrics[0].total_sleep_hour_interruptions, Some(2));
+        assert_eq!(metrics[0].total_seconds_on_call, Some(604800));
+    }
+}
@@ -0,0 +1,396 @@
+//! Audit Records
+//!
+//! Audit records describe changes made to users, teams, schedules, escalation policies, and
+//! services: who made them, how, and which fields and references changed. The records of a
+//! single resource are listed with [`list`](fn.list.html), newest first.
+//!
+//! Audit records are paginated with cursors rather than offsets. Each response carries a
+//! `next_cursor` which is passed to `ListAuditRecords::set_cursor` to request the following page.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::Value as Json;
+
+use AuthToken;
+use request::{self, Requestable};
+use timestamp::Timestamp;
+use super::{get_body_response, segment, Query, Reference, Response};
+
+/// A resource whose changes are audited
+#[derive(Debug, Clone)]
+pub enum AuditedResource<'a> {
+    User(Cow<'a, str>),
+    Team(Cow<'a, str>),
+    Schedule(Cow<'a, str>),
+    EscalationPolicy(Cow<'a, str>),
+    Service(Cow<'a, str>),
+}
+
+impl<'a> AuditedResource<'a> {
+    /// User `id`
+    pub fn user<S>(id: S) -> AuditedResource<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AuditedResource::User(id.into())
+    }
+
+    /// Team `id`
+    pub fn team<S>(id: S) -> AuditedResource<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AuditedResource::Team(id.into())
+    }
+
+    /// Schedule `id`
+    pub fn schedule<S>(id: S) -> AuditedResource<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AuditedResource::Schedule(id.into())
+    }
+
+    /// Escalation policy `id`
+    pub fn escalation_policy<S>(id: S) -> AuditedResource<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AuditedResource::EscalationPolicy(id.into())
+    }
+
+    /// Service `id`
+    pub fn service<S>(id: S) -> AuditedResource<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AuditedResource::Service(id.into())
+    }
+
+    /// The resource's ID
+    pub fn id(&self) -> &str {
+        match *self {
+            AuditedResource::User(ref id) |
+            AuditedResource::Team(ref id) |
+            AuditedResource::Schedule(ref id) |
+            AuditedResource::EscalationPolicy(ref id) |
+            AuditedResource::Service(ref id) => id,
+        }
+    }
+
+    fn path(&self) -> String {
+        let collection = match *self {
+            AuditedResource::User(_) => "users",
+            AuditedResource::Team(_) => "teams",
+            AuditedResource::Schedule(_) => "schedules",
+            AuditedResource::EscalationPolicy(_) => "escalation_policies",
+            AuditedResource::Service(_) => "services",
+        };
+
+        format!("/{}/{}/audit/records", collection, segment(self.id()))
+    }
+}
+
+/// The kind of change an audit record describes
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+
+    /// An action this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// Where the request making a change came from
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ExecutionContext {
+    /// The ID of the request which made the change
+    #[serde(default)]
+    pub request_id: Option<String>,
+
+    /// The IP address the request was made from
+    #[serde(default)]
+    pub remote_address: Option<String>,
+}
+
+/// How the actor making a change was authenticated
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ExecutionMethod {
+    /// The kind of authentication, for example `browser`, `api_token`, or `oauth`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The last few characters of the API token used, if any
+    #[serde(default)]
+    pub truncated_token: Option<String>,
+
+    /// A description of the authentication method
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A field whose value was changed
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct FieldChange {
+    /// The name of the field, for example `name` or `time_zone`
+    pub name: String,
+
+    /// A description of the field
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The field's value after the change, or `None` if it was removed
+    #[serde(default)]
+    pub value: Option<Json>,
+
+    /// The field's value before the change, or `None` if it was just set
+    #[serde(default)]
+    pub before_value: Option<Json>,
+}
+
+/// A set of references to other objects which was changed
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ReferenceChange {
+    /// The name of the references, for example `escalation_rules` or `teams`
+    pub name: String,
+
+    /// A description of the references
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Objects which were added
+    #[serde(default)]
+    pub added: Vec<Reference>,
+
+    /// Objects which were removed
+    #[serde(default)]
+    pub removed: Vec<Reference>,
+}
+
+/// What was changed on a resource
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct AuditDetails {
+    /// The resource which was changed
+    pub resource: Reference,
+
+    /// Fields which were changed
+    #[serde(default)]
+    pub fields: Vec<FieldChange>,
+
+    /// References which were changed
+    #[serde(default)]
+    pub references: Vec<ReferenceChange>,
+}
+
+/// A single change to an audited resource
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct AuditRecord {
+    /// The record's ID
+    pub id: String,
+
+    /// The API URL at which the record is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// The time at which the change was made
+    pub execution_time: String,
+
+    /// Where the request making the change came from
+    #[serde(default)]
+    pub execution_context: Option<ExecutionContext>,
+
+    /// The users, API tokens, or apps which made the change
+    #[serde(default)]
+    pub actors: Vec<Reference>,
+
+    /// How the actors were authenticated
+    #[serde(default)]
+    pub method: Option<ExecutionMethod>,
+
+    /// The resource the record is for
+    pub root_resource: Reference,
+
+    /// The kind of change
+    pub action: AuditAction,
+
+    /// What was changed, when available
+    #[serde(default)]
+    pub details: Option<AuditDetails>,
+}
+
+/// A page of audit records
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct AuditRecords {
+    /// The records on this page, newest first
+    pub records: Vec<AuditRecord>,
+
+    /// The cursor of the next page, or `None` if this is the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// List the audit records of a resource
+#[derive(Debug)]
+pub struct ListAuditRecords<'a> {
+    resource: AuditedResource<'a>,
+    since: Option<Cow<'a, str>>,
+    until: Option<Cow<'a, str>>,
+    limit: Option<usize>,
+    cursor: Option<Cow<'a, str>>,
+}
+
+impl<'a> ListAuditRecords<'a> {
+    /// Create a request listing the audit records of `resource`
+    pub fn new(resource: AuditedResource<'a>) -> ListAuditRecords<'a> {
+        ListAuditRecords {
+            resource,
+            since: None,
+            until: None,
+            limit: None,
+            cursor: None,
+        }
+    }
+
+    /// Only return records of changes made at or after `since`, in ISO 8601
+    pub fn set_since<S>(mut self, since: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.since = Some(since.into_timestamp());
+        self
+    }
+
+    /// Only return records of changes made before `until`, in ISO 8601
+    pub fn set_until<S>(mut self, until: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.until = Some(until.into_timestamp());
+        self
+    }
+
+    /// Set the number of records per page
+    pub fn set_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the page at `cursor`, the `next_cursor` of the previous page
+    pub fn set_cursor<S>(mut self, cursor: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListAuditRecords<'a> {
+    type Response = Response<AuditRecords>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref since) = self.since {
+            query.push("since", since);
+        }
+        if let Some(ref until) = self.until {
+            query.push("until", until);
+        }
+        if let Some(limit) = self.limit {
+            query.push("limit", limit.to_string());
+        }
+        if let Some(ref cursor) = self.cursor {
+            query.push("cursor", cursor);
+        }
+
+        query.url(&self.resource.path()).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<AuditRecords>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// List the audit records of a resource
+pub fn list(auth: &AuthToken,
+            request: &ListAuditRecords) -> request::Result<Response<AuditRecords>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use super::{AuditAction, AuditedResource, ListAuditRecords};
+
+    #[test]
+    fn list_audit_records() {
+        let request = ListAuditRecords::new(AuditedResource::escalation_policy("PANZZEQ"))
+            .set_since("2023-01-01T00:00:00Z")
+            .set_cursor("dXNlcjpQWFBHRjQy");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/escalation_policies/PANZZEQ/audit/records\
+                    ?since=2023-01-01T00%3A00%3A00Z&cursor=dXNlcjpQWFBHRjQy");
+
+        let body = stringify!({
+            "records": [
+                {
+                    "id": "PDRECORDID1_POLICY_UPDATED",
+                    "execution_time": "2023-01-15T12:00:00Z",
+                    "execution_context": {
+                        "request_id": "100abc9f-a2cd-40ff-9a8b-fbbb4fab0bc1",
+                        "remote_address": "203.0.113.7"
+                    },
+                    "actors": [
+                        { "id": "PXPGF42", "type": "user_reference", "summary": "Jane Doe" }
+                    ],
+                    "method": { "type": "api_token", "truncated_token": "3xyz" },
+                    "root_resource": { "id": "PANZZEQ", "type": "escalation_policy_reference" },
+                    "action": "update",
+                    "details": {
+                        "resource": { "id": "PANZZEQ", "type": "escalation_policy_reference" },
+                        "fields": [
+                            { "name": "num_loops", "value": 2, "before_value": 0 }
+                        ],
+                        "references": [
+                            {
+                                "name": "teams",
+                                "added": [ { "id": "PQ9K7I8", "type": "team_reference" } ]
+                            }
+                        ]
+                    }
+                }
+            ],
+            "next_cursor": null,
+            "limit": 10
+        });
+
+        let res = ListAuditRecords::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+        let record = &page.records[0];
+        let details = record.details.as_ref().unwrap();
+
+        assert_eq!(record.action, AuditAction::Update);
+        assert_eq!(record.actors[0].summary, Some("Jane Doe".to_owned()));
+        assert_eq!(record.method.as_ref().unwrap().kind, "api_token");
+        assert_eq!(details.fields[0].value, Some(2.into()));
+        assert_eq!(details.references[0].added[0].id, "PQ9K7I8");
+        assert_eq!(page.next_cursor, None);
+    }
+}
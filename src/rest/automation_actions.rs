@@ -0,0 +1,1487 @@
+//! Automation Actions
+//!
+//! An automation action is a script or Process Automation job which responders can invoke on an
+//! incident, such as collecting diagnostics or restarting a service. Actions run on a runner
+//! installed in the customer's infrastructure, and each run of an action is an invocation.
+//!
+//! Actions are paginated with cursors rather than offsets. Each response carries a `next_cursor`
+//! which is passed to `ListAutomationActions::set_cursor` to request the following page.
+//!
+//! # Runners
+//!
+//! Runners are registered with [`create_runner`](fn.create_runner.html). A runner reports its
+//! health through `Runner::status`, which tells whether it has been set up, and
+//! `Runner::last_seen`, the last time it contacted PagerDuty.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_body_response, get_empty_response, get_response, segment, Query, Reference,
+            Response, BASE_URL};
+
+/// What an automation action runs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionType {
+    /// A script run by a runner
+    Script,
+
+    /// A job of Process Automation or Runbook Automation
+    ProcessAutomation,
+}
+
+/// Whether an automation action only gathers information, or changes something
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionClassification {
+    Diagnostic,
+    Remediation,
+}
+
+/// The script or job an automation action runs
+///
+/// Script actions set `script` and optionally `invocation_command`, and Process Automation actions
+/// set the `process_automation_*` fields.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActionData {
+    /// The body of the script
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+
+    /// The command the script is run with, such as `/usr/bin/python3`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation_command: Option<String>,
+
+    /// The ID of the job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_automation_job_id: Option<String>,
+
+    /// Arguments passed to the job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_automation_job_arguments: Option<String>,
+
+    /// A filter selecting the nodes the job runs on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_automation_node_filter: Option<String>,
+}
+
+/// A script or job which can be invoked on incidents
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct AutomationAction {
+    /// The action's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the action
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The name of the action
+    pub name: String,
+
+    /// A description of the action
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// What the action runs
+    pub action_type: ActionType,
+
+    /// Whether the action only gathers information, or changes something
+    #[serde(default)]
+    pub action_classification: Option<ActionClassification>,
+
+    /// The script or job the action runs
+    #[serde(default)]
+    pub action_data_reference: ActionData,
+
+    /// The ID of the runner the action runs on
+    #[serde(default)]
+    pub runner: Option<String>,
+
+    /// The kind of runner the action runs on, for example `sidecar` or `runbook`
+    #[serde(default)]
+    pub runner_type: Option<String>,
+
+    /// Services whose incidents the action can be invoked on
+    #[serde(default)]
+    pub services: Vec<Reference>,
+
+    /// Teams whose members can invoke the action
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// The time at which the action was created
+    #[serde(default)]
+    pub creation_time: Option<String>,
+
+    /// The time at which the action was last modified
+    #[serde(default)]
+    pub modify_time: Option<String>,
+
+    /// The time at which the action was last invoked
+    #[serde(default)]
+    pub last_run: Option<String>,
+
+    /// The user who last invoked the action
+    #[serde(default)]
+    pub last_run_by: Option<Reference>,
+}
+
+/// A page of automation actions
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct AutomationActions {
+    /// The actions on this page
+    pub actions: Vec<AutomationAction>,
+
+    /// The cursor of the next page, or `None` if this is the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// The progress of an invocation
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvocationState {
+    Prepared,
+    Created,
+    Sent,
+    Queued,
+    Running,
+    Aborted,
+    Completed,
+    Error,
+
+    /// The state couldn't be determined, or isn't known to this library
+    #[serde(other)]
+    Unknown,
+}
+
+impl InvocationState {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            InvocationState::Prepared => "prepared",
+            InvocationState::Created => "created",
+            InvocationState::Sent => "sent",
+            InvocationState::Queued => "queued",
+            InvocationState::Running => "running",
+            InvocationState::Aborted => "aborted",
+            InvocationState::Completed => "completed",
+            InvocationState::Error => "error",
+            InvocationState::Unknown => "unknown",
+        }
+    }
+}
+
+/// The time at which an invocation entered a state
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct StateTiming {
+    pub state: InvocationState,
+    pub creation_timestamp: String,
+}
+
+/// Where and by whom an action was invoked
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct InvocationMetadata {
+    /// The incident the action was invoked on
+    #[serde(default)]
+    pub incident_id: Option<String>,
+
+    /// The user or other agent which invoked the action
+    #[serde(default)]
+    pub agent: Option<Reference>,
+}
+
+/// The action as it was when invoked
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ActionSnapshot {
+    pub name: String,
+
+    #[serde(default)]
+    pub action_type: Option<ActionType>,
+
+    #[serde(default)]
+    pub action_data_reference: ActionData,
+}
+
+/// A run of an automation action
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Invocation {
+    /// The invocation's ID
+    pub id: String,
+
+    /// The action which was invoked
+    #[serde(default)]
+    pub action_id: Option<String>,
+
+    /// The runner the action ran on
+    #[serde(default)]
+    pub runner_id: Option<String>,
+
+    /// The current progress of the invocation
+    pub state: InvocationState,
+
+    /// The action as it was when invoked
+    #[serde(default)]
+    pub action_snapshot: Option<ActionSnapshot>,
+
+    /// Where and by whom the action was invoked
+    #[serde(default)]
+    pub metadata: Option<InvocationMetadata>,
+
+    /// The times at which the invocation entered each state so far
+    #[serde(default)]
+    pub timing: Vec<StateTiming>,
+
+    /// How long the invocation ran, in milliseconds, once finished
+    #[serde(default)]
+    pub duration: Option<u64>,
+}
+
+/// The kind of a runner
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunnerType {
+    /// A runner installed in the customer's infrastructure which runs scripts
+    Sidecar,
+
+    /// A Runbook Automation instance which runs Process Automation jobs
+    Runbook,
+}
+
+/// Whether a runner has been set up
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum RunnerStatus {
+    Configured,
+    NotConfigured,
+
+    /// A status this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// Where automation actions run
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Runner {
+    /// The runner's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the runner
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The name of the runner
+    pub name: String,
+
+    /// A description of the runner
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The kind of runner
+    pub runner_type: RunnerType,
+
+    /// Whether the runner has been set up
+    #[serde(default)]
+    pub status: Option<RunnerStatus>,
+
+    /// The last time the runner contacted PagerDuty, or `None` if it never has
+    #[serde(default)]
+    pub last_seen: Option<String>,
+
+    /// The URL of the Runbook Automation instance, for `RunnerType::Runbook`
+    #[serde(default)]
+    pub runbook_base_uri: Option<String>,
+
+    /// Teams which may use the runner
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// The time at which the runner was created
+    #[serde(default)]
+    pub creation_time: Option<String>,
+}
+
+/// A page of runners
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Runners {
+    /// The runners on this page
+    pub runners: Vec<Runner>,
+
+    /// The cursor of the next page, or `None` if this is the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// List automation actions, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListAutomationActions<'a> {
+    name: Option<Cow<'a, str>>,
+    runner_id: Option<Cow<'a, str>>,
+    team_id: Option<Cow<'a, str>>,
+    service_id: Option<Cow<'a, str>>,
+    classification: Option<ActionClassification>,
+    action_type: Option<ActionType>,
+    limit: Option<usize>,
+    cursor: Option<Cow<'a, str>>,
+}
+
+impl<'a> ListAutomationActions<'a> {
+    /// Create a request listing all automation actions
+    pub fn new() -> ListAutomationActions<'a> {
+        ListAutomationActions::default()
+    }
+
+    /// Only return actions whose names contain `name`
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only return actions which run on runner `runner_id`
+    pub fn set_runner_id<S>(mut self, runner_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.runner_id = Some(runner_id.into());
+        self
+    }
+
+    /// Only return actions associated with team `team_id`
+    pub fn set_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    /// Only return actions associated with service `service_id`
+    pub fn set_service_id<S>(mut self, service_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.service_id = Some(service_id.into());
+        self
+    }
+
+    /// Only return actions of `classification`
+    pub fn set_classification(mut self, classification: ActionClassification) -> Self {
+        self.classification = Some(classification);
+        self
+    }
+
+    /// Only return actions of `action_type`
+    pub fn set_action_type(mut self, action_type: ActionType) -> Self {
+        self.action_type = Some(action_type);
+        self
+    }
+
+    /// Set the number of actions per page
+    pub fn set_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the page at `cursor`, the `next_cursor` of the previous page
+    pub fn set_cursor<S>(mut self, cursor: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListAutomationActions<'a> {
+    type Response = Response<AutomationActions>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref name) = self.name {
+            query.push("name", name);
+        }
+        if let Some(ref runner_id) = self.runner_id {
+            query.push("runner_id", runner_id);
+        }
+        if let Some(ref team_id) = self.team_id {
+            query.push("team_id", team_id);
+        }
+        if let Some(ref service_id) = self.service_id {
+            query.push("service_id", service_id);
+        }
+        match self.classification {
+            Some(ActionClassification::Diagnostic) => query.push("classification", "diagnostic"),
+            Some(ActionClassification::Remediation) => query.push("classification", "remediation"),
+            None => (),
+        }
+        match self.action_type {
+            Some(ActionType::Script) => query.push("action_type", "script"),
+            Some(ActionType::ProcessAutomation) => {
+                query.push("action_type", "process_automation")
+            },
+            None => (),
+        }
+        if let Some(limit) = self.limit {
+            query.push("limit", limit.to_string());
+        }
+        if let Some(ref cursor) = self.cursor {
+            query.push("cursor", cursor);
+        }
+
+        query.url("/automation_actions/actions").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<AutomationActions>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// Get details about an existing automation action
+#[derive(Debug)]
+pub struct GetAutomationAction<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetAutomationAction<'a> {
+    /// Create a request for automation action `id`
+    pub fn new<S>(id: S) -> GetAutomationAction<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetAutomationAction {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetAutomationAction<'a> {
+    type Response = Response<AutomationAction>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/actions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<AutomationAction>> {
+        get_response(status, headers, body, "action")
+    }
+}
+
+/// Fields of an automation action to create or update
+#[derive(Debug, Default, Serialize)]
+pub struct AutomationActionFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    action_type: Option<ActionType>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    action_classification: Option<ActionClassification>,
+
+    #[serde(rename = "action_data_reference", skip_serializing_if="Option::is_none")]
+    data: Option<ActionData>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    runner: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    services: Vec<Reference>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    teams: Vec<Reference>,
+}
+
+impl<'a> AutomationActionFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> AutomationActionFields<'a> {
+        AutomationActionFields::default()
+    }
+
+    /// Set the name of the action
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the description of the action
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set whether the action only gathers information, or changes something
+    pub fn set_classification(mut self, classification: ActionClassification) -> Self {
+        self.action_classification = Some(classification);
+        self
+    }
+
+    /// Run `script` using `invocation_command`, such as `/bin/bash`
+    pub fn set_script<S>(mut self, script: S, invocation_command: Option<S>) -> Self
+        where S: Into<String>
+    {
+        self.action_type = Some(ActionType::Script);
+        self.data = Some(ActionData {
+            script: Some(script.into()),
+            invocation_command: invocation_command.map(Into::into),
+            ..ActionData::default()
+        });
+        self
+    }
+
+    /// Run Process Automation job `job_id`, optionally with `arguments`
+    pub fn set_process_automation_job<S>(mut self, job_id: S, arguments: Option<S>) -> Self
+        where S: Into<String>
+    {
+        self.action_type = Some(ActionType::ProcessAutomation);
+        self.data = Some(ActionData {
+            process_automation_job_id: Some(job_id.into()),
+            process_automation_job_arguments: arguments.map(Into::into),
+            ..ActionData::default()
+        });
+        self
+    }
+
+    /// Set the runner the action runs on
+    pub fn set_runner<S>(mut self, runner_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.runner = Some(runner_id.into());
+        self
+    }
+
+    /// Allow invoking the action on incidents of service `service_id`
+    ///
+    /// May be called more than once. Only applies when creating an action.
+    pub fn add_service<S>(mut self, service_id: S) -> Self
+        where S: Into<String>
+    {
+        self.services.push(Reference::new(service_id, "service_reference"));
+        self
+    }
+
+    /// Allow members of team `team_id` to invoke the action
+    ///
+    /// May be called more than once. Only applies when creating an action.
+    pub fn add_team<S>(mut self, team_id: S) -> Self
+        where S: Into<String>
+    {
+        self.teams.push(Reference::new(team_id, "team_reference"));
+        self
+    }
+}
+
+/// Create a new automation action
+#[derive(Debug, Serialize)]
+pub struct CreateAutomationAction<'a> {
+    action: AutomationActionFields<'a>,
+}
+
+impl<'a> CreateAutomationAction<'a> {
+    /// Create a request for a new automation action described by `fields`
+    ///
+    /// PagerDuty requires a name and a script or job.
+    pub fn new(fields: AutomationActionFields<'a>) -> CreateAutomationAction<'a> {
+        CreateAutomationAction {
+            action: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateAutomationAction<'a> {
+    type Response = Response<AutomationAction>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/actions", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<AutomationAction>> {
+        get_response(status, headers, body, "action")
+    }
+}
+
+/// Update an existing automation action
+#[derive(Debug, Serialize)]
+pub struct UpdateAutomationAction<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    action: AutomationActionFields<'a>,
+}
+
+impl<'a> UpdateAutomationAction<'a> {
+    /// Create a request applying `fields` to automation action `id`
+    pub fn new<S>(id: S, fields: AutomationActionFields<'a>) -> UpdateAutomationAction<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateAutomationAction {
+            id: id.into(),
+            action: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateAutomationAction<'a> {
+    type Response = Response<AutomationAction>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/actions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<AutomationAction>> {
+        get_response(status, headers, body, "action")
+    }
+}
+
+/// Delete an existing automation action
+#[derive(Debug)]
+pub struct DeleteAutomationAction<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteAutomationAction<'a> {
+    /// Create a request deleting automation action `id`
+    pub fn new<S>(id: S) -> DeleteAutomationAction<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteAutomationAction {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteAutomationAction<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/actions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct InvocationFields<'a> {
+    metadata: InvocationTarget<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct InvocationTarget<'a> {
+    incident_id: Cow<'a, str>,
+}
+
+/// Invoke an automation action on an incident
+#[derive(Debug, Serialize)]
+pub struct InvokeAutomationAction<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    invocation: InvocationFields<'a>,
+}
+
+impl<'a> InvokeAutomationAction<'a> {
+    /// Create a request invoking automation action `id` on incident `incident_id`
+    pub fn new<S>(id: S, incident_id: S) -> InvokeAutomationAction<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        InvokeAutomationAction {
+            id: id.into(),
+            invocation: InvocationFields {
+                metadata: InvocationTarget {
+                    incident_id: incident_id.into(),
+                },
+            },
+        }
+    }
+}
+
+impl<'a> Requestable for InvokeAutomationAction<'a> {
+    type Response = Response<Invocation>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/actions/{}/invocations", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Invocation>> {
+        get_response(status, headers, body, "invocation")
+    }
+}
+
+/// List invocations of automation actions, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListInvocations<'a> {
+    action_id: Option<Cow<'a, str>>,
+    incident_id: Option<Cow<'a, str>>,
+    state: Option<InvocationState>,
+    not_state: Option<InvocationState>,
+}
+
+impl<'a> ListInvocations<'a> {
+    /// Create a request listing all invocations
+    pub fn new() -> ListInvocations<'a> {
+        ListInvocations::default()
+    }
+
+    /// Only return invocations of automation action `action_id`
+    pub fn set_action_id<S>(mut self, action_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.action_id = Some(action_id.into());
+        self
+    }
+
+    /// Only return invocations on incident `incident_id`
+    pub fn set_incident_id<S>(mut self, incident_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.incident_id = Some(incident_id.into());
+        self
+    }
+
+    /// Only return invocations in `state`
+    pub fn set_state(mut self, state: InvocationState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Only return invocations which aren't in `state`, such as `InvocationState::Completed`
+    pub fn set_not_state(mut self, state: InvocationState) -> Self {
+        self.not_state = Some(state);
+        self
+    }
+}
+
+impl<'a> Requestable for ListInvocations<'a> {
+    type Response = Response<Vec<Invocation>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref action_id) = self.action_id {
+            query.push("action_id", action_id);
+        }
+        if let Some(ref incident_id) = self.incident_id {
+            query.push("incident_id", incident_id);
+        }
+        if let Some(state) = self.state {
+            query.push("invocation_state", state.as_str());
+        }
+        if let Some(state) = self.not_state {
+            query.push("not_invocation_state", state.as_str());
+        }
+
+        query.url("/automation_actions/invocations").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<Invocation>>> {
+        get_response(status, headers, body, "invocations")
+    }
+}
+
+/// Get details about an invocation of an automation action
+#[derive(Debug)]
+pub struct GetInvocation<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetInvocation<'a> {
+    /// Create a request for invocation `id`
+    pub fn new<S>(id: S) -> GetInvocation<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetInvocation {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetInvocation<'a> {
+    type Response = Response<Invocation>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/invocations/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Invocation>> {
+        get_response(status, headers, body, "invocation")
+    }
+}
+
+/// List runners, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListRunners<'a> {
+    name: Option<Cow<'a, str>>,
+    limit: Option<usize>,
+    cursor: Option<Cow<'a, str>>,
+}
+
+impl<'a> ListRunners<'a> {
+    /// Create a request listing all runners
+    pub fn new() -> ListRunners<'a> {
+        ListRunners::default()
+    }
+
+    /// Only return runners whose names contain `name`
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the number of runners per page
+    pub fn set_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the page at `cursor`, the `next_cursor` of the previous page
+    pub fn set_cursor<S>(mut self, cursor: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListRunners<'a> {
+    type Response = Response<Runners>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref name) = self.name {
+            query.push("name", name);
+        }
+        if let Some(limit) = self.limit {
+            query.push("limit", limit.to_string());
+        }
+        if let Some(ref cursor) = self.cursor {
+            query.push("cursor", cursor);
+        }
+
+        query.url("/automation_actions/runners").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Runners>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// Get details about an existing runner, including its health
+#[derive(Debug)]
+pub struct GetRunner<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetRunner<'a> {
+    /// Create a request for runner `id`
+    pub fn new<S>(id: S) -> GetRunner<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetRunner {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetRunner<'a> {
+    type Response = Response<Runner>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/runners/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Runner>> {
+        get_response(status, headers, body, "runner")
+    }
+}
+
+/// Fields of a runner to create or update
+///
+/// The type of a runner can't be changed once it is created.
+#[derive(Debug, Default, Serialize)]
+pub struct RunnerFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    runner_type: Option<RunnerType>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    runbook_base_uri: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    runbook_api_key: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    teams: Vec<Reference>,
+}
+
+impl<'a> RunnerFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> RunnerFields<'a> {
+        RunnerFields::default()
+    }
+
+    /// Set the name of the runner
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the description of the runner
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the kind of runner
+    pub fn set_runner_type(mut self, runner_type: RunnerType) -> Self {
+        self.runner_type = Some(runner_type);
+        self
+    }
+
+    /// Set the URL of the Runbook Automation instance, for `RunnerType::Runbook`
+    pub fn set_runbook_base_uri<S>(mut self, uri: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.runbook_base_uri = Some(uri.into());
+        self
+    }
+
+    /// Set the API key of the Runbook Automation instance, for `RunnerType::Runbook`
+    pub fn set_runbook_api_key<S>(mut self, api_key: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.runbook_api_key = Some(api_key.into());
+        self
+    }
+
+    /// Allow team `team_id` to use the runner; may be called more than once
+    pub fn add_team<S>(mut self, team_id: S) -> Self
+        where S: Into<String>
+    {
+        self.teams.push(Reference::new(team_id, "team_reference"));
+        self
+    }
+}
+
+/// Register a new runner
+#[derive(Debug, Serialize)]
+pub struct CreateRunner<'a> {
+    runner: RunnerFields<'a>,
+}
+
+impl<'a> CreateRunner<'a> {
+    /// Create a request for a new runner described by `fields`
+    ///
+    /// PagerDuty requires a name and runner type, and the Runbook Automation URL and API key for
+    /// runbook runners.
+    pub fn new(fields: RunnerFields<'a>) -> CreateRunner<'a> {
+        CreateRunner {
+            runner: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateRunner<'a> {
+    type Response = Response<Runner>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/runners", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Runner>> {
+        get_response(status, headers, body, "runner")
+    }
+}
+
+/// Update an existing runner
+#[derive(Debug, Serialize)]
+pub struct UpdateRunner<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    runner: RunnerFields<'a>,
+}
+
+impl<'a> UpdateRunner<'a> {
+    /// Create a request applying `fields` to runner `id`
+    pub fn new<S>(id: S, fields: RunnerFields<'a>) -> UpdateRunner<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateRunner {
+            id: id.into(),
+            runner: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateRunner<'a> {
+    type Response = Response<Runner>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/runners/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Runner>> {
+        get_response(status, headers, body, "runner")
+    }
+}
+
+/// Delete an existing runner
+#[derive(Debug)]
+pub struct DeleteRunner<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteRunner<'a> {
+    /// Create a request deleting runner `id`
+    pub fn new<S>(id: S) -> DeleteRunner<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteRunner {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteRunner<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/automation_actions/runners/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List automation actions
+pub fn list(auth: &AuthToken,
+            request: &ListAutomationActions) -> request::Result<Response<AutomationActions>> {
+    request::perform(auth, request)
+}
+
+/// Get a single automation action
+pub fn get(auth: &AuthToken,
+           request: &GetAutomationAction) -> request::Result<Response<AutomationAction>> {
+    request::perform(auth, request)
+}
+
+/// Create an automation action
+pub fn create(auth: &AuthToken,
+              request: &CreateAutomationAction) -> request::Result<Response<AutomationAction>> {
+    request::perform(auth, request)
+}
+
+/// Update an automation action
+pub fn update(auth: &AuthToken,
+              request: &UpdateAutomationAction) -> request::Result<Response<AutomationAction>> {
+    request::perform(auth, request)
+}
+
+/// Delete an automation action
+pub fn delete(auth: &AuthToken,
+              request: &DeleteAutomationAction) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Invoke an automation action on an incident
+pub fn invoke(auth: &AuthToken,
+              request: &InvokeAutomationAction) -> request::Result<Response<Invocation>> {
+    request::perform(auth, request)
+}
+
+/// List invocations of automation actions
+pub fn list_invocations(auth: &AuthToken,
+                        request: &ListInvocations) -> request::Result<Response<Vec<Invocation>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single invocation of an automation action
+pub fn get_invocation(auth: &AuthToken,
+                      request: &GetInvocation) -> request::Result<Response<Invocation>> {
+    request::perform(auth, request)
+}
+
+/// List runners
+pub fn list_runners(auth: &AuthToken, request: &ListRunners) -> request::Result<Response<Runners>> {
+    request::perform(auth, request)
+}
+
+/// Get a single runner
+pub fn get_runner(auth: &AuthToken, request: &GetRunner) -> request::Result<Response<Runner>> {
+    request::perform(auth, request)
+}
+
+/// Register a runner
+pub fn create_runner(auth: &AuthToken,
+                     request: &CreateRunner) -> request::Result<Response<Runner>> {
+    request::perform(auth, request)
+}
+
+/// Update a runner
+pub fn update_runner(auth: &AuthToken,
+                     request: &UpdateRunner) -> request::Result<Response<Runner>> {
+    request::perform(auth, request)
+}
+
+/// Delete a runner
+pub fn delete_runner(auth: &AuthToken, request: &DeleteRunner) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{ActionClassification, AutomationActionFields, CreateAutomationAction, CreateRunner,
+                GetInvocation, InvocationState, InvokeAutomationAction, ListInvocations,
+                ListRunners, RunnerFields, RunnerStatus, RunnerType};
+
+    #[test]
+    fn create_script_action() {
+        let expected: Json = from_str(stringify!({
+            "action": {
+                "name": "Disk usage",
+                "action_type": "script",
+                "action_classification": "diagnostic",
+                "action_data_reference": {
+                    "script": "df -h",
+                    "invocation_command": "/bin/bash"
+                },
+                "runner": "01DF4OBNYKW84FS9CCYVYS1MOS",
+                "services": [ { "id": "PIJ90N7", "type": "service_reference" } ]
+            }
+        })).unwrap();
+
+        let fields = AutomationActionFields::new()
+            .set_name("Disk usage")
+            .set_classification(ActionClassification::Diagnostic)
+            .set_script("df -h", Some("/bin/bash"))
+            .set_runner("01DF4OBNYKW84FS9CCYVYS1MOS")
+            .add_service("PIJ90N7");
+        let request = CreateAutomationAction::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn invoke_action() {
+        let request = InvokeAutomationAction::new("01DF4OBNYKW84FS9CCYVYS1MOS", "PT4KHLK");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/automation_actions/actions/\
+                    01DF4OBNYKW84FS9CCYVYS1MOS/invocations");
+        assert_eq!(request.body(), r#"{"invocation":{"metadata":{"incident_id":"PT4KHLK"}}}"#);
+
+        let request = ListInvocations::new()
+            .set_incident_id("PT4KHLK")
+            .set_not_state(InvocationState::Completed);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/automation_actions/invocations\
+                    ?incident_id=PT4KHLK&not_invocation_state=completed");
+    }
+
+    #[test]
+    fn invocation_from_json() {
+        let body = stringify!({
+            "invocation": {
+                "id": "01DF4OBNYKW84FS9CCYVYS1MOT",
+                "type": "invocation",
+                "action_id": "01DF4OBNYKW84FS9CCYVYS1MOS",
+                "runner_id": "01DF4O9T1MDPYOUT7SUX9EXZ4R",
+                "state": "completed",
+                "action_snapshot": {
+                    "name": "Disk usage",
+                    "action_type": "script",
+                    "action_data_reference": { "script": "df -h" }
+                },
+                "metadata": {
+                    "incident_id": "PT4KHLK",
+                    "agent": { "id": "PXPGF42", "type": "user_reference" }
+                },
+                "timing": [
+                    { "state": "created", "creation_timestamp": "2023-01-03T10:00:00Z" },
+                    { "state": "completed", "creation_timestamp": "2023-01-03T10:00:05Z" }
+                ],
+                "duration": 5000
+            }
+        });
+
+        let res = GetInvocation::get_response(StatusCode::Ok, &Headers::new(), body);
+        let invocation = res.unwrap().success().unwrap();
+
+        assert_eq!(invocation.state, InvocationState::Completed);
+        assert_eq!(invocation.timing[1].state, InvocationState::Completed);
+        assert_eq!(invocation.metadata.unwrap().agent.unwrap().id, "PXPGF42");
+        assert_eq!(invocation.duration, Some(5000));
+    }
+
+    #[test]
+    fn create_runbook_runner() {
+        let expected: Json = from_str(stringify!({
+            "runner": {
+                "name": "Runbook Automation",
+                "runner_type": "runbook",
+                "runbook_base_uri": "example.runbook.pagerduty.cloud",
+                "runbook_api_key": "secret",
+                "teams": [ { "id": "PQ9K7I8", "type": "team_reference" } ]
+            }
+        })).unwrap();
+
+        let fields = RunnerFields::new()
+            .set_name("Runbook Automation")
+            .set_runner_type(RunnerType::Runbook)
+            .set_runbook_base_uri("example.runbook.pagerduty.cloud")
+            .set_runbook_api_key("secret")
+            .add_team("PQ9K7I8");
+        let request = CreateRunner::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn runners_from_json() {
+        let body = stringify!({
+            "runners": [
+                {
+                    "id": "01DF4O9T1MDPYOUT7SUX9EXZ4R",
+                    "type": "runner",
+                    "summary": "Sidecar in us-east-1",
+                    "name": "Sidecar in us-east-1",
+                    "runner_type": "sidecar",
+                    "status": "Configured",
+                    "last_seen": "2023-01-03T10:00:00Z",
+                    "teams": [],
+                    "creation_time": "2022-12-01T00:00:00Z"
+                },
+                {
+                    "id": "01DF4O9T1MDPYOUT7SUX9EXZ4S",
+                    "type": "runner",
+                    "name": "Sidecar in eu-west-1",
+                    "runner_type": "sidecar",
+                    "status": "NotConfigured",
+                    "last_seen": null
+                }
+            ],
+            "next_cursor": null,
+            "limit": 2
+        });
+
+        let res = ListRunners::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.runners[0].status, Some(RunnerStatus::Configured));
+        assert_eq!(page.runners[0].last_seen, Some("2023-01-03T10:00:00Z".to_owned()));
+        assert_eq!(page.runners[1].status, Some(RunnerStatus::NotConfigured));
+        assert_eq!(page.runners[1].last_seen, None);
+    }
+}
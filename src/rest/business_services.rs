@@ -0,0 +1,894 @@
+//! Business Services
+//!
+//! A business service models a capability delivered to customers, such as online checkout, which
+//! is supported by technical services. Stakeholders subscribe to business services to be kept
+//! informed of incidents affecting them.
+//!
+//! Subscribers can be managed from either side; here by business service, or by user or team in
+//! [`notification_subscriptions`](../notification_subscriptions/index.html).
+//!
+//! # Impact
+//!
+//! The business services currently affected by incidents are listed with
+//! [`list_impacts`](fn.list_impacts.html), and the incidents affecting them with
+//! [`list_impactors`](fn.list_impactors.html). These endpoints are in early access, and this
+//! library sends the header PagerDuty requires to use them.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_body_response, get_empty_response, get_page, get_response, segment, ListRequest,
+            Page, Pagination, Query, Reference, Response, BASE_URL};
+use super::notification_subscriptions::{Subscriber, Subscription, UnsubscribeResult};
+
+/// A capability delivered to customers
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct BusinessService {
+    /// The business service's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the business service
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the business service is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the business service is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the business service
+    pub name: String,
+
+    /// A description of the business service
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The owner of the business service
+    #[serde(default)]
+    pub point_of_contact: Option<String>,
+
+    /// The team owning the business service, if any
+    #[serde(default)]
+    pub team: Option<Reference>,
+}
+
+/// A user or team subscribed to a business service
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct BusinessServiceSubscriber {
+    /// The ID of the user or team
+    pub subscriber_id: String,
+
+    /// Either `user` or `team`
+    pub subscriber_type: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SubscriberRef<'a> {
+    subscriber_id: Cow<'a, str>,
+    subscriber_type: &'static str,
+}
+
+impl<'a> SubscriberRef<'a> {
+    fn new(subscriber: Subscriber<'a>) -> SubscriberRef<'a> {
+        SubscriberRef {
+            subscriber_type: subscriber.kind(),
+            subscriber_id: match subscriber {
+                Subscriber::User(id) | Subscriber::Team(id) => id,
+            },
+        }
+    }
+}
+
+/// Value of the `X-EARLY-ACCESS` header required by the business impact endpoints
+const IMPACT_EARLY_ACCESS: &str = "business-impact-early-access";
+
+/// Whether a business service is affected by incidents
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactStatus {
+    Impacted,
+    NotImpacted,
+
+    /// A status this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// The impact of incidents on a business service
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Impact {
+    /// The business service's ID
+    pub id: String,
+
+    /// The kind of object impacted, `business_service`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The name of the business service
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Whether the business service is impacted
+    pub status: ImpactStatus,
+
+    /// Details included on request, such as the highest impacting priority
+    #[serde(default)]
+    pub additional_fields: Option<ImpactDetails>,
+}
+
+/// Details of an impact included on request
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ImpactDetails {
+    /// The most severe priority among incidents impacting the business service, when
+    /// `"services.highest_impacting_priority"` is included
+    #[serde(default)]
+    pub highest_impacting_priority: Option<ImpactingPriority>,
+}
+
+/// A priority impacting a business service
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ImpactingPriority {
+    /// The priority's ID
+    pub id: String,
+
+    /// The rank of the priority; higher is more severe
+    pub order: u64,
+}
+
+/// An object, such as an incident, impacting business services
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Impactor {
+    /// The ID of the object
+    pub id: String,
+
+    /// The kind of object, such as `incident`
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// List business services
+#[derive(Debug, Default)]
+pub struct ListBusinessServices {
+    pagination: Pagination,
+}
+
+impl ListBusinessServices {
+    /// Create a request listing all business services
+    pub fn new() -> ListBusinessServices {
+        ListBusinessServices::default()
+    }
+}
+
+impl ListRequest for ListBusinessServices {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl Requestable for ListBusinessServices {
+    type Response = Response<Page<BusinessService>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url("/business_services").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<BusinessService>>> {
+        get_page(status, headers, body, "business_services")
+    }
+}
+
+/// Get details about an existing business service
+#[derive(Debug)]
+pub struct GetBusinessService<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetBusinessService<'a> {
+    /// Create a request for business service `id`
+    pub fn new<S>(id: S) -> GetBusinessService<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetBusinessService {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetBusinessService<'a> {
+    type Response = Response<BusinessService>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<BusinessService>> {
+        get_response(status, headers, body, "business_service")
+    }
+}
+
+/// Writable fields of a business service
+#[derive(Debug, Default, Serialize)]
+pub struct BusinessServiceFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    point_of_contact: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    team: Option<Reference>,
+}
+
+impl<'a> BusinessServiceFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> BusinessServiceFields<'a> {
+        BusinessServiceFields::default()
+    }
+
+    /// Set the name of the business service
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the description of the business service
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the owner of the business service
+    pub fn set_point_of_contact<S>(mut self, point_of_contact: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.point_of_contact = Some(point_of_contact.into());
+        self
+    }
+
+    /// Set the team owning the business service
+    pub fn set_team<S>(mut self, team_id: S) -> Self
+        where S: Into<String>
+    {
+        self.team = Some(Reference::new(team_id, "team_reference"));
+        self
+    }
+}
+
+/// Create a new business service
+#[derive(Debug, Serialize)]
+pub struct CreateBusinessService<'a> {
+    business_service: BusinessServiceFields<'a>,
+}
+
+impl<'a> CreateBusinessService<'a> {
+    /// Create a request for a new business service described by `fields`
+    ///
+    /// PagerDuty requires at least a name.
+    pub fn new(fields: BusinessServiceFields<'a>) -> CreateBusinessService<'a> {
+        CreateBusinessService {
+            business_service: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateBusinessService<'a> {
+    type Response = Response<BusinessService>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<BusinessService>> {
+        get_response(status, headers, body, "business_service")
+    }
+}
+
+/// Update an existing business service
+#[derive(Debug, Serialize)]
+pub struct UpdateBusinessService<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    business_service: BusinessServiceFields<'a>,
+}
+
+impl<'a> UpdateBusinessService<'a> {
+    /// Create a request applying `fields` to business service `id`
+    pub fn new<S>(id: S, fields: BusinessServiceFields<'a>) -> UpdateBusinessService<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateBusinessService {
+            id: id.into(),
+            business_service: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateBusinessService<'a> {
+    type Response = Response<BusinessService>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<BusinessService>> {
+        get_response(status, headers, body, "business_service")
+    }
+}
+
+/// Delete an existing business service
+#[derive(Debug)]
+pub struct DeleteBusinessService<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteBusinessService<'a> {
+    /// Create a request deleting business service `id`
+    pub fn new<S>(id: S) -> DeleteBusinessService<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteBusinessService {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteBusinessService<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List the subscribers of a business service
+#[derive(Debug)]
+pub struct ListSubscribers<'a> {
+    id: Cow<'a, str>,
+    pagination: Pagination,
+}
+
+impl<'a> ListSubscribers<'a> {
+    /// Create a request listing the subscribers of business service `id`
+    pub fn new<S>(id: S) -> ListSubscribers<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListSubscribers {
+            id: id.into(),
+            pagination: Pagination::default(),
+        }
+    }
+}
+
+impl<'a> ListRequest for ListSubscribers<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListSubscribers<'a> {
+    type Response = Response<Page<BusinessServiceSubscriber>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url(&format!("/business_services/{}/subscribers", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<BusinessServiceSubscriber>>> {
+        get_page(status, headers, body, "subscribers")
+    }
+}
+
+/// Subscribe users and teams to a business service
+#[derive(Debug, Serialize)]
+pub struct AddSubscribers<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    subscribers: Vec<SubscriberRef<'a>>,
+}
+
+impl<'a> AddSubscribers<'a> {
+    /// Create a request subscribing to business service `id`
+    pub fn new<S>(id: S) -> AddSubscribers<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AddSubscribers {
+            id: id.into(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Subscribe `subscriber`; may be called more than once
+    pub fn add_subscriber(mut self, subscriber: Subscriber<'a>) -> Self {
+        self.subscribers.push(SubscriberRef::new(subscriber));
+        self
+    }
+}
+
+impl<'a> Requestable for AddSubscribers<'a> {
+    type Response = Response<Vec<Subscription>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services/{}/subscribers", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<Subscription>>> {
+        get_response(status, headers, body, "subscriptions")
+    }
+}
+
+/// Unsubscribe users and teams from a business service
+#[derive(Debug, Serialize)]
+pub struct RemoveSubscribers<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    subscribers: Vec<SubscriberRef<'a>>,
+}
+
+impl<'a> RemoveSubscribers<'a> {
+    /// Create a request unsubscribing from business service `id`
+    pub fn new<S>(id: S) -> RemoveSubscribers<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        RemoveSubscribers {
+            id: id.into(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Unsubscribe `subscriber`; may be called more than once
+    pub fn add_subscriber(mut self, subscriber: Subscriber<'a>) -> Self {
+        self.subscribers.push(SubscriberRef::new(subscriber));
+        self
+    }
+}
+
+impl<'a> Requestable for RemoveSubscribers<'a> {
+    type Response = Response<UnsubscribeResult>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services/{}/unsubscribe", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<UnsubscribeResult>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// List business services along with their current impact
+#[derive(Debug, Default)]
+pub struct ListImpacts<'a> {
+    ids: Vec<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ListImpacts<'a> {
+    /// Create a request listing the impact on all business services
+    pub fn new() -> ListImpacts<'a> {
+        ListImpacts::default()
+    }
+
+    /// Only return the impact on business service `id`; may be called more than once
+    pub fn add_id<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.ids.push(id.into());
+        self
+    }
+
+    /// Include additional details in the response, such as
+    /// `"services.highest_impacting_priority"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListImpacts<'a> {
+    type Response = Response<Page<Impact>>;
+
+    fn headers(&self) -> Headers {
+        let mut headers = super::headers(None);
+        headers.set_raw("X-EARLY-ACCESS", vec![IMPACT_EARLY_ACCESS.as_bytes().to_vec()]);
+        headers
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if !self.ids.is_empty() {
+            query.push("ids", self.ids.join(","));
+        }
+        query.push_array("include", &self.include);
+        query.url("/business_services/impacts").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Impact>>> {
+        get_page(status, headers, body, "services")
+    }
+}
+
+/// List the objects, such as incidents, currently impacting business services
+#[derive(Debug, Default)]
+pub struct ListImpactors;
+
+impl ListImpactors {
+    /// Create a request listing all impactors
+    pub fn new() -> ListImpactors {
+        ListImpactors
+    }
+}
+
+impl Requestable for ListImpactors {
+    type Response = Response<Page<Impactor>>;
+
+    fn headers(&self) -> Headers {
+        let mut headers = super::headers(None);
+        headers.set_raw("X-EARLY-ACCESS", vec![IMPACT_EARLY_ACCESS.as_bytes().to_vec()]);
+        headers
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/business_services/impactors", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Impactor>>> {
+        get_page(status, headers, body, "impactors")
+    }
+}
+
+/// List business services
+pub fn list(auth: &AuthToken, request: &ListBusinessServices)
+    -> request::Result<Response<Page<BusinessService>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get a single business service
+pub fn get(auth: &AuthToken,
+           request: &GetBusinessService) -> request::Result<Response<BusinessService>> {
+    request::perform(auth, request)
+}
+
+/// Create a business service
+pub fn create(auth: &AuthToken,
+              request: &CreateBusinessService) -> request::Result<Response<BusinessService>> {
+    request::perform(auth, request)
+}
+
+/// Update a business service
+pub fn update(auth: &AuthToken,
+              request: &UpdateBusinessService) -> request::Result<Response<BusinessService>> {
+    request::perform(auth, request)
+}
+
+/// Delete a business service
+pub fn delete(auth: &AuthToken, request: &DeleteBusinessService) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List the subscribers of a business service
+pub fn list_subscribers(auth: &AuthToken, request: &ListSubscribers)
+    -> request::Result<Response<Page<BusinessServiceSubscriber>>>
+{
+    request::perform(auth, request)
+}
+
+/// Subscribe users and teams to a business service
+pub fn add_subscribers(auth: &AuthToken,
+                       request: &AddSubscribers) -> request::Result<Response<Vec<Subscription>>> {
+    request::perform(auth, request)
+}
+
+/// Unsubscribe users and teams from a business service
+pub fn remove_subscribers(auth: &AuthToken, request: &RemoveSubscribers)
+    -> request::Result<Response<UnsubscribeResult>>
+{
+    request::perform(auth, request)
+}
+
+/// List the impact on business services
+pub fn list_impacts(auth: &AuthToken,
+                    request: &ListImpacts) -> request::Result<Response<Page<Impact>>> {
+    request::perform(auth, request)
+}
+
+/// List the objects impacting business services
+pub fn list_impactors(auth: &AuthToken,
+                      request: &ListImpactors) -> request::Result<Response<Page<Impactor>>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use rest::notification_subscriptions::Subscriber;
+    use super::{AddSubscribers, BusinessServiceFields, CreateBusinessService, ImpactStatus,
+                ListImpacts, ListSubscribers};
+
+    #[test]
+    fn impacts_from_json() {
+        let request = ListImpacts::new()
+            .add_id("P7ZRB5X")
+            .add_id("PD1234")
+            .add_include("services.highest_impacting_priority");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/business_services/impacts?ids=P7ZRB5X%2CPD1234&\
+                    include%5B%5D=services.highest_impacting_priority");
+        assert!(request.headers().get_raw("X-EARLY-ACCESS").is_some());
+
+        let body = stringify!({
+            "services": [
+                {
+                    "id": "P7ZRB5X",
+                    "name": "Checkout",
+                    "type": "business_service",
+                    "status": "impacted",
+                    "additional_fields": {
+                        "highest_impacting_priority": { "id": "PSO75BM", "order": 256 }
+                    }
+                },
+                {
+                    "id": "PD1234",
+                    "name": "Search",
+                    "type": "business_service",
+                    "status": "not_impacted"
+                }
+            ],
+            "limit": 100,
+            "more": false
+        });
+
+        let res = ListImpacts::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+        let priority = page.items[0].additional_fields.as_ref()
+            .and_then(|fields| fields.highest_impacting_priority.as_ref())
+            .unwrap();
+
+        assert_eq!(page.items[0].status, ImpactStatus::Impacted);
+        assert_eq!(priority.order, 256);
+        assert_eq!(page.items[1].status, ImpactStatus::NotImpacted);
+    }
+
+    #[test]
+    fn add_subscribers_body() {
+        let expected: Json = from_str(stringify!({
+            "subscribers": [
+                { "subscriber_id": "PXPGF42", "subscriber_type": "user" },
+                { "subscriber_id": "PQ9K7I8", "subscriber_type": "team" }
+            ]
+        })).expect("expected is valid json");
+
+        let request = AddSubscribers::new("P7ZRB5X")
+            .add_subscriber(Subscriber::user("PXPGF42"))
+            .add_subscriber(Subscriber::team("PQ9K7I8"));
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/business_services/P7ZRB5X/subscribers");
+    }
+
+    #[test]
+    fn subscribers_from_json() {
+        let body = stringify!({
+            "subscribers": [
+                { "subscriber_id": "PXPGF42", "subscriber_type": "user" }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false,
+            "total": null
+        });
+
+        let res = ListSubscribers::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].subscriber_type, "user");
+    }
+
+    #[test]
+    fn create_business_service_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "business_service": {
+                "name": "Checkout",
+                "point_of_contact": "payments@example.com",
+                "team": { "id": "PQ9K7I8", "type": "team_reference" }
+            }
+        })).expect("expected is valid json");
+
+        let fields = BusinessServiceFields::new()
+            .set_name("Checkout")
+            .set_point_of_contact("payments@example.com")
+            .set_team("PQ9K7I8");
+        let request = CreateBusinessService::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "business_service": {
+                "id": "P7ZRB5X",
+                "type": "business_service",
+                "summary": "Checkout",
+                "self": "https://api.pagerduty.com/business_services/P7ZRB5X",
+                "html_url": null,
+                "name": "Checkout",
+                "description": null,
+                "point_of_contact": "payments@example.com",
+                "team": { "id": "PQ9K7I8", "type": "team_reference" }
+            }
+        });
+
+        let res = CreateBusinessService::get_response(StatusCode::Created, &Headers::new(), body);
+        let service = res.unwrap().success().unwrap();
+
+        assert_eq!(service.id, "P7ZRB5X");
+        assert_eq!(service.description, None);
+    }
+}
@@ -0,0 +1,1180 @@
+//! Custom Fields
+//!
+//! Custom fields extend incidents with account-specific metadata, such as the affected region or
+//! the tier of the affected customer. Each field has a data type, and fields with a fixed set of
+//! allowed values have field options listing them.
+//!
+//! This module manages the definitions of fields and their options, and the values of fields on
+//! individual incidents.
+//!
+//! # Values
+//!
+//! The values of fields on an incident are read with [`get_values`](fn.get_values.html) and
+//! written with [`set_values`](fn.set_values.html). Values are represented by
+//! [`FieldValue`](enum.FieldValue.html), whose variants follow the field's `DataType`.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde::{Serialize, Serializer};
+use serde_json::{to_string, to_value, Value as Json};
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_response, segment, Query, Response, BASE_URL};
+
+/// The type of a custom field's values
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DataType {
+    Boolean,
+    Integer,
+    Float,
+    String,
+    Datetime,
+    Url,
+}
+
+/// How many values a custom field holds, and whether they're restricted to its options
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    /// A single value of any kind
+    SingleValue,
+
+    /// A single value from the field's options
+    SingleValueFixed,
+
+    /// Any number of values of any kind
+    MultiValue,
+
+    /// Any number of values from the field's options
+    MultiValueFixed,
+}
+
+/// An allowed value of a custom field
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct FieldOption {
+    /// The field option's ID
+    pub id: String,
+
+    /// The type of object, `field_option`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The value of the option
+    pub data: FieldOptionData,
+
+    /// The time at which the option was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+
+    /// The time at which the option was last updated
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// The value of a field option
+///
+/// Field options are only supported on fields of `DataType::String`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FieldOptionData {
+    pub data_type: DataType,
+    pub value: String,
+}
+
+/// The definition of a custom field on incidents
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct CustomField {
+    /// The field's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the field
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the field is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// The name of the field, used to refer to it in the API
+    pub name: String,
+
+    /// The name of the field shown to users
+    pub display_name: String,
+
+    /// A description of the field
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The type of the field's values
+    pub data_type: DataType,
+
+    /// How many values the field holds, and whether they're restricted to its options
+    pub field_type: FieldType,
+
+    /// The value of the field on incidents which don't set it
+    #[serde(default)]
+    pub default_value: Option<Json>,
+
+    /// The allowed values of the field; only included when requested
+    #[serde(default)]
+    pub field_options: Vec<FieldOption>,
+
+    /// The time at which the field was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+
+    /// The time at which the field was last updated
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+/// List the custom fields of incidents
+#[derive(Debug, Default)]
+pub struct ListCustomFields<'a> {
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ListCustomFields<'a> {
+    /// Create a request listing all custom fields
+    pub fn new() -> ListCustomFields<'a> {
+        ListCustomFields::default()
+    }
+
+    /// Include additional details, such as `field_options`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListCustomFields<'a> {
+    type Response = Response<Vec<CustomField>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+
+        query.url("/incidents/custom_fields").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<CustomField>>> {
+        get_response(status, headers, body, "fields")
+    }
+}
+
+/// Get details about an existing custom field
+#[derive(Debug)]
+pub struct GetCustomField<'a> {
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetCustomField<'a> {
+    /// Create a request for custom field `id`
+    pub fn new<S>(id: S) -> GetCustomField<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetCustomField {
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional details, such as `field_options`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetCustomField<'a> {
+    type Response = Response<CustomField>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+
+        query.url(&format!("/incidents/custom_fields/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<CustomField>> {
+        get_response(status, headers, body, "field")
+    }
+}
+
+/// Fields of a custom field definition to create or update
+///
+/// The name, data type, and field type of a custom field can't be changed once it is created.
+#[derive(Debug, Default, Serialize)]
+pub struct CustomFieldFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    display_name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    data_type: Option<DataType>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    field_type: Option<FieldType>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    default_value: Option<Json>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    field_options: Vec<FieldOptionFields<'a>>,
+}
+
+impl<'a> CustomFieldFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> CustomFieldFields<'a> {
+        CustomFieldFields::default()
+    }
+
+    /// Set the name of the field, used to refer to it in the API
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the name of the field shown to users
+    pub fn set_display_name<S>(mut self, display_name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Set the description of the field
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the type of the field's values
+    pub fn set_data_type(mut self, data_type: DataType) -> Self {
+        self.data_type = Some(data_type);
+        self
+    }
+
+    /// Set how many values the field holds, and whether they're restricted to its options
+    pub fn set_field_type(mut self, field_type: FieldType) -> Self {
+        self.field_type = Some(field_type);
+        self
+    }
+
+    /// Set the value of the field on incidents which don't set it
+    ///
+    /// # Panics
+    ///
+    /// If `value` can't be serialized to JSON.
+    pub fn set_default_value<T>(mut self, value: &T) -> Self
+        where T: ?Sized + Serialize
+    {
+        self.default_value = Some(to_value(value).unwrap());
+        self
+    }
+
+    /// Add an allowed string `value` when creating a field; may be called more than once
+    ///
+    /// Options of an existing field are managed with [`create_option`](fn.create_option.html)
+    /// and friends.
+    pub fn add_option<S>(mut self, value: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.field_options.push(FieldOptionFields::new(value));
+        self
+    }
+}
+
+/// Create a new custom field
+#[derive(Debug, Serialize)]
+pub struct CreateCustomField<'a> {
+    field: CustomFieldFields<'a>,
+}
+
+impl<'a> CreateCustomField<'a> {
+    /// Create a request for a new custom field described by `fields`
+    ///
+    /// PagerDuty requires a name, display name, data type, and field type.
+    pub fn new(fields: CustomFieldFields<'a>) -> CreateCustomField<'a> {
+        CreateCustomField {
+            field: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateCustomField<'a> {
+    type Response = Response<CustomField>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<CustomField>> {
+        get_response(status, headers, body, "field")
+    }
+}
+
+/// Update an existing custom field
+#[derive(Debug, Serialize)]
+pub struct UpdateCustomField<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    field: CustomFieldFields<'a>,
+}
+
+impl<'a> UpdateCustomField<'a> {
+    /// Create a request applying `fields` to custom field `id`
+    pub fn new<S>(id: S, fields: CustomFieldFields<'a>) -> UpdateCustomField<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateCustomField {
+            id: id.into(),
+            field: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateCustomField<'a> {
+    type Response = Response<CustomField>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<CustomField>> {
+        get_response(status, headers, body, "field")
+    }
+}
+
+/// Delete a custom field, and its values on all incidents
+#[derive(Debug)]
+pub struct DeleteCustomField<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteCustomField<'a> {
+    /// Create a request deleting custom field `id`
+    pub fn new<S>(id: S) -> DeleteCustomField<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteCustomField {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteCustomField<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// A field option to create or update
+#[derive(Debug, Serialize)]
+struct FieldOptionFields<'a> {
+    data: FieldOptionValue<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct FieldOptionValue<'a> {
+    data_type: DataType,
+    value: Cow<'a, str>,
+}
+
+impl<'a> FieldOptionFields<'a> {
+    fn new<S>(value: S) -> FieldOptionFields<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        FieldOptionFields {
+            data: FieldOptionValue {
+                data_type: DataType::String,
+                value: value.into(),
+            },
+        }
+    }
+}
+
+/// List the options of a custom field
+#[derive(Debug)]
+pub struct ListFieldOptions<'a> {
+    field_id: Cow<'a, str>,
+}
+
+impl<'a> ListFieldOptions<'a> {
+    /// Create a request listing the options of custom field `field_id`
+    pub fn new<S>(field_id: S) -> ListFieldOptions<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListFieldOptions {
+            field_id: field_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for ListFieldOptions<'a> {
+    type Response = Response<Vec<FieldOption>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields/{}/field_options",
+                BASE_URL,
+                segment(&self.field_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<FieldOption>>> {
+        get_response(status, headers, body, "field_options")
+    }
+}
+
+/// Add an allowed value to a custom field
+#[derive(Debug, Serialize)]
+pub struct CreateFieldOption<'a> {
+    #[serde(skip_serializing)]
+    field_id: Cow<'a, str>,
+
+    field_option: FieldOptionFields<'a>,
+}
+
+impl<'a> CreateFieldOption<'a> {
+    /// Create a request adding string `value` to the options of custom field `field_id`
+    pub fn new<S>(field_id: S, value: S) -> CreateFieldOption<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateFieldOption {
+            field_id: field_id.into(),
+            field_option: FieldOptionFields::new(value),
+        }
+    }
+}
+
+impl<'a> Requestable for CreateFieldOption<'a> {
+    type Response = Response<FieldOption>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields/{}/field_options",
+                BASE_URL,
+                segment(&self.field_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<FieldOption>> {
+        get_response(status, headers, body, "field_option")
+    }
+}
+
+/// Change the value of a custom field's option
+#[derive(Debug, Serialize)]
+pub struct UpdateFieldOption<'a> {
+    #[serde(skip_serializing)]
+    field_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    field_option: FieldOptionFields<'a>,
+}
+
+impl<'a> UpdateFieldOption<'a> {
+    /// Create a request changing option `id` of custom field `field_id` to string `value`
+    pub fn new<S>(field_id: S, id: S, value: S) -> UpdateFieldOption<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateFieldOption {
+            field_id: field_id.into(),
+            id: id.into(),
+            field_option: FieldOptionFields::new(value),
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateFieldOption<'a> {
+    type Response = Response<FieldOption>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields/{}/field_options/{}",
+                BASE_URL, segment(&self.field_id), segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<FieldOption>> {
+        get_response(status, headers, body, "field_option")
+    }
+}
+
+/// Remove an allowed value from a custom field
+#[derive(Debug)]
+pub struct DeleteFieldOption<'a> {
+    field_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteFieldOption<'a> {
+    /// Create a request deleting option `id` of custom field `field_id`
+    pub fn new<S>(field_id: S, id: S) -> DeleteFieldOption<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteFieldOption {
+            field_id: field_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteFieldOption<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/custom_fields/{}/field_options/{}",
+                BASE_URL, segment(&self.field_id), segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// The value of a custom field on an incident
+///
+/// Each variant corresponds to a `DataType`; fields of the `multi_value` field types hold a
+/// `List` of them. Values which don't match the field's data type are kept as `Other`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+
+    /// A time in ISO 8601
+    Datetime(String),
+
+    Url(String),
+
+    /// The values of a multi-value field
+    List(Vec<FieldValue>),
+
+    /// A value which doesn't match the field's data type
+    Other(Json),
+}
+
+impl FieldValue {
+    /// Interpret `value` according to `data_type`, or `None` if it's `null`
+    pub fn from_json(data_type: DataType, value: Json) -> Option<FieldValue> {
+        let typed = match (data_type, value) {
+            (_, Json::Null) => return None,
+            (_, Json::Array(values)) => {
+                let values = values.into_iter()
+                    .filter_map(|value| FieldValue::from_json(data_type, value))
+                    .collect();
+                FieldValue::List(values)
+            },
+            (DataType::Boolean, Json::Bool(value)) => FieldValue::Boolean(value),
+            (DataType::Integer, Json::Number(ref value)) if value.is_i64() => {
+                FieldValue::Integer(value.as_i64().unwrap())
+            },
+            (DataType::Float, Json::Number(ref value)) => {
+                FieldValue::Float(value.as_f64().unwrap())
+            },
+            (DataType::String, Json::String(value)) => FieldValue::String(value),
+            (DataType::Datetime, Json::String(value)) => FieldValue::Datetime(value),
+            (DataType::Url, Json::String(value)) => FieldValue::Url(value),
+            (_, other) => FieldValue::Other(other),
+        };
+
+        Some(typed)
+    }
+}
+
+impl Serialize for FieldValue {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match *self {
+            FieldValue::Boolean(value) => serializer.serialize_bool(value),
+            FieldValue::Integer(value) => serializer.serialize_i64(value),
+            FieldValue::Float(value) => serializer.serialize_f64(value),
+            FieldValue::String(ref value) |
+            FieldValue::Datetime(ref value) |
+            FieldValue::Url(ref value) => serializer.serialize_str(value),
+            FieldValue::List(ref values) => values.serialize(serializer),
+            FieldValue::Other(ref value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> FieldValue {
+        FieldValue::Boolean(value)
+    }
+}
+
+impl From<i64> for FieldValue {
+    fn from(value: i64) -> FieldValue {
+        FieldValue::Integer(value)
+    }
+}
+
+impl From<f64> for FieldValue {
+    fn from(value: f64) -> FieldValue {
+        FieldValue::Float(value)
+    }
+}
+
+impl<'a> From<&'a str> for FieldValue {
+    fn from(value: &'a str) -> FieldValue {
+        FieldValue::String(value.to_owned())
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> FieldValue {
+        FieldValue::String(value)
+    }
+}
+
+/// A custom field and its value on an incident
+#[derive(Debug, PartialEq)]
+pub struct CustomFieldValue {
+    /// The field's ID
+    pub id: String,
+
+    /// The name of the field, used to refer to it in the API
+    pub name: String,
+
+    /// The name of the field shown to users
+    pub display_name: Option<String>,
+
+    /// A description of the field
+    pub description: Option<String>,
+
+    /// The type of the field's values
+    pub data_type: DataType,
+
+    /// How many values the field holds, and whether they're restricted to its options
+    pub field_type: Option<FieldType>,
+
+    /// The field's value, or `None` if it isn't set
+    pub value: Option<FieldValue>,
+}
+
+/// A field value as returned by the API, before `value` is interpreted by `data_type`
+#[derive(Debug, Deserialize)]
+struct RawFieldValue {
+    id: String,
+    name: String,
+
+    #[serde(default)]
+    display_name: Option<String>,
+
+    #[serde(default)]
+    description: Option<String>,
+
+    data_type: DataType,
+
+    #[serde(default)]
+    field_type: Option<FieldType>,
+
+    #[serde(default)]
+    value: Json,
+}
+
+impl From<RawFieldValue> for CustomFieldValue {
+    fn from(raw: RawFieldValue) -> CustomFieldValue {
+        CustomFieldValue {
+            value: FieldValue::from_json(raw.data_type, raw.value),
+            id: raw.id,
+            name: raw.name,
+            display_name: raw.display_name,
+            description: raw.description,
+            data_type: raw.data_type,
+            field_type: raw.field_type,
+        }
+    }
+}
+
+fn get_values_response(status: StatusCode,
+                       headers: &Headers,
+                       body: &str) -> request::Result<Response<Vec<CustomFieldValue>>> {
+    let res: Response<Vec<RawFieldValue>> = get_response(status, headers, body,
+                                                         "custom_fields")?;
+    Ok(res.map(|values| values.into_iter().map(CustomFieldValue::from).collect()))
+}
+
+/// Get the values of custom fields on an incident
+#[derive(Debug)]
+pub struct GetFieldValues<'a> {
+    incident_id: Cow<'a, str>,
+}
+
+impl<'a> GetFieldValues<'a> {
+    /// Create a request for the custom field values of incident `incident_id`
+    pub fn new<S>(incident_id: S) -> GetFieldValues<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetFieldValues {
+            incident_id: incident_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetFieldValues<'a> {
+    type Response = Response<Vec<CustomFieldValue>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/custom_fields/values", BASE_URL, segment(&self.incident_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<CustomFieldValue>>> {
+        get_values_response(status, headers, body)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct FieldValueUpdate<'a> {
+    name: Cow<'a, str>,
+    value: Option<FieldValue>,
+}
+
+/// Set the values of custom fields on an incident
+///
+/// Fields which aren't mentioned keep their current values.
+#[derive(Debug, Serialize)]
+pub struct SetFieldValues<'a> {
+    #[serde(skip_serializing)]
+    incident_id: Cow<'a, str>,
+
+    custom_fields: Vec<FieldValueUpdate<'a>>,
+}
+
+impl<'a> SetFieldValues<'a> {
+    /// Create a request setting custom field values on incident `incident_id`
+    pub fn new<S>(incident_id: S) -> SetFieldValues<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        SetFieldValues {
+            incident_id: incident_id.into(),
+            custom_fields: Vec::new(),
+        }
+    }
+
+    /// Set field `name` to `value`; may be called more than once
+    pub fn set_value<S, V>(mut self, name: S, value: V) -> Self
+        where S: Into<Cow<'a, str>>,
+              V: Into<FieldValue>
+    {
+        self.custom_fields.push(FieldValueUpdate {
+            name: name.into(),
+            value: Some(value.into()),
+        });
+        self
+    }
+
+    /// Unset field `name`; may be called more than once
+    pub fn clear_value<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.custom_fields.push(FieldValueUpdate {
+            name: name.into(),
+            value: None,
+        });
+        self
+    }
+}
+
+impl<'a> Requestable for SetFieldValues<'a> {
+    type Response = Response<Vec<CustomFieldValue>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/custom_fields/values", BASE_URL, segment(&self.incident_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<CustomFieldValue>>> {
+        get_values_response(status, headers, body)
+    }
+}
+
+/// List custom fields
+pub fn list(auth: &AuthToken,
+            request: &ListCustomFields) -> request::Result<Response<Vec<CustomField>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single custom field
+pub fn get(auth: &AuthToken, request: &GetCustomField) -> request::Result<Response<CustomField>> {
+    request::perform(auth, request)
+}
+
+/// Create a custom field
+pub fn create(auth: &AuthToken,
+              request: &CreateCustomField) -> request::Result<Response<CustomField>> {
+    request::perform(auth, request)
+}
+
+/// Update a custom field
+pub fn update(auth: &AuthToken,
+              request: &UpdateCustomField) -> request::Result<Response<CustomField>> {
+    request::perform(auth, request)
+}
+
+/// Delete a custom field
+pub fn delete(auth: &AuthToken, request: &DeleteCustomField) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List the options of a custom field
+pub fn list_options(auth: &AuthToken,
+                    request: &ListFieldOptions) -> request::Result<Response<Vec<FieldOption>>> {
+    request::perform(auth, request)
+}
+
+/// Add an option to a custom field
+pub fn create_option(auth: &AuthToken,
+                     request: &CreateFieldOption) -> request::Result<Response<FieldOption>> {
+    request::perform(auth, request)
+}
+
+/// Change an option of a custom field
+pub fn update_option(auth: &AuthToken,
+                     request: &UpdateFieldOption) -> request::Result<Response<FieldOption>> {
+    request::perform(auth, request)
+}
+
+/// Remove an option from a custom field
+pub fn delete_option(auth: &AuthToken,
+                     request: &DeleteFieldOption) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Get the values of custom fields on an incident
+pub fn get_values(auth: &AuthToken,
+                  request: &GetFieldValues) -> request::Result<Response<Vec<CustomFieldValue>>> {
+    request::perform(auth, request)
+}
+
+/// Set the values of custom fields on an incident
+pub fn set_values(auth: &AuthToken,
+                  request: &SetFieldValues) -> request::Result<Response<Vec<CustomFieldValue>>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{CreateCustomField, CreateFieldOption, CustomFieldFields, DataType, FieldType,
+                FieldValue, GetCustomField, SetFieldValues};
+
+    #[test]
+    fn create_custom_field() {
+        let expected: Json = from_str(stringify!({
+            "field": {
+                "name": "customer_tier",
+                "display_name": "Customer tier",
+                "data_type": "string",
+                "field_type": "single_value_fixed",
+                "default_value": "standard",
+                "field_options": [
+                    { "data": { "data_type": "string", "value": "standard" } },
+                    { "data": { "data_type": "string", "value": "enterprise" } }
+                ]
+            }
+        })).unwrap();
+
+        let fields = CustomFieldFields::new()
+            .set_name("customer_tier")
+            .set_display_name("Customer tier")
+            .set_data_type(DataType::String)
+            .set_field_type(FieldType::SingleValueFixed)
+            .set_default_value("standard")
+            .add_option("standard")
+            .add_option("enterprise");
+        let request = CreateCustomField::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let request = CreateFieldOption::new("PT4KHLK", "strategic");
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents/custom_fields/PT4KHLK/field_options");
+        assert_eq!(request.body(),
+                   r#"{"field_option":{"data":{"data_type":"string","value":"strategic"}}}"#);
+    }
+
+    #[test]
+    fn custom_field_from_json() {
+        let body = stringify!({
+            "field": {
+                "id": "PT4KHLK",
+                "type": "field",
+                "summary": "Customer tier",
+                "self": "https://api.pagerduty.com/incidents/custom_fields/PT4KHLK",
+                "name": "customer_tier",
+                "display_name": "Customer tier",
+                "description": null,
+                "data_type": "string",
+                "field_type": "single_value_fixed",
+                "default_value": "standard",
+                "field_options": [
+                    {
+                        "id": "PFX0DZ4",
+                        "type": "field_option",
+                        "data": { "data_type": "string", "value": "standard" },
+                        "created_at": "2023-01-01T00:00:00Z",
+                        "updated_at": "2023-01-01T00:00:00Z"
+                    }
+                ],
+                "created_at": "2023-01-01T00:00:00Z",
+                "updated_at": "2023-01-01T00:00:00Z"
+            }
+        });
+
+        let res = GetCustomField::get_response(StatusCode::Ok, &Headers::new(), body);
+        let field = res.unwrap().success().unwrap();
+
+        assert_eq!(field.field_type, FieldType::SingleValueFixed);
+        assert_eq!(field.default_value, Some("standard".into()));
+        assert_eq!(field.field_options[0].data.value, "standard");
+    }
+
+    #[test]
+    fn set_field_values() {
+        let request = SetFieldValues::new("PT4KHLK")
+            .set_value("customer_tier", "enterprise")
+            .set_value("affected_users", 1200)
+            .set_value("postmortem", FieldValue::Url("https://example.com/pm/12".to_owned()))
+            .clear_value("region");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/custom_fields/values");
+        assert_eq!(request.body(),
+                   "{\"custom_fields\":[\
+                    {\"name\":\"customer_tier\",\"value\":\"enterprise\"},\
+                    {\"name\":\"affected_users\",\"value\":1200},\
+                    {\"name\":\"postmortem\",\"value\":\"https://example.com/pm/12\"},\
+                    {\"name\":\"region\",\"value\":null}]}");
+
+        let body = stringify!({
+            "custom_fields": [
+                {
+                    "id": "PT4KHLK",
+                    "type": "field_value",
+                    "name": "customer_tier",
+                    "display_name": "Customer tier",
+                    "data_type": "string",
+                    "field_type": "single_value_fixed",
+                    "value": "enterprise"
+                },
+                {
+                    "id": "PFX0DZ4",
+                    "name": "affected_regions",
+                    "data_type": "string",
+                    "field_type": "multi_value",
+                    "value": ["us-east-1", "eu-west-1"]
+                },
+                {
+                    "id": "P2KTJ5D",
+                    "name": "affected_users",
+                    "data_type": "integer",
+                    "field_type": "single_value",
+                    "value": 1200
+                },
+                {
+                    "id": "PR1B3ZS",
+                    "name": "region",
+                    "data_type": "string",
+                    "field_type": "single_value",
+                    "value": null
+                }
+            ]
+        });
+
+        let res = SetFieldValues::get_response(StatusCode::Ok, &Headers::new(), body);
+        let values = res.unwrap().success().unwrap();
+
+        assert_eq!(values[0].value, Some(FieldValue::String("enterprise".to_owned())));
+        assert_eq!(values[1].value, Some(FieldValue::List(vec!["us-east-1".into(),
+                                                                "eu-west-1".into()])));
+        assert_eq!(values[2].value, Some(FieldValue::Integer(1200)));
+        assert_eq!(values[3].value, None);
+    }
+}
@@ -0,0 +1,92 @@
+//! Serialization of `Duration` fields
+//!
+//! The API expresses lengths of time as integer counts of seconds or minutes, with the unit in the
+//! field name. These modules are used with `#[serde(with = "...")]` so models can expose a
+//! `Duration` instead.
+
+/// Durations sent as a number of seconds
+pub mod seconds {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_u64(duration.as_secs())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+        where D: Deserializer<'de>
+    {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+/// Optional durations sent as a number of seconds, or `null`
+pub mod optional_seconds {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+        where D: Deserializer<'de>
+    {
+        Option::<u64>::deserialize(deserializer).map(|secs| secs.map(Duration::from_secs))
+    }
+}
+
+/// Durations sent as a number of minutes
+///
+/// Durations which aren't a whole number of minutes are rounded up when serialized.
+pub mod minutes {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let secs = duration.as_secs() + if duration.subsec_nanos() > 0 { 1 } else { 0 };
+        serializer.serialize_u64(secs.div_ceil(60))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+        where D: Deserializer<'de>
+    {
+        u64::deserialize(deserializer).map(|minutes| Duration::from_secs(minutes * 60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{from_str, to_string};
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Lengths {
+        #[serde(with = "super::seconds")]
+        seconds: Duration,
+
+        #[serde(with = "super::minutes")]
+        minutes: Duration,
+    }
+
+    #[test]
+    fn durations_round_trip() {
+        let lengths: Lengths = from_str(r#"{"seconds":86400,"minutes":30}"#).unwrap();
+        assert_eq!(lengths.seconds, Duration::from_secs(86400));
+        assert_eq!(lengths.minutes, Duration::from_secs(30 * 60));
+        assert_eq!(to_string(&lengths).unwrap(), r#"{"seconds":86400,"minutes":30}"#);
+    }
+
+    #[test]
+    fn partial_minutes_round_up() {
+        let lengths = Lengths {
+            seconds: Duration::from_secs(0),
+            minutes: Duration::from_secs(61),
+        };
+        assert_eq!(to_string(&lengths).unwrap(), r#"{"seconds":0,"minutes":2}"#);
+    }
+}
@@ -0,0 +1,740 @@
+//! Escalation Policies
+//!
+//! Escalation policies make sure the right people are alerted at the right time. An escalation
+//! policy determines what user or schedule will be notified first, second, and so on when an
+//! incident is triggered.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// An escalation policy
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct EscalationPolicy {
+    /// The escalation policy's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the escalation policy
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the escalation policy is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the escalation policy is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the escalation policy
+    pub name: String,
+
+    /// Escalation policy description
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The number of times the escalation policy will repeat after reaching the end of its
+    /// escalation
+    #[serde(default)]
+    pub num_loops: u32,
+
+    /// Determines how on call handoff notifications will be sent for users on the escalation
+    /// policy, either `if_has_services` or `always`
+    #[serde(default)]
+    pub on_call_handoff_notifications: Option<String>,
+
+    /// The ordered list of escalation rules for the policy
+    #[serde(default)]
+    pub escalation_rules: Vec<EscalationRule>,
+
+    /// Services which use this escalation policy
+    #[serde(default)]
+    pub services: Vec<Reference>,
+
+    /// Teams associated with the policy
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+}
+
+/// A level of an escalation policy
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct EscalationRule {
+    /// The escalation rule's ID
+    #[serde(default)]
+    pub id: Option<String>,
+
+    /// How long before an unacknowledged incident escalates away from this rule
+    #[serde(rename = "escalation_delay_in_minutes", with = "super::duration::minutes")]
+    pub escalation_delay: Duration,
+
+    /// The users and schedules notified when an incident reaches this rule
+    pub targets: Vec<EscalationTarget>,
+}
+
+/// A user or schedule notified by an escalation rule
+///
+/// Serialized with the API's `type` discriminator. Targets expanded with `include[]=targets`
+/// (typed `user` or `schedule`) deserialize as the corresponding reference variant.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum EscalationTarget {
+    #[serde(rename = "user_reference", alias = "user")]
+    UserReference(TargetReference),
+
+    #[serde(rename = "schedule_reference", alias = "schedule")]
+    ScheduleReference(TargetReference),
+}
+
+impl EscalationTarget {
+    /// Target user `id`
+    pub fn user<S>(id: S) -> EscalationTarget
+        where S: Into<String>
+    {
+        EscalationTarget::UserReference(TargetReference::new(id))
+    }
+
+    /// Target schedule `id`, notifying whoever is on call
+    pub fn schedule<S>(id: S) -> EscalationTarget
+        where S: Into<String>
+    {
+        EscalationTarget::ScheduleReference(TargetReference::new(id))
+    }
+
+    /// The ID of the targeted user or schedule
+    pub fn id(&self) -> &str {
+        match *self {
+            EscalationTarget::UserReference(ref target) |
+            EscalationTarget::ScheduleReference(ref target) => &target.id,
+        }
+    }
+}
+
+/// The fields of a reference to an escalation target
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TargetReference {
+    /// The user or schedule's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    /// The API URL at which the target is accessible
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub self_url: Option<String>,
+
+    /// A URL at which the target is rendered in the web application
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+}
+
+impl TargetReference {
+    fn new<S>(id: S) -> TargetReference
+        where S: Into<String>
+    {
+        TargetReference {
+            id: id.into(),
+            summary: None,
+            self_url: None,
+            html_url: None,
+        }
+    }
+}
+
+/// List escalation policies, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListEscalationPolicies<'a> {
+    query: Option<Cow<'a, str>>,
+    user_ids: Vec<Cow<'a, str>>,
+    team_ids: Vec<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    sort_by: Option<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListEscalationPolicies<'a> {
+    /// Create a request listing all escalation policies
+    pub fn new() -> ListEscalationPolicies<'a> {
+        ListEscalationPolicies::default()
+    }
+
+    /// Only return escalation policies whose names contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Only return escalation policies with user `user_id` as a target; may be called more than
+    /// once
+    pub fn add_user_id<S>(mut self, user_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.user_ids.push(user_id.into());
+        self
+    }
+
+    /// Only return escalation policies belonging to team `team_id`; may be called more than once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Include additional models in the response, such as `"services"` or `"targets"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+
+    /// Sort results by `name` or `name:asc` or `name:desc`
+    pub fn set_sort_by<S>(mut self, sort_by: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.sort_by = Some(sort_by.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListEscalationPolicies<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListEscalationPolicies<'a> {
+    type Response = Response<Page<EscalationPolicy>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        query.push_array("user_ids", &self.user_ids);
+        query.push_array("team_ids", &self.team_ids);
+        query.push_array("include", &self.include);
+        if let Some(ref sort_by) = self.sort_by {
+            query.push("sort_by", sort_by);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/escalation_policies").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<EscalationPolicy>>> {
+        get_page(status, headers, body, "escalation_policies")
+    }
+}
+
+/// Get information about an existing escalation policy and its rules
+#[derive(Debug)]
+pub struct GetEscalationPolicy<'a> {
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetEscalationPolicy<'a> {
+    /// Create a request for escalation policy `id`
+    pub fn new<S>(id: S) -> GetEscalationPolicy<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetEscalationPolicy {
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional models in the response, such as `"services"` or `"targets"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetEscalationPolicy<'a> {
+    type Response = Response<EscalationPolicy>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url(&format!("/escalation_policies/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EscalationPolicy>> {
+        get_response(status, headers, body, "escalation_policy")
+    }
+}
+
+/// Writable fields of an escalation policy
+///
+/// Used to describe a new policy with `CreateEscalationPolicy`, or changes to an existing one with
+/// `UpdateEscalationPolicy`. Fields which aren't set are left out of the request.
+#[derive(Debug, Serialize)]
+pub struct EscalationPolicyFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    num_loops: Option<u32>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    on_call_handoff_notifications: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    escalation_rules: Option<Vec<EscalationRuleFields>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    teams: Option<Vec<Reference>>,
+}
+
+impl<'a> Default for EscalationPolicyFields<'a> {
+    fn default() -> EscalationPolicyFields<'a> {
+        EscalationPolicyFields::new()
+    }
+}
+
+impl<'a> EscalationPolicyFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> EscalationPolicyFields<'a> {
+        EscalationPolicyFields {
+            kind: "escalation_policy",
+            name: None,
+            description: None,
+            num_loops: None,
+            on_call_handoff_notifications: None,
+            escalation_rules: None,
+            teams: None,
+        }
+    }
+
+    /// Set the name of the escalation policy
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the escalation policy description
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the number of times the escalation policy will repeat after reaching the end of its
+    /// escalation
+    pub fn set_num_loops(mut self, num_loops: u32) -> Self {
+        self.num_loops = Some(num_loops);
+        self
+    }
+
+    /// Set when on call handoff notifications are sent, either `if_has_services` or `always`
+    pub fn set_on_call_handoff_notifications<S>(mut self, notifications: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.on_call_handoff_notifications = Some(notifications.into());
+        self
+    }
+
+    /// Add an escalation rule after those already added
+    ///
+    /// When updating a policy, the rules added replace all of the policy's existing rules.
+    pub fn add_escalation_rule(mut self, rule: EscalationRuleFields) -> Self {
+        self.escalation_rules.get_or_insert_with(Vec::new).push(rule);
+        self
+    }
+
+    /// Associate the escalation policy with team `team_id`
+    ///
+    /// When updating a policy, the teams added replace all of the policy's existing teams.
+    pub fn add_team<S>(mut self, team_id: S) -> Self
+        where S: Into<String>
+    {
+        let team = Reference::new(team_id, "team_reference");
+        self.teams.get_or_insert_with(Vec::new).push(team);
+        self
+    }
+}
+
+/// Writable fields of an escalation rule
+#[derive(Debug, Serialize)]
+pub struct EscalationRuleFields {
+    #[serde(rename = "escalation_delay_in_minutes", with = "super::duration::minutes")]
+    escalation_delay: Duration,
+    targets: Vec<EscalationTarget>,
+}
+
+impl EscalationRuleFields {
+    /// Create a rule escalating after `escalation_delay`
+    ///
+    /// The API counts the delay in minutes, so it is rounded up to a whole number of minutes.
+    pub fn new(escalation_delay: Duration) -> EscalationRuleFields {
+        EscalationRuleFields {
+            escalation_delay,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Notify `target` when an incident reaches this rule
+    pub fn add_target(mut self, target: EscalationTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+}
+
+/// Create a new escalation policy
+#[derive(Debug, Serialize)]
+pub struct CreateEscalationPolicy<'a> {
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    escalation_policy: EscalationPolicyFields<'a>,
+}
+
+impl<'a> CreateEscalationPolicy<'a> {
+    /// Create a request for a new escalation policy described by `fields`
+    ///
+    /// PagerDuty requires at least a name and one escalation rule.
+    pub fn new(fields: EscalationPolicyFields<'a>) -> CreateEscalationPolicy<'a> {
+        CreateEscalationPolicy {
+            from: None,
+            escalation_policy: fields,
+        }
+    }
+
+    /// Set the email address of the user creating the escalation policy
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for CreateEscalationPolicy<'a> {
+    type Response = Response<EscalationPolicy>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/escalation_policies", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EscalationPolicy>> {
+        get_response(status, headers, body, "escalation_policy")
+    }
+}
+
+/// Update an existing escalation policy and its rules
+#[derive(Debug, Serialize)]
+pub struct UpdateEscalationPolicy<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    escalation_policy: EscalationPolicyFields<'a>,
+}
+
+impl<'a> UpdateEscalationPolicy<'a> {
+    /// Create a request applying `fields` to escalation policy `id`
+    pub fn new<S>(id: S, fields: EscalationPolicyFields<'a>) -> UpdateEscalationPolicy<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateEscalationPolicy {
+            id: id.into(),
+            from: None,
+            escalation_policy: fields,
+        }
+    }
+
+    /// Set the email address of the user updating the escalation policy
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for UpdateEscalationPolicy<'a> {
+    type Response = Response<EscalationPolicy>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/escalation_policies/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EscalationPolicy>> {
+        get_response(status, headers, body, "escalation_policy")
+    }
+}
+
+/// Delete an existing escalation policy
+///
+/// Only escalation policies which are not used by any services can be deleted.
+#[derive(Debug)]
+pub struct DeleteEscalationPolicy<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteEscalationPolicy<'a> {
+    /// Create a request deleting escalation policy `id`
+    pub fn new<S>(id: S) -> DeleteEscalationPolicy<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteEscalationPolicy {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteEscalationPolicy<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/escalation_policies/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List escalation policies
+pub fn list(auth: &AuthToken, request: &ListEscalationPolicies)
+    -> request::Result<Response<Page<EscalationPolicy>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get a single escalation policy
+pub fn get(auth: &AuthToken,
+           request: &GetEscalationPolicy) -> request::Result<Response<EscalationPolicy>> {
+    request::perform(auth, request)
+}
+
+/// Create an escalation policy
+pub fn create(auth: &AuthToken,
+              request: &CreateEscalationPolicy) -> request::Result<Response<EscalationPolicy>> {
+    request::perform(auth, request)
+}
+
+/// Update an escalation policy
+pub fn update(auth: &AuthToken,
+              request: &UpdateEscalationPolicy) -> request::Result<Response<EscalationPolicy>> {
+    request::perform(auth, request)
+}
+
+/// Delete an escalation policy
+pub fn delete(auth: &AuthToken, request: &DeleteEscalationPolicy) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{CreateEscalationPolicy, EscalationPolicyFields, EscalationRuleFields,
+                EscalationTarget, GetEscalationPolicy, ListEscalationPolicies,
+                UpdateEscalationPolicy};
+
+    #[test]
+    fn create_escalation_policy_to_json() {
+        let expected: Json = from_str(stringify!({
+            "escalation_policy": {
+                "type": "escalation_policy",
+                "name": "Engineering Escalation Policy",
+                "num_loops": 2,
+                "escalation_rules": [
+                    {
+                        "escalation_delay_in_minutes": 30,
+                        "targets": [{ "id": "PEYSGVF", "type": "user_reference" }]
+                    }
+                ],
+                "teams": [{ "id": "PQ9K7I8", "type": "team_reference" }]
+            }
+        })).expect("expected is valid json");
+
+        let rule = EscalationRuleFields::new(Duration::from_secs(30 * 60))
+                       .add_target(EscalationTarget::user("PEYSGVF"));
+        let fields = EscalationPolicyFields::new()
+                         .set_name("Engineering Escalation Policy")
+                         .set_num_loops(2)
+                         .add_escalation_rule(rule)
+                         .add_team("PQ9K7I8");
+        let request = CreateEscalationPolicy::new(fields).set_from("user@example.com");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn update_escalation_policy_sends_from() {
+        let fields = EscalationPolicyFields::new().set_num_loops(1);
+        let request = UpdateEscalationPolicy::new("PANZZEQ", fields).set_from("user@example.com");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, from_str::<Json>(stringify!({
+            "escalation_policy": { "type": "escalation_policy", "num_loops": 1 }
+        })).unwrap());
+        assert_eq!(request.url(), "https://api.pagerduty.com/escalation_policies/PANZZEQ");
+        assert_eq!(request.headers().get_raw("From").unwrap()[0], b"user@example.com".to_vec());
+    }
+
+    #[test]
+    fn list_escalation_policies_url() {
+        let request = ListEscalationPolicies::new()
+                          .set_query("ops")
+                          .add_user_id("PXPGF42")
+                          .add_team_id("PQ9K7I8")
+                          .add_include("targets");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/escalation_policies?query=ops&\
+                    user_ids%5B%5D=PXPGF42&team_ids%5B%5D=PQ9K7I8&include%5B%5D=targets");
+    }
+
+    #[test]
+    fn escalation_policy_from_json() {
+        let body = stringify!({
+            "escalation_policy": {
+                "id": "PANZZEQ",
+                "type": "escalation_policy",
+                "summary": "Engineering Escalation Policy",
+                "name": "Engineering Escalation Policy",
+                "escalation_rules": [
+                    {
+                        "id": "PANZZEQ",
+                        "escalation_delay_in_minutes": 30,
+                        "targets": [
+                            { "id": "PEYSGVF", "type": "user_reference" },
+                            { "id": "PI7DH85", "type": "schedule", "summary": "Daily Engineering" }
+                        ]
+                    }
+                ],
+                "services": [{ "id": "PIJ90N7", "type": "service_reference" }],
+                "num_loops": 0,
+                "teams": [],
+                "description": "Here is the ep for the engineering team."
+            }
+        });
+
+        let res = GetEscalationPolicy::get_response(StatusCode::Ok, &Headers::new(), body);
+        let policy = res.unwrap().success().unwrap();
+
+        assert_eq!(policy.name, "Engineering Escalation Policy");
+        assert_eq!(policy.escalation_rules[0].escalation_delay, Duration::from_secs(30 * 60));
+        assert_eq!(policy.escalation_rules[0].targets[0], EscalationTarget::user("PEYSGVF"));
+        match policy.escalation_rules[0].targets[1] {
+            EscalationTarget::ScheduleReference(ref schedule) => {
+                assert_eq!(schedule.id, "PI7DH85");
+                assert_eq!(schedule.summary, Some("Daily Engineering".to_owned()));
+            },
+            ref other => panic!("expected schedule target, got {:?}", other),
+        }
+        assert_eq!(policy.services.len(), 1);
+    }
+}
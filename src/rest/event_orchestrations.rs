@@ -0,0 +1,976 @@
+//! Event Orchestrations
+//!
+//! Event orchestrations are the successor of rulesets. Events sent to the routing key of one of
+//! an orchestration's integrations are evaluated against its router rules, which decide the
+//! service each event is sent to.
+//!
+//! # Router and unrouted rules
+//!
+//! An orchestration's rules are organized in paths. The router path decides which service each
+//! event is routed to, and the unrouted path processes events the router didn't route. Both are
+//! read and replaced as a whole; rules are evaluated in order, starting with the set whose ID is
+//! `start`.
+//!
+//! ```
+//! use pagerduty::rest::event_orchestrations::{Condition, RouterActions, Rule, UpdateRouter};
+//!
+//! let rule = Rule::new(vec![Condition::new("event.summary matches part 'database'")],
+//!                      RouterActions::route_to("PIJ90N7"))
+//!     .set_label("Database events");
+//!
+//! let request = UpdateRouter::new("b02e973d", vec![rule]);
+//! ```
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use events_v2::Severity;
+use request::{self, Requestable};
+use super::{get_page, get_response, segment, ListRequest, Page, Pagination, Query, Reference,
+            Response, BASE_URL};
+
+/// A global event orchestration
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct EventOrchestration {
+    /// The orchestration's ID
+    pub id: String,
+
+    /// The API URL at which the orchestration is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// The name of the orchestration
+    pub name: String,
+
+    /// A description of the orchestration
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The team owning the orchestration, if any
+    #[serde(default)]
+    pub team: Option<Reference>,
+
+    /// Integrations through which events are sent to the orchestration
+    #[serde(default)]
+    pub integrations: Vec<OrchestrationIntegration>,
+
+    /// The number of routes of the orchestration's router
+    #[serde(default)]
+    pub routes: Option<u64>,
+
+    /// The time at which the orchestration was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+
+    /// The user who created the orchestration
+    #[serde(default)]
+    pub created_by: Option<Reference>,
+
+    /// The time at which the orchestration was last updated
+    #[serde(default)]
+    pub updated_at: Option<String>,
+
+    /// The user who last updated the orchestration
+    #[serde(default)]
+    pub updated_by: Option<Reference>,
+
+    /// Version of the orchestration's configuration
+    #[serde(default)]
+    pub version: Option<String>,
+}
+
+/// An integration through which events are sent to an orchestration
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct OrchestrationIntegration {
+    /// The integration's ID
+    pub id: String,
+
+    /// How events are sent to the integration
+    pub parameters: IntegrationParameters,
+}
+
+impl OrchestrationIntegration {
+    /// The routing key events are sent with
+    pub fn routing_key(&self) -> &str {
+        &self.parameters.routing_key
+    }
+}
+
+/// How events are sent to an orchestration integration
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct IntegrationParameters {
+    /// The routing key events are sent with
+    pub routing_key: String,
+
+    /// The kind of integration, such as `global`
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+/// A condition under which a rule applies to an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Condition {
+    /// A PagerDuty Condition Language expression, such as `event.severity matches 'critical'`
+    pub expression: String,
+}
+
+impl Condition {
+    /// Create a condition from a PagerDuty Condition Language expression
+    pub fn new<S>(expression: S) -> Condition
+        where S: Into<String>
+    {
+        Condition {
+            expression: expression.into(),
+        }
+    }
+}
+
+/// A rule of an orchestration path, with actions of type `A`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Rule<A> {
+    /// The rule's ID; assigned by PagerDuty when the rule is created
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub id: Option<String>,
+
+    /// A description of the rule
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub label: Option<String>,
+
+    /// The rule applies when any of the conditions match; a rule without conditions always applies
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+
+    /// The actions applied to matching events
+    pub actions: A,
+
+    /// Whether the rule is disabled
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+impl<A> Rule<A> {
+    /// Create a rule applying `actions` to events matching any of `conditions`
+    pub fn new(conditions: Vec<Condition>, actions: A) -> Rule<A> {
+        Rule {
+            id: None,
+            label: None,
+            conditions,
+            actions,
+            disabled: false,
+        }
+    }
+
+    /// Set the description of the rule
+    pub fn set_label<S>(mut self, label: S) -> Self
+        where S: Into<String>
+    {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set whether the rule is disabled
+    pub fn set_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// An ordered set of rules within an orchestration path
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuleSet<A> {
+    /// The set's ID; evaluation begins at the set with ID `start`
+    pub id: String,
+
+    /// The rules of the set, in evaluation order
+    #[serde(default = "Vec::new")]
+    pub rules: Vec<Rule<A>>,
+}
+
+/// The actions applied to events matching no rule of a path
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CatchAll<A> {
+    pub actions: A,
+}
+
+/// The rules of an orchestration path
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrchestrationPath<A> {
+    /// The orchestration the path belongs to
+    #[serde(default, skip_serializing)]
+    pub parent: Option<Reference>,
+
+    /// The sets of rules of the path
+    pub sets: Vec<RuleSet<A>>,
+
+    /// The actions applied to events matching no rule
+    pub catch_all: CatchAll<A>,
+}
+
+/// Actions of router rules
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RouterActions {
+    /// The ID of the service events are sent to, or `unrouted`
+    pub route_to: String,
+}
+
+impl RouterActions {
+    /// Route events to service `service_id`
+    pub fn route_to<S>(service_id: S) -> RouterActions
+        where S: Into<String>
+    {
+        RouterActions {
+            route_to: service_id.into(),
+        }
+    }
+
+    /// Leave events unrouted, so they are processed by the unrouted rules
+    pub fn unrouted() -> RouterActions {
+        RouterActions::route_to("unrouted")
+    }
+}
+
+/// A variable extracted from an event, for use in later rules and extractions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Variable {
+    /// The name the variable is referred to by
+    pub name: String,
+
+    /// The path of the event field the variable is read from
+    pub path: String,
+
+    /// How the value is extracted; `regex`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The regular expression extracting the value
+    pub value: String,
+}
+
+/// A rewrite of an event field
+///
+/// The new value is either matched by `regex` in the field at `source`, or rendered from
+/// `template`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Extraction {
+    /// The path of the event field written to
+    pub target: String,
+
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub regex: Option<String>,
+
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub source: Option<String>,
+
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// Actions of unrouted rules
+///
+/// Actions which are `None` or empty are left alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnroutedActions {
+    /// The ID of the rule set evaluated next
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub route_to: Option<String>,
+
+    /// Set the severity of the event
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub severity: Option<Severity>,
+
+    /// Change the event action, `trigger` or `resolve`
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub event_action: Option<String>,
+
+    /// Extract variables from the event
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub variables: Vec<Variable>,
+
+    /// Rewrite fields of the event
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub extractions: Vec<Extraction>,
+}
+
+/// The router rules of an orchestration
+pub type RouterPath = OrchestrationPath<RouterActions>;
+
+/// The unrouted rules of an orchestration
+pub type UnroutedPath = OrchestrationPath<UnroutedActions>;
+
+/// List event orchestrations
+#[derive(Debug, Default)]
+pub struct ListEventOrchestrations<'a> {
+    sort_by: Option<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListEventOrchestrations<'a> {
+    /// Create a request listing all event orchestrations
+    pub fn new() -> ListEventOrchestrations<'a> {
+        ListEventOrchestrations::default()
+    }
+
+    /// Sort results by a field such as `name:asc` or `created_at:desc`
+    pub fn set_sort_by<S>(mut self, sort_by: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.sort_by = Some(sort_by.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListEventOrchestrations<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListEventOrchestrations<'a> {
+    type Response = Response<Page<EventOrchestration>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref sort_by) = self.sort_by {
+            query.push("sort_by", sort_by);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/event_orchestrations").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<EventOrchestration>>> {
+        get_page(status, headers, body, "orchestrations")
+    }
+}
+
+/// Get details about an existing event orchestration
+#[derive(Debug)]
+pub struct GetEventOrchestration<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetEventOrchestration<'a> {
+    /// Create a request for event orchestration `id`
+    pub fn new<S>(id: S) -> GetEventOrchestration<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetEventOrchestration {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetEventOrchestration<'a> {
+    type Response = Response<EventOrchestration>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EventOrchestration>> {
+        get_response(status, headers, body, "orchestration")
+    }
+}
+
+/// Writable fields of an event orchestration
+#[derive(Debug, Default, Serialize)]
+pub struct EventOrchestrationFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    team: Option<Reference>,
+}
+
+impl<'a> EventOrchestrationFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> EventOrchestrationFields<'a> {
+        EventOrchestrationFields::default()
+    }
+
+    /// Set the name of the orchestration
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the description of the orchestration
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the team owning the orchestration
+    pub fn set_team<S>(mut self, team_id: S) -> Self
+        where S: Into<String>
+    {
+        self.team = Some(Reference::new(team_id, "team_reference"));
+        self
+    }
+}
+
+/// Create a new event orchestration
+///
+/// The new orchestration comes with an integration whose routing key events can be sent to.
+#[derive(Debug, Serialize)]
+pub struct CreateEventOrchestration<'a> {
+    orchestration: EventOrchestrationFields<'a>,
+}
+
+impl<'a> CreateEventOrchestration<'a> {
+    /// Create a request for a new orchestration described by `fields`
+    ///
+    /// PagerDuty requires at least a name.
+    pub fn new(fields: EventOrchestrationFields<'a>) -> CreateEventOrchestration<'a> {
+        CreateEventOrchestration {
+            orchestration: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateEventOrchestration<'a> {
+    type Response = Response<EventOrchestration>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EventOrchestration>> {
+        get_response(status, headers, body, "orchestration")
+    }
+}
+
+/// Update an existing event orchestration
+#[derive(Debug, Serialize)]
+pub struct UpdateEventOrchestration<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    orchestration: EventOrchestrationFields<'a>,
+}
+
+impl<'a> UpdateEventOrchestration<'a> {
+    /// Create a request applying `fields` to event orchestration `id`
+    pub fn new<S>(id: S, fields: EventOrchestrationFields<'a>) -> UpdateEventOrchestration<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateEventOrchestration {
+            id: id.into(),
+            orchestration: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateEventOrchestration<'a> {
+    type Response = Response<EventOrchestration>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EventOrchestration>> {
+        get_response(status, headers, body, "orchestration")
+    }
+}
+
+/// List the integrations of an event orchestration, along with their routing keys
+#[derive(Debug)]
+pub struct ListOrchestrationIntegrations<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> ListOrchestrationIntegrations<'a> {
+    /// Create a request listing the integrations of event orchestration `id`
+    pub fn new<S>(id: S) -> ListOrchestrationIntegrations<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListOrchestrationIntegrations {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for ListOrchestrationIntegrations<'a> {
+    type Response = Response<Vec<OrchestrationIntegration>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}/integrations", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<OrchestrationIntegration>>> {
+        get_response(status, headers, body, "integrations")
+    }
+}
+
+/// Get the router rules of an event orchestration
+#[derive(Debug)]
+pub struct GetRouter<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetRouter<'a> {
+    /// Create a request for the router rules of event orchestration `id`
+    pub fn new<S>(id: S) -> GetRouter<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetRouter {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetRouter<'a> {
+    type Response = Response<RouterPath>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}/router", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<RouterPath>> {
+        get_response(status, headers, body, "orchestration_path")
+    }
+}
+
+/// Replace the router rules of an event orchestration
+#[derive(Debug, Serialize)]
+pub struct UpdateRouter<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    orchestration_path: RouterPath,
+}
+
+impl<'a> UpdateRouter<'a> {
+    /// Create a request setting the router of event orchestration `id` to `rules`, with events
+    /// matching none of them left unrouted
+    pub fn new<S>(id: S, rules: Vec<Rule<RouterActions>>) -> UpdateRouter<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateRouter::from_path(id, OrchestrationPath {
+            parent: None,
+            sets: vec![RuleSet {
+                id: "start".into(),
+                rules,
+            }],
+            catch_all: CatchAll {
+                actions: RouterActions::unrouted(),
+            },
+        })
+    }
+
+    /// Create a request setting the router of event orchestration `id` to `path`
+    ///
+    /// This allows a router retrieved with `GetRouter` to be modified and sent back.
+    pub fn from_path<S>(id: S, path: RouterPath) -> UpdateRouter<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateRouter {
+            id: id.into(),
+            orchestration_path: path,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateRouter<'a> {
+    type Response = Response<RouterPath>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}/router", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<RouterPath>> {
+        get_response(status, headers, body, "orchestration_path")
+    }
+}
+
+/// Get the unrouted rules of an event orchestration
+#[derive(Debug)]
+pub struct GetUnrouted<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetUnrouted<'a> {
+    /// Create a request for the unrouted rules of event orchestration `id`
+    pub fn new<S>(id: S) -> GetUnrouted<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetUnrouted {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetUnrouted<'a> {
+    type Response = Response<UnroutedPath>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}/unrouted", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<UnroutedPath>> {
+        get_response(status, headers, body, "orchestration_path")
+    }
+}
+
+/// Replace the unrouted rules of an event orchestration
+#[derive(Debug, Serialize)]
+pub struct UpdateUnrouted<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    orchestration_path: UnroutedPath,
+}
+
+impl<'a> UpdateUnrouted<'a> {
+    /// Create a request setting the unrouted rules of event orchestration `id` to `path`
+    pub fn new<S>(id: S, path: UnroutedPath) -> UpdateUnrouted<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateUnrouted {
+            id: id.into(),
+            orchestration_path: path,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateUnrouted<'a> {
+    type Response = Response<UnroutedPath>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/event_orchestrations/{}/unrouted", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<UnroutedPath>> {
+        get_response(status, headers, body, "orchestration_path")
+    }
+}
+
+/// List event orchestrations
+pub fn list(auth: &AuthToken, request: &ListEventOrchestrations)
+    -> request::Result<Response<Page<EventOrchestration>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get a single event orchestration
+pub fn get(auth: &AuthToken,
+           request: &GetEventOrchestration) -> request::Result<Response<EventOrchestration>> {
+    request::perform(auth, request)
+}
+
+/// Create an event orchestration
+pub fn create(auth: &AuthToken,
+              request: &CreateEventOrchestration) -> request::Result<Response<EventOrchestration>> {
+    request::perform(auth, request)
+}
+
+/// Update an event orchestration
+pub fn update(auth: &AuthToken,
+              request: &UpdateEventOrchestration) -> request::Result<Response<EventOrchestration>> {
+    request::perform(auth, request)
+}
+
+/// List the integrations of an event orchestration
+pub fn list_integrations(auth: &AuthToken, request: &ListOrchestrationIntegrations)
+    -> request::Result<Response<Vec<OrchestrationIntegration>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get the router rules of an event orchestration
+pub fn get_router(auth: &AuthToken, request: &GetRouter) -> request::Result<Response<RouterPath>> {
+    request::perform(auth, request)
+}
+
+/// Replace the router rules of an event orchestration
+pub fn update_router(auth: &AuthToken,
+                     request: &UpdateRouter) -> request::Result<Response<RouterPath>> {
+    request::perform(auth, request)
+}
+
+/// Get the unrouted rules of an event orchestration
+pub fn get_unrouted(auth: &AuthToken,
+                    request: &GetUnrouted) -> request::Result<Response<UnroutedPath>> {
+    request::perform(auth, request)
+}
+
+/// Replace the unrouted rules of an event orchestration
+pub fn update_unrouted(auth: &AuthToken,
+                       request: &UpdateUnrouted) -> request::Result<Response<UnroutedPath>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use events_v2::Severity;
+    use request::Requestable;
+    use super::{Condition, CreateEventOrchestration, EventOrchestrationFields, GetUnrouted,
+                RouterActions, Rule, UpdateRouter};
+
+    #[test]
+    fn update_router_body() {
+        let expected: Json = from_str(stringify!({
+            "orchestration_path": {
+                "sets": [
+                    {
+                        "id": "start",
+                        "rules": [
+                            {
+                                "label": "Database events",
+                                "conditions": [
+                                    { "expression": "event.summary matches part 'database'" }
+                                ],
+                                "actions": { "route_to": "PIJ90N7" },
+                                "disabled": false
+                            }
+                        ]
+                    }
+                ],
+                "catch_all": { "actions": { "route_to": "unrouted" } }
+            }
+        })).expect("expected is valid json");
+
+        let rule = Rule::new(vec![Condition::new("event.summary matches part 'database'")],
+                             RouterActions::route_to("PIJ90N7"))
+            .set_label("Database events");
+        let request = UpdateRouter::new("b02e973d", vec![rule]);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(), "https://api.pagerduty.com/event_orchestrations/b02e973d/router");
+    }
+
+    #[test]
+    fn unrouted_from_json() {
+        let body = stringify!({
+            "orchestration_path": {
+                "type": "unrouted",
+                "parent": { "id": "b02e973d", "type": "event_orchestration_reference" },
+                "sets": [
+                    {
+                        "id": "start",
+                        "rules": [
+                            {
+                                "id": "c91f72f3",
+                                "label": "Downgrade staging",
+                                "conditions": [
+                                    { "expression": "event.source matches part 'staging'" }
+                                ],
+                                "actions": {
+                                    "severity": "info",
+                                    "extractions": [
+                                        { "target": "event.summary", "template": "[staging]" }
+                                    ]
+                                },
+                                "disabled": false
+                            }
+                        ]
+                    }
+                ],
+                "catch_all": { "actions": {} },
+                "created_at": "2021-11-18T16:42:01Z"
+            }
+        });
+
+        let res = GetUnrouted::get_response(StatusCode::Ok, &Headers::new(), body);
+        let path = res.unwrap().success().unwrap();
+        let rule = &path.sets[0].rules[0];
+
+        assert_eq!(path.parent.unwrap().id, "b02e973d");
+        assert_eq!(rule.id, Some("c91f72f3".to_owned()));
+        assert_eq!(rule.actions.severity, Some(Severity::Info));
+        assert_eq!(rule.actions.extractions[0].template, Some("[staging]".to_owned()));
+        assert_eq!(path.catch_all.actions.route_to, None);
+    }
+
+    #[test]
+    fn create_orchestration_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "orchestration": {
+                "name": "Shopping cart",
+                "team": { "id": "PQ9K7I8", "type": "team_reference" }
+            }
+        })).expect("expected is valid json");
+
+        let fields = EventOrchestrationFields::new().set_name("Shopping cart").set_team("PQ9K7I8");
+        let request = CreateEventOrchestration::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "orchestration": {
+                "id": "b02e973d-9620-4e0a-9edc-00fedf7d4694",
+                "self": "https://api.pagerduty.com/event_orchestrations/b02e973d",
+                "name": "Shopping cart",
+                "description": null,
+                "team": { "id": "PQ9K7I8", "type": "team_reference" },
+                "integrations": [
+                    {
+                        "id": "1c26698b-803f-4ac4-8c6e-b3cf0e1e6d32",
+                        "parameters": {
+                            "routing_key": "R028DIE06SNKNO6V5ACSLRV7Y0TUVG7T",
+                            "type": "global"
+                        }
+                    }
+                ],
+                "routes": 0,
+                "created_at": "2021-11-18T16:42:01Z",
+                "version": "rn1Mja13T1HBdmPChqFilSQXUW2fWXM_"
+            }
+        });
+
+        let res = CreateEventOrchestration::get_response(StatusCode::Created,
+                                                         &Headers::new(),
+                                                         body);
+        let orchestration = res.unwrap().success().unwrap();
+
+        assert_eq!(orchestration.integrations[0].routing_key(),
+                   "R028DIE06SNKNO6V5ACSLRV7Y0TUVG7T");
+        assert_eq!(orchestration.routes, Some(0));
+    }
+}
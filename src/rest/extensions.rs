@@ -0,0 +1,749 @@
+//! Extensions
+//!
+//! Extensions send information about incidents to outside systems, such as a chat tool or a
+//! custom webhook. Each extension is an instance of an extension schema, which determines the
+//! configuration it accepts, and is attached to one or more extension objects, such as services.
+//! The available schemas can be listed with [`list_schemas`](fn.list_schemas.html).
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde::Serialize;
+use serde_json::{to_string, to_value, Value as Json};
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// An extension sending incident information to an outside system
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Extension {
+    /// The extension's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the extension
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the extension is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the extension is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the extension
+    pub name: String,
+
+    /// The URL at which the extension sends information, for schemas which take one
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+
+    /// The objects, such as services, the extension is attached to
+    #[serde(default)]
+    pub extension_objects: Vec<Reference>,
+
+    /// The schema the extension is an instance of
+    pub extension_schema: Reference,
+
+    /// Schema-specific configuration of the extension
+    #[serde(default)]
+    pub config: Option<Json>,
+
+    /// Whether the extension was disabled after repeated delivery failures
+    #[serde(default)]
+    pub temporarily_disabled: bool,
+}
+
+/// A type of extension which can be created
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ExtensionSchema {
+    /// The schema's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the schema
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the schema is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the schema is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// A short, unique key identifying the schema, such as `generic_v2_webhook`
+    pub key: String,
+
+    /// The human-readable name of the schema
+    pub label: String,
+
+    /// A description of the schema
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// A link to documentation on configuring extensions of this schema
+    #[serde(default)]
+    pub guide_url: Option<String>,
+
+    /// URL of a small icon representing the schema
+    #[serde(default)]
+    pub icon_url: Option<String>,
+
+    /// URL of a large logo representing the schema
+    #[serde(default)]
+    pub logo_url: Option<String>,
+
+    /// The types of incident events sent to extensions of this schema, such as `trigger`
+    #[serde(default)]
+    pub send_types: Vec<String>,
+
+    /// The URL extensions of this schema send to, if fixed by the schema rather than set with
+    /// `ExtensionFields::set_endpoint_url`
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// List extensions, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListExtensions<'a> {
+    query: Option<Cow<'a, str>>,
+    extension_object_id: Option<Cow<'a, str>>,
+    extension_schema_id: Option<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListExtensions<'a> {
+    /// Create a request listing all extensions
+    pub fn new() -> ListExtensions<'a> {
+        ListExtensions::default()
+    }
+
+    /// Only return extensions whose names contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Only return extensions attached to object `id`, such as a service
+    pub fn set_extension_object_id<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.extension_object_id = Some(id.into());
+        self
+    }
+
+    /// Only return instances of extension schema `id`
+    pub fn set_extension_schema_id<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.extension_schema_id = Some(id.into());
+        self
+    }
+
+    /// Include additional models in the response, such as `"extension_objects"` or
+    /// `"extension_schemas"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListExtensions<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListExtensions<'a> {
+    type Response = Response<Page<Extension>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        if let Some(ref id) = self.extension_object_id {
+            query.push("extension_object_id", id);
+        }
+        if let Some(ref id) = self.extension_schema_id {
+            query.push("extension_schema_id", id);
+        }
+        query.push_array("include", &self.include);
+        self.pagination.push_to(&mut query);
+
+        query.url("/extensions").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Extension>>> {
+        get_page(status, headers, body, "extensions")
+    }
+}
+
+/// Get details about an existing extension
+#[derive(Debug)]
+pub struct GetExtension<'a> {
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetExtension<'a> {
+    /// Create a request for extension `id`
+    pub fn new<S>(id: S) -> GetExtension<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetExtension {
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional models in the response, such as `"extension_schemas"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetExtension<'a> {
+    type Response = Response<Extension>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url(&format!("/extensions/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Extension>> {
+        get_response(status, headers, body, "extension")
+    }
+}
+
+/// Writable fields of an extension
+///
+/// Only fields which have been set are sent, so the same type is used for creating and updating
+/// extensions.
+#[derive(Debug, Serialize)]
+pub struct ExtensionFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    endpoint_url: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    extension_schema: Option<Reference>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    extension_objects: Vec<Reference>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    config: Option<Json>,
+}
+
+impl<'a> Default for ExtensionFields<'a> {
+    fn default() -> ExtensionFields<'a> {
+        ExtensionFields::new()
+    }
+}
+
+impl<'a> ExtensionFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> ExtensionFields<'a> {
+        ExtensionFields {
+            kind: "extension",
+            name: None,
+            endpoint_url: None,
+            extension_schema: None,
+            extension_objects: Vec::new(),
+            config: None,
+        }
+    }
+
+    /// Set the name of the extension
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the URL at which the extension sends information
+    pub fn set_endpoint_url<S>(mut self, endpoint_url: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.endpoint_url = Some(endpoint_url.into());
+        self
+    }
+
+    /// Set the schema the extension is an instance of
+    pub fn set_extension_schema<S>(mut self, schema_id: S) -> Self
+        where S: Into<String>
+    {
+        self.extension_schema = Some(Reference::new(schema_id, "extension_schema_reference"));
+        self
+    }
+
+    /// Attach the extension to service `service_id`; may be called more than once
+    ///
+    /// When updating, the attached services are replaced by those added here.
+    pub fn add_service<S>(mut self, service_id: S) -> Self
+        where S: Into<String>
+    {
+        self.extension_objects.push(Reference::new(service_id, "service_reference"));
+        self
+    }
+
+    /// Set the schema-specific configuration of the extension
+    ///
+    /// # Panics
+    /// Panics if `serde_json::to_value` on the config type returns an error.
+    pub fn set_config<T>(mut self, config: &T) -> Self
+        where T: ?Sized + Serialize
+    {
+        self.config = Some(to_value(config).unwrap());
+        self
+    }
+}
+
+/// Create a new extension
+#[derive(Debug, Serialize)]
+pub struct CreateExtension<'a> {
+    extension: ExtensionFields<'a>,
+}
+
+impl<'a> CreateExtension<'a> {
+    /// Create a request for a new extension described by `fields`
+    ///
+    /// PagerDuty requires at least a name, an extension schema, and one extension object.
+    pub fn new(fields: ExtensionFields<'a>) -> CreateExtension<'a> {
+        CreateExtension {
+            extension: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateExtension<'a> {
+    type Response = Response<Extension>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/extensions", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Extension>> {
+        get_response(status, headers, body, "extension")
+    }
+}
+
+/// Update an existing extension
+#[derive(Debug, Serialize)]
+pub struct UpdateExtension<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    extension: ExtensionFields<'a>,
+}
+
+impl<'a> UpdateExtension<'a> {
+    /// Create a request applying `fields` to extension `id`
+    pub fn new<S>(id: S, fields: ExtensionFields<'a>) -> UpdateExtension<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateExtension {
+            id: id.into(),
+            extension: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateExtension<'a> {
+    type Response = Response<Extension>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/extensions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Extension>> {
+        get_response(status, headers, body, "extension")
+    }
+}
+
+/// Delete an existing extension
+#[derive(Debug)]
+pub struct DeleteExtension<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteExtension<'a> {
+    /// Create a request deleting extension `id`
+    pub fn new<S>(id: S) -> DeleteExtension<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteExtension {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteExtension<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/extensions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Re-enable an extension
+///
+/// PagerDuty temporarily disables extensions whose deliveries fail repeatedly, which is reported
+/// by `Extension::temporarily_disabled`.
+#[derive(Debug)]
+pub struct EnableExtension<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> EnableExtension<'a> {
+    /// Create a request enabling extension `id`
+    pub fn new<S>(id: S) -> EnableExtension<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        EnableExtension {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for EnableExtension<'a> {
+    type Response = Response<Extension>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/extensions/{}/enable", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Extension>> {
+        get_response(status, headers, body, "extension")
+    }
+}
+
+/// List the available extension schemas
+#[derive(Debug, Default)]
+pub struct ListExtensionSchemas {
+    pagination: Pagination,
+}
+
+impl ListExtensionSchemas {
+    /// Create a request listing all extension schemas
+    pub fn new() -> ListExtensionSchemas {
+        ListExtensionSchemas::default()
+    }
+}
+
+impl ListRequest for ListExtensionSchemas {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl Requestable for ListExtensionSchemas {
+    type Response = Response<Page<ExtensionSchema>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url("/extension_schemas").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<ExtensionSchema>>> {
+        get_page(status, headers, body, "extension_schemas")
+    }
+}
+
+/// Get details about an extension schema
+#[derive(Debug)]
+pub struct GetExtensionSchema<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetExtensionSchema<'a> {
+    /// Create a request for extension schema `id`
+    pub fn new<S>(id: S) -> GetExtensionSchema<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetExtensionSchema {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetExtensionSchema<'a> {
+    type Response = Response<ExtensionSchema>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/extension_schemas/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<ExtensionSchema>> {
+        get_response(status, headers, body, "extension_schema")
+    }
+}
+
+/// List extensions
+pub fn list(auth: &AuthToken,
+            request: &ListExtensions) -> request::Result<Response<Page<Extension>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single extension
+pub fn get(auth: &AuthToken, request: &GetExtension) -> request::Result<Response<Extension>> {
+    request::perform(auth, request)
+}
+
+/// Create an extension
+pub fn create(auth: &AuthToken, request: &CreateExtension) -> request::Result<Response<Extension>> {
+    request::perform(auth, request)
+}
+
+/// Update an extension
+pub fn update(auth: &AuthToken, request: &UpdateExtension) -> request::Result<Response<Extension>> {
+    request::perform(auth, request)
+}
+
+/// Delete an extension
+pub fn delete(auth: &AuthToken, request: &DeleteExtension) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Enable an extension
+pub fn enable(auth: &AuthToken, request: &EnableExtension) -> request::Result<Response<Extension>> {
+    request::perform(auth, request)
+}
+
+/// List extension schemas
+pub fn list_schemas(auth: &AuthToken, request: &ListExtensionSchemas)
+    -> request::Result<Response<Page<ExtensionSchema>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get a single extension schema
+pub fn get_schema(auth: &AuthToken,
+                  request: &GetExtensionSchema) -> request::Result<Response<ExtensionSchema>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{CreateExtension, ExtensionFields, ListExtensionSchemas};
+
+    #[test]
+    fn create_extension_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "extension": {
+                "type": "extension",
+                "name": "Deploy hook",
+                "endpoint_url": "https://example.com/hook",
+                "extension_schema": { "id": "PJFWPEP", "type": "extension_schema_reference" },
+                "extension_objects": [ { "id": "PIJ90N7", "type": "service_reference" } ],
+                "config": { "notify_types": { "resolve": true } }
+            }
+        })).expect("expected is valid json");
+
+        let config: Json = from_str(r#"{"notify_types": {"resolve": true}}"#).unwrap();
+        let fields = ExtensionFields::new()
+            .set_name("Deploy hook")
+            .set_endpoint_url("https://example.com/hook")
+            .set_extension_schema("PJFWPEP")
+            .add_service("PIJ90N7")
+            .set_config(&config);
+
+        let request = CreateExtension::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "extension": {
+                "id": "PPGPXHO",
+                "type": "extension",
+                "summary": "Deploy hook",
+                "name": "Deploy hook",
+                "endpoint_url": "https://example.com/hook",
+                "extension_schema": { "id": "PJFWPEP", "type": "extension_schema_reference" },
+                "extension_objects": [ { "id": "PIJ90N7", "type": "service_reference" } ],
+                "temporarily_disabled": false
+            }
+        });
+
+        let res = CreateExtension::get_response(StatusCode::Created, &Headers::new(), body);
+        let extension = res.unwrap().success().unwrap();
+
+        assert_eq!(extension.extension_objects[0].id, "PIJ90N7");
+        assert!(!extension.temporarily_disabled);
+    }
+
+    #[test]
+    fn extension_schemas_from_json() {
+        let body = stringify!({
+            "extension_schemas": [
+                {
+                    "id": "PJFWPEP",
+                    "type": "extension_schema",
+                    "summary": "Generic V2 Webhook",
+                    "key": "generic_v2_webhook",
+                    "label": "Generic V2 Webhook",
+                    "description": "Send incident events to a URL",
+                    "send_types": ["trigger", "acknowledge", "resolve"],
+                    "url": null
+                }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false,
+            "total": null
+        });
+
+        let res = ListExtensionSchemas::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].key, "generic_v2_webhook");
+        assert_eq!(page.items[0].send_types.len(), 3);
+    }
+}
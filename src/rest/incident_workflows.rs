@@ -0,0 +1,653 @@
+//! Incident Workflows
+//!
+//! An incident workflow is a sequence of automated steps, such as creating a conference bridge or
+//! posting a status update, run on an incident. Workflows are started by triggers: manual triggers
+//! let responders start the workflow on demand, and conditional triggers start it when an incident
+//! matches a condition.
+//!
+//! Workflows can also be started directly on an incident with
+//! [`start_instance`](fn.start_instance.html).
+//!
+//! Triggers are paginated with cursors rather than offsets. Each response carries a `next_cursor`
+//! which is passed to `ListWorkflowTriggers::set_cursor` to request the following page.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_body_response, get_empty_response, get_page, get_response, segment, ListRequest,
+            Page, Pagination, Query, Reference, Response, BASE_URL};
+
+/// A sequence of automated steps run on an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct IncidentWorkflow {
+    /// The workflow's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the workflow
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the workflow is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the workflow is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the workflow
+    pub name: String,
+
+    /// A description of the workflow
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The team which owns the workflow, if any
+    #[serde(default)]
+    pub team: Option<Reference>,
+
+    /// The time at which the workflow was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// How a workflow trigger starts its workflow
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerType {
+    /// The workflow is started on demand by a responder
+    Manual,
+
+    /// The workflow is started when an incident matches the trigger's condition
+    Conditional,
+}
+
+/// Starts a workflow on incidents of some services
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct WorkflowTrigger {
+    /// The trigger's ID
+    pub id: String,
+
+    /// The API URL at which the trigger is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// How the trigger starts its workflow
+    pub trigger_type: TriggerType,
+
+    /// The condition incidents must match, for `TriggerType::Conditional`
+    ///
+    /// Conditions are written in PagerDuty Condition Language, for example
+    /// `incident.priority matches 'P1'`.
+    #[serde(default)]
+    pub condition: Option<String>,
+
+    /// The workflow the trigger starts
+    pub workflow: Reference,
+
+    /// The services whose incidents the trigger applies to
+    #[serde(default)]
+    pub services: Vec<Reference>,
+
+    /// Whether the trigger applies to incidents of all services
+    #[serde(default)]
+    pub is_subscribed_to_all_services: bool,
+}
+
+/// A page of workflow triggers
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct WorkflowTriggers {
+    /// The triggers on this page
+    pub triggers: Vec<WorkflowTrigger>,
+
+    /// The cursor of the next page, or `None` if this is the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// A run of a workflow on an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct WorkflowInstance {
+    /// The instance's ID
+    pub id: String,
+
+    /// The incident the workflow is running on
+    pub incident: Reference,
+}
+
+/// List incident workflows, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListIncidentWorkflows<'a> {
+    query: Option<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListIncidentWorkflows<'a> {
+    /// Create a request listing all incident workflows
+    pub fn new() -> ListIncidentWorkflows<'a> {
+        ListIncidentWorkflows::default()
+    }
+
+    /// Only return workflows whose names contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListIncidentWorkflows<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListIncidentWorkflows<'a> {
+    type Response = Response<Page<IncidentWorkflow>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/incident_workflows").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<IncidentWorkflow>>> {
+        get_page(status, headers, body, "incident_workflows")
+    }
+}
+
+/// List workflow triggers, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListWorkflowTriggers<'a> {
+    workflow_id: Option<Cow<'a, str>>,
+    incident_id: Option<Cow<'a, str>>,
+    service_id: Option<Cow<'a, str>>,
+    trigger_type: Option<TriggerType>,
+    limit: Option<usize>,
+    cursor: Option<Cow<'a, str>>,
+}
+
+impl<'a> ListWorkflowTriggers<'a> {
+    /// Create a request listing all workflow triggers
+    pub fn new() -> ListWorkflowTriggers<'a> {
+        ListWorkflowTriggers::default()
+    }
+
+    /// Only return triggers of workflow `workflow_id`
+    pub fn set_workflow_id<S>(mut self, workflow_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.workflow_id = Some(workflow_id.into());
+        self
+    }
+
+    /// Only return triggers which can be started manually on incident `incident_id`
+    pub fn set_incident_id<S>(mut self, incident_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.incident_id = Some(incident_id.into());
+        self
+    }
+
+    /// Only return triggers which apply to service `service_id`
+    pub fn set_service_id<S>(mut self, service_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.service_id = Some(service_id.into());
+        self
+    }
+
+    /// Only return triggers of `trigger_type`
+    pub fn set_trigger_type(mut self, trigger_type: TriggerType) -> Self {
+        self.trigger_type = Some(trigger_type);
+        self
+    }
+
+    /// Set the number of triggers per page
+    pub fn set_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Request the page at `cursor`, the `next_cursor` of the previous page
+    pub fn set_cursor<S>(mut self, cursor: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListWorkflowTriggers<'a> {
+    type Response = Response<WorkflowTriggers>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref workflow_id) = self.workflow_id {
+            query.push("workflow_id", workflow_id);
+        }
+        if let Some(ref incident_id) = self.incident_id {
+            query.push("incident_id", incident_id);
+        }
+        if let Some(ref service_id) = self.service_id {
+            query.push("service_id", service_id);
+        }
+        match self.trigger_type {
+            Some(TriggerType::Manual) => query.push("trigger_type", "manual"),
+            Some(TriggerType::Conditional) => query.push("trigger_type", "conditional"),
+            None => (),
+        }
+        if let Some(limit) = self.limit {
+            query.push("limit", limit.to_string());
+        }
+        if let Some(ref cursor) = self.cursor {
+            query.push("cursor", cursor);
+        }
+
+        query.url("/incident_workflows/triggers").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WorkflowTriggers>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// Get details about an existing workflow trigger
+#[derive(Debug)]
+pub struct GetWorkflowTrigger<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetWorkflowTrigger<'a> {
+    /// Create a request for workflow trigger `id`
+    pub fn new<S>(id: S) -> GetWorkflowTrigger<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetWorkflowTrigger {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetWorkflowTrigger<'a> {
+    type Response = Response<WorkflowTrigger>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incident_workflows/triggers/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WorkflowTrigger>> {
+        get_response(status, headers, body, "trigger")
+    }
+}
+
+/// Fields of a new workflow trigger
+#[derive(Debug, Serialize)]
+pub struct WorkflowTriggerFields<'a> {
+    trigger_type: TriggerType,
+
+    workflow: Reference,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    condition: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    services: Vec<Reference>,
+
+    is_subscribed_to_all_services: bool,
+}
+
+impl<'a> WorkflowTriggerFields<'a> {
+    /// A trigger letting responders start workflow `workflow_id` manually
+    pub fn manual<S>(workflow_id: S) -> WorkflowTriggerFields<'a>
+        where S: Into<String>
+    {
+        WorkflowTriggerFields {
+            trigger_type: TriggerType::Manual,
+            workflow: Reference::new(workflow_id, "incident_workflow_reference"),
+            condition: None,
+            services: Vec::new(),
+            is_subscribed_to_all_services: false,
+        }
+    }
+
+    /// A trigger starting workflow `workflow_id` when an incident matches `condition`
+    ///
+    /// Conditions are written in PagerDuty Condition Language, for example
+    /// `incident.priority matches 'P1'`.
+    pub fn conditional<S, C>(workflow_id: S, condition: C) -> WorkflowTriggerFields<'a>
+        where S: Into<String>,
+              C: Into<Cow<'a, str>>
+    {
+        WorkflowTriggerFields {
+            trigger_type: TriggerType::Conditional,
+            condition: Some(condition.into()),
+            ..WorkflowTriggerFields::manual(workflow_id)
+        }
+    }
+
+    /// Apply the trigger to incidents of service `service_id`; may be called more than once
+    pub fn add_service<S>(mut self, service_id: S) -> Self
+        where S: Into<String>
+    {
+        self.services.push(Reference::new(service_id, "service_reference"));
+        self
+    }
+
+    /// Apply the trigger to incidents of all services, including those created later
+    pub fn set_all_services(mut self, all: bool) -> Self {
+        self.is_subscribed_to_all_services = all;
+        self
+    }
+}
+
+/// Create a new workflow trigger
+#[derive(Debug, Serialize)]
+pub struct CreateWorkflowTrigger<'a> {
+    trigger: WorkflowTriggerFields<'a>,
+}
+
+impl<'a> CreateWorkflowTrigger<'a> {
+    /// Create a request for a new workflow trigger described by `fields`
+    pub fn new(fields: WorkflowTriggerFields<'a>) -> CreateWorkflowTrigger<'a> {
+        CreateWorkflowTrigger {
+            trigger: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateWorkflowTrigger<'a> {
+    type Response = Response<WorkflowTrigger>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incident_workflows/triggers", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WorkflowTrigger>> {
+        get_response(status, headers, body, "trigger")
+    }
+}
+
+/// Delete an existing workflow trigger
+#[derive(Debug)]
+pub struct DeleteWorkflowTrigger<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteWorkflowTrigger<'a> {
+    /// Create a request deleting workflow trigger `id`
+    pub fn new<S>(id: S) -> DeleteWorkflowTrigger<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteWorkflowTrigger {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteWorkflowTrigger<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incident_workflows/triggers/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct InstanceFields {
+    incident: Reference,
+}
+
+/// Start a workflow on an incident
+#[derive(Debug, Serialize)]
+pub struct StartWorkflowInstance<'a> {
+    #[serde(skip_serializing)]
+    workflow_id: Cow<'a, str>,
+
+    incident_workflow_instance: InstanceFields,
+}
+
+impl<'a> StartWorkflowInstance<'a> {
+    /// Create a request starting workflow `workflow_id` on incident `incident_id`
+    pub fn new<S, I>(workflow_id: S, incident_id: I) -> StartWorkflowInstance<'a>
+        where S: Into<Cow<'a, str>>,
+              I: Into<String>
+    {
+        StartWorkflowInstance {
+            workflow_id: workflow_id.into(),
+            incident_workflow_instance: InstanceFields {
+                incident: Reference::new(incident_id, "incident_reference"),
+            },
+        }
+    }
+}
+
+impl<'a> Requestable for StartWorkflowInstance<'a> {
+    type Response = Response<WorkflowInstance>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incident_workflows/{}/instances", BASE_URL, segment(&self.workflow_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WorkflowInstance>> {
+        get_response(status, headers, body, "incident_workflow_instance")
+    }
+}
+
+/// List incident workflows
+pub fn list(auth: &AuthToken, request: &ListIncidentWorkflows)
+    -> request::Result<Response<Page<IncidentWorkflow>>>
+{
+    request::perform(auth, request)
+}
+
+/// List workflow triggers
+pub fn list_triggers(auth: &AuthToken, request: &ListWorkflowTriggers)
+    -> request::Result<Response<WorkflowTriggers>>
+{
+    request::perform(auth, request)
+}
+
+/// Get a single workflow trigger
+pub fn get_trigger(auth: &AuthToken,
+                   request: &GetWorkflowTrigger) -> request::Result<Response<WorkflowTrigger>> {
+    request::perform(auth, request)
+}
+
+/// Create a workflow trigger
+pub fn create_trigger(auth: &AuthToken, request: &CreateWorkflowTrigger)
+    -> request::Result<Response<WorkflowTrigger>>
+{
+    request::perform(auth, request)
+}
+
+/// Delete a workflow trigger
+pub fn delete_trigger(auth: &AuthToken,
+                      request: &DeleteWorkflowTrigger) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Start a workflow on an incident
+pub fn start_instance(auth: &AuthToken, request: &StartWorkflowInstance)
+    -> request::Result<Response<WorkflowInstance>>
+{
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{CreateWorkflowTrigger, ListWorkflowTriggers, StartWorkflowInstance, TriggerType,
+                WorkflowTriggerFields};
+
+    #[test]
+    fn create_conditional_trigger() {
+        let expected: Json = from_str(stringify!({
+            "trigger": {
+                "trigger_type": "conditional",
+                "workflow": { "id": "PSFEVL7", "type": "incident_workflow_reference" },
+                "condition": "incident.priority matches 'P1'",
+                "services": [ { "id": "PIJ90N7", "type": "service_reference" } ],
+                "is_subscribed_to_all_services": false
+            }
+        })).unwrap();
+
+        let fields = WorkflowTriggerFields::conditional("PSFEVL7", "incident.priority matches 'P1'")
+            .add_service("PIJ90N7");
+        let request = CreateWorkflowTrigger::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn list_triggers_from_json() {
+        let request = ListWorkflowTriggers::new()
+            .set_incident_id("PT4KHLK")
+            .set_trigger_type(TriggerType::Manual);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incident_workflows/triggers\
+                    ?incident_id=PT4KHLK&trigger_type=manual");
+
+        let body = stringify!({
+            "triggers": [
+                {
+                    "id": "PR1B3ZS",
+                    "type": "workflow_trigger",
+                    "trigger_type": "manual",
+                    "workflow": {
+                        "id": "PSFEVL7",
+                        "type": "incident_workflow",
+                        "summary": "Major incident"
+                    },
+                    "services": [],
+                    "is_subscribed_to_all_services": true
+                }
+            ],
+            "next_cursor": "dHJpZ2dlcjpQUjFCM1pT",
+            "limit": 1
+        });
+
+        let res = ListWorkflowTriggers::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.triggers[0].workflow.summary, Some("Major incident".to_owned()));
+        assert!(page.triggers[0].is_subscribed_to_all_services);
+        assert_eq!(page.next_cursor, Some("dHJpZ2dlcjpQUjFCM1pT".to_owned()));
+    }
+
+    #[test]
+    fn start_workflow_instance() {
+        let request = StartWorkflowInstance::new("PSFEVL7", "PT4KHLK");
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/incident_workflows/PSFEVL7/instances");
+        let actual: Json = from_str(&request.body()).unwrap();
+        assert_eq!(actual["incident_workflow_instance"]["incident"]["id"], "PT4KHLK");
+    }
+}
@@ -0,0 +1,2011 @@
+//! Incidents
+//!
+//! An incident represents a problem or an issue that needs to be addressed and resolved. Incidents
+//! can be triggered through the integration API or created through the REST API.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, Utc};
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde::Serialize;
+use serde_json::{to_string, to_value, Value as Json};
+
+use AuthToken;
+use request::{self, Requestable};
+use timestamp::Timestamp;
+
+pub use events_v2::Severity;
+pub use super::users::Urgency;
+use super::{get_page, get_response, segment, ListRequest, Page, Pagination, Query, Reference,
+            Response, BASE_URL};
+use super::custom_fields::{self, CustomFieldValue, FieldValue, GetFieldValues, SetFieldValues};
+use super::log_entries::{ListLogEntries, LogEntry};
+
+/// The current state of an incident
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentStatus {
+    Triggered,
+    Acknowledged,
+    Resolved,
+}
+
+impl IncidentStatus {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            IncidentStatus::Triggered => "triggered",
+            IncidentStatus::Acknowledged => "acknowledged",
+            IncidentStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// A problem or issue which needs to be addressed and resolved
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Incident {
+    /// The incident's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the incident
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the incident is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the incident is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The incident's number, unique within the account
+    pub incident_number: u64,
+
+    /// The title of the incident
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// The time at which the incident was created
+    pub created_at: String,
+
+    /// The current state of the incident
+    pub status: IncidentStatus,
+
+    /// The key incidents are deduplicated with
+    #[serde(default)]
+    pub incident_key: Option<String>,
+
+    /// The service the incident belongs to
+    pub service: Reference,
+
+    /// The users the incident is currently assigned to
+    #[serde(default)]
+    pub assignments: Vec<Assignment>,
+
+    /// The time at which the status of the incident last changed
+    #[serde(default)]
+    pub last_status_change_at: Option<String>,
+
+    /// The escalation policy the incident is following
+    #[serde(default)]
+    pub escalation_policy: Option<Reference>,
+
+    /// The teams involved in the incident's lifecycle
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// The priority of the incident, if one is set
+    #[serde(default)]
+    pub priority: Option<Reference>,
+
+    /// The incident's urgency
+    pub urgency: Urgency,
+}
+
+#[cfg(feature = "chrono")]
+impl Incident {
+    /// The time at which the incident was created, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// The assignment of an incident to a user
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Assignment {
+    /// The time at which the incident was assigned
+    pub at: String,
+
+    /// The user the incident is assigned to
+    pub assignee: Reference,
+}
+
+/// List incidents
+///
+/// Without a date range, only incidents of the last 30 days are returned.
+#[derive(Debug, Default)]
+pub struct ListIncidents<'a> {
+    statuses: Vec<IncidentStatus>,
+    urgencies: Vec<Urgency>,
+    service_ids: Vec<Cow<'a, str>>,
+    team_ids: Vec<Cow<'a, str>>,
+    user_ids: Vec<Cow<'a, str>>,
+    incident_key: Option<Cow<'a, str>>,
+    since: Option<Cow<'a, str>>,
+    until: Option<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListIncidents<'a> {
+    /// Create a request listing all incidents
+    pub fn new() -> ListIncidents<'a> {
+        ListIncidents::default()
+    }
+
+    /// Only return incidents with the given status; may be called more than once
+    pub fn add_status(mut self, status: IncidentStatus) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Only return incidents with the given urgency; may be called more than once
+    pub fn add_urgency(mut self, urgency: Urgency) -> Self {
+        self.urgencies.push(urgency);
+        self
+    }
+
+    /// Only return incidents of service `service_id`; may be called more than once
+    pub fn add_service_id<S>(mut self, service_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.service_ids.push(service_id.into());
+        self
+    }
+
+    /// Only return incidents of team `team_id`; may be called more than once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Only return incidents assigned to user `user_id`; may be called more than once
+    pub fn add_user_id<S>(mut self, user_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.user_ids.push(user_id.into());
+        self
+    }
+
+    /// Only return the incident with the given incident key
+    pub fn set_incident_key<S>(mut self, incident_key: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.incident_key = Some(incident_key.into());
+        self
+    }
+
+    /// Only return incidents created at or after `since`, in ISO 8601
+    pub fn set_since<S>(mut self, since: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.since = Some(since.into_timestamp());
+        self
+    }
+
+    /// Only return incidents created before `until`, in ISO 8601
+    pub fn set_until<S>(mut self, until: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.until = Some(until.into_timestamp());
+        self
+    }
+
+    /// Include additional details in the response, for example `"assignees"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListIncidents<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListIncidents<'a> {
+    type Response = Response<Page<Incident>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("statuses", self.statuses.iter().map(|status| status.as_str()));
+        query.push_array("urgencies", self.urgencies.iter().map(|urgency| urgency.as_str()));
+        query.push_array("service_ids", &self.service_ids);
+        query.push_array("team_ids", &self.team_ids);
+        query.push_array("user_ids", &self.user_ids);
+        if let Some(ref incident_key) = self.incident_key {
+            query.push("incident_key", incident_key);
+        }
+        if let Some(ref since) = self.since {
+            query.push("since", since);
+        }
+        if let Some(ref until) = self.until {
+            query.push("until", until);
+        }
+        query.push_array("include", &self.include);
+        self.pagination.push_to(&mut query);
+
+        query.url("/incidents").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Incident>>> {
+        get_page(status, headers, body, "incidents")
+    }
+}
+
+/// Get a single incident
+#[derive(Debug)]
+pub struct GetIncident<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetIncident<'a> {
+    /// Create a request for incident `id`
+    ///
+    /// `id` may also be the incident's number.
+    pub fn new<S>(id: S) -> GetIncident<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetIncident {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetIncident<'a> {
+    type Response = Response<Incident>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Incident>> {
+        get_response(status, headers, body, "incident")
+    }
+}
+
+/// Changes to make to an incident
+///
+/// Only the properties which are set are changed.
+#[derive(Debug, Serialize)]
+pub struct IncidentUpdate<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    status: Option<IncidentStatus>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    priority: Option<Reference>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    urgency: Option<Urgency>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    title: Option<Cow<'a, str>>,
+}
+
+impl<'a> IncidentUpdate<'a> {
+    /// Create an update which doesn't change anything yet
+    pub fn new() -> IncidentUpdate<'a> {
+        IncidentUpdate {
+            kind: "incident_reference",
+            status: None,
+            priority: None,
+            urgency: None,
+            title: None,
+        }
+    }
+
+    /// Acknowledge or resolve the incident
+    pub fn set_status(mut self, status: IncidentStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Set the incident's priority; see [`Priority::to_reference`]
+    ///
+    /// [`Priority::to_reference`]: ../priorities/struct.Priority.html#method.to_reference
+    pub fn set_priority(mut self, priority: Reference) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Set the incident's urgency
+    pub fn set_urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = Some(urgency);
+        self
+    }
+
+    /// Set the title of the incident
+    pub fn set_title<S>(mut self, title: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl<'a> Default for IncidentUpdate<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Update a single incident
+#[derive(Debug, Serialize)]
+pub struct UpdateIncident<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    incident: IncidentUpdate<'a>,
+}
+
+impl<'a> UpdateIncident<'a> {
+    /// Create a request applying `incident` to incident `id`
+    pub fn new<S>(id: S, incident: IncidentUpdate<'a>) -> UpdateIncident<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateIncident {
+            id: id.into(),
+            from: None,
+            incident,
+        }
+    }
+
+    /// Set the email address of the user updating the incident
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for UpdateIncident<'a> {
+    type Response = Response<Incident>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Incident>> {
+        get_response(status, headers, body, "incident")
+    }
+}
+
+/// A note attached to an incident
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Note {
+    /// The note's ID
+    pub id: String,
+
+    /// The user who created the note
+    pub user: Reference,
+
+    /// The means by which the note was created
+    #[serde(default)]
+    pub channel: Option<NoteChannel>,
+
+    /// The note's content
+    pub content: String,
+
+    /// The time at which the note was submitted
+    pub created_at: String,
+}
+
+#[cfg(feature = "chrono")]
+impl Note {
+    /// The time at which the note was submitted, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// Describes how a note was created
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteChannel {
+    /// A short description of the channel, for example "The PagerDuty website or APIs"
+    pub summary: String,
+}
+
+/// List existing notes for the specified incident
+#[derive(Debug)]
+pub struct ListNotes<'a> {
+    incident_id: Cow<'a, str>,
+}
+
+impl<'a> ListNotes<'a> {
+    /// Create a request listing the notes of incident `incident_id`
+    pub fn new<S>(incident_id: S) -> ListNotes<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListNotes {
+            incident_id: incident_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for ListNotes<'a> {
+    type Response = Response<Vec<Note>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/notes", BASE_URL, segment(&self.incident_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<Note>>> {
+        get_response(status, headers, body, "notes")
+    }
+}
+
+/// Create a new note for the specified incident
+#[derive(Debug, Serialize)]
+pub struct CreateNote<'a> {
+    #[serde(skip_serializing)]
+    incident_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    note: NoteContent<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct NoteContent<'a> {
+    content: Cow<'a, str>,
+}
+
+impl<'a> CreateNote<'a> {
+    /// Create a request adding a note with `content` to incident `incident_id`
+    pub fn new<S, T>(incident_id: S, content: T) -> CreateNote<'a>
+        where S: Into<Cow<'a, str>>,
+              T: Into<Cow<'a, str>>
+    {
+        CreateNote {
+            incident_id: incident_id.into(),
+            from: None,
+            note: NoteContent {
+                content: content.into(),
+            },
+        }
+    }
+
+    /// Set the email address of the user creating the note
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for CreateNote<'a> {
+    type Response = Response<Note>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/notes", BASE_URL, segment(&self.incident_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Note>> {
+        get_response(status, headers, body, "note")
+    }
+}
+
+/// An update on the progress of an incident, sent to its stakeholders
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatusUpdate {
+    /// The status update's ID
+    pub id: String,
+
+    /// The message sent to stakeholders
+    pub message: String,
+
+    /// The user who sent the update
+    #[serde(default)]
+    pub sender: Option<Reference>,
+
+    /// The subject of the email sent to stakeholders, when customized
+    #[serde(default)]
+    pub subject: Option<String>,
+
+    /// The time at which the update was sent
+    pub created_at: String,
+}
+
+#[cfg(feature = "chrono")]
+impl StatusUpdate {
+    /// The time at which the update was sent, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// List the status updates sent for an incident
+#[derive(Debug)]
+pub struct ListStatusUpdates<'a> {
+    incident_id: Cow<'a, str>,
+}
+
+impl<'a> ListStatusUpdates<'a> {
+    /// Create a request listing the status updates of incident `incident_id`
+    pub fn new<S>(incident_id: S) -> ListStatusUpdates<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListStatusUpdates {
+            incident_id: incident_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for ListStatusUpdates<'a> {
+    type Response = Response<Vec<StatusUpdate>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/status_updates", BASE_URL, segment(&self.incident_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<StatusUpdate>>> {
+        get_response(status, headers, body, "status_updates")
+    }
+}
+
+/// Record structured facts about an incident
+///
+/// Facts are given as any type implementing `Serialize`, and are rendered into a note with one
+/// `key: value` line per field. Facts which don't serialize to an object are rendered as JSON.
+///
+/// Each fact named after one of the incident's custom fields is also stored in that field, when
+/// custom fields are available on the account and the fact's type matches the field's data type.
+/// See [`annotate`](fn.annotate.html).
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use]
+/// # extern crate serde_derive;
+/// # extern crate pagerduty;
+/// use pagerduty::rest::incidents::Annotate;
+///
+/// #[derive(Serialize)]
+/// struct Facts {
+///     deploy: &'static str,
+///     rollback: bool,
+/// }
+///
+/// # fn main() {
+/// let request = Annotate::new("PT4KHLK", &Facts { deploy: "v1.4.2", rollback: true });
+/// assert_eq!(request.content(), "deploy: v1.4.2\nrollback: true");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Annotate<'a> {
+    note: CreateNote<'a>,
+    facts: Json,
+}
+
+impl<'a> Annotate<'a> {
+    /// Create a request annotating incident `incident_id` with `facts`
+    ///
+    /// # Panics
+    /// Panics if `serde_json::to_value` on the facts type returns an error.
+    pub fn new<S, T>(incident_id: S, facts: &T) -> Annotate<'a>
+        where S: Into<Cow<'a, str>>,
+              T: ?Sized + Serialize
+    {
+        let facts = to_value(facts).unwrap();
+        let content = render_facts(&facts);
+        Annotate {
+            note: CreateNote::new(incident_id, content),
+            facts,
+        }
+    }
+
+    /// Set the email address of the user annotating the incident
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.note = self.note.set_from(from);
+        self
+    }
+
+    /// The content of the note which will be added
+    pub fn content(&self) -> &str {
+        &self.note.note.content
+    }
+
+    /// The request storing facts in the incident's custom fields of the same name
+    ///
+    /// `fields` are the incident's current custom field values. A `null` fact clears its field.
+    /// Facts whose type doesn't match their field's data type are left out; see
+    /// [`mismatched_facts`](#method.mismatched_facts). Returns `None` when no fact can be stored
+    /// in one of the fields.
+    pub fn field_values(&self, fields: &[CustomFieldValue]) -> Option<SetFieldValues<'a>> {
+        let facts = match self.facts {
+            Json::Object(ref facts) => facts,
+            _ => return None,
+        };
+
+        let mut request = SetFieldValues::new(self.note.incident_id.clone());
+        let mut matched = false;
+
+        for field in fields {
+            if let Some(fact) = facts.get(&field.name) {
+                request = match FieldValue::from_json(field.data_type, fact.clone()) {
+                    Some(ref value) if !matches_type(value) => continue,
+                    Some(value) => request.set_value(field.name.clone(), value),
+                    None => request.clear_value(field.name.clone()),
+                };
+                matched = true;
+            }
+        }
+
+        if matched {
+            Some(request)
+        } else {
+            None
+        }
+    }
+
+    /// The names of facts which aren't stored in the custom field of the same name, because the
+    /// fact's type doesn't match the field's data type
+    pub fn mismatched_facts(&self, fields: &[CustomFieldValue]) -> Vec<String> {
+        let facts = match self.facts {
+            Json::Object(ref facts) => facts,
+            _ => return Vec::new(),
+        };
+
+        fields.iter()
+            .filter(|field| {
+                facts.get(&field.name)
+                    .and_then(|fact| FieldValue::from_json(field.data_type, fact.clone()))
+                    .map(|value| !matches_type(&value))
+                    .unwrap_or(false)
+            })
+            .map(|field| field.name.clone())
+            .collect()
+    }
+}
+
+/// Whether `value` has the data type of the field it was interpreted for
+fn matches_type(value: &FieldValue) -> bool {
+    match *value {
+        FieldValue::Other(_) => false,
+        FieldValue::List(ref values) => values.iter().all(matches_type),
+        _ => true,
+    }
+}
+
+/// The outcome of annotating an incident
+#[derive(Debug)]
+pub struct Annotation {
+    /// The note the facts were rendered into
+    pub note: Note,
+
+    /// The incident's custom field values after facts were stored in them
+    ///
+    /// This is empty when no fact is named after a custom field. It's an error when the values
+    /// couldn't be read or stored, such as on plans without custom fields; the note is added
+    /// regardless.
+    pub fields: Result<Vec<CustomFieldValue>, FieldsError>,
+
+    /// Facts named after a custom field which weren't stored in it, because the fact's type
+    /// doesn't match the field's data type
+    pub mismatched: Vec<String>,
+}
+
+/// Why facts weren't stored in an incident's custom fields
+#[derive(Debug)]
+pub enum FieldsError {
+    /// The request failed, or its response wasn't understood
+    ///
+    /// PagerDuty answers `402 Payment Required` on plans without custom fields, which is reported
+    /// as `request::Error::UnexpectedApiResponse`.
+    Request(request::Error),
+
+    /// PagerDuty refused to read or store the custom field values
+    Response(Response<()>),
+}
+
+fn render_facts(facts: &Json) -> String {
+    match *facts {
+        Json::Object(ref fields) => {
+            fields.iter()
+                .map(|(key, value)| match *value {
+                    Json::String(ref value) => format!("{}: {}", key, value),
+                    ref value => format!("{}: {}", key, value),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+        ref other => other.to_string(),
+    }
+}
+
+/// The current state of an alert
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertStatus {
+    Triggered,
+    Resolved,
+}
+
+impl AlertStatus {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            AlertStatus::Triggered => "triggered",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// A problem reported by a monitoring tool, grouped into an incident
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Alert {
+    /// The alert's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the alert
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the alert is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the alert is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The time at which the alert was created
+    pub created_at: String,
+
+    /// The current state of the alert
+    pub status: AlertStatus,
+
+    /// The magnitude of the problem described by the alert
+    #[serde(default)]
+    pub severity: Option<Severity>,
+
+    /// The key used to correlate triggers, acknowledges, and resolves for the same alert
+    #[serde(default)]
+    pub alert_key: Option<String>,
+
+    /// The service the alert was sent to
+    #[serde(default)]
+    pub service: Option<Reference>,
+
+    /// The incident the alert is grouped into
+    #[serde(default)]
+    pub incident: Option<Reference>,
+
+    /// The integration which received the alert
+    #[serde(default)]
+    pub integration: Option<Reference>,
+
+    /// Whether the alert was suppressed and didn't create an incident
+    #[serde(default)]
+    pub suppressed: bool,
+
+    /// The raw data of the alert as sent by the monitoring tool
+    #[serde(default)]
+    pub body: Option<AlertBody>,
+}
+
+#[cfg(feature = "chrono")]
+impl Alert {
+    /// The time at which the alert was created, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// The raw data of an alert
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AlertBody {
+    /// Links and images sent with the alert
+    #[serde(default)]
+    pub contexts: Vec<Json>,
+
+    /// Arbitrary details sent with the alert
+    #[serde(default)]
+    pub details: Option<Json>,
+
+    /// Details of the alert in Common Event Format, when available
+    #[serde(default)]
+    pub cef_details: Option<Json>,
+}
+
+/// List alerts for the specified incident
+#[derive(Debug)]
+pub struct ListAlerts<'a> {
+    incident_id: Cow<'a, str>,
+    alert_key: Option<Cow<'a, str>>,
+    statuses: Vec<AlertStatus>,
+    include: Vec<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListAlerts<'a> {
+    /// Create a request listing the alerts of incident `incident_id`
+    pub fn new<S>(incident_id: S) -> ListAlerts<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListAlerts {
+            incident_id: incident_id.into(),
+            alert_key: None,
+            statuses: Vec::new(),
+            include: Vec::new(),
+            pagination: Pagination::default(),
+        }
+    }
+
+    /// Only return alerts with the given alert key
+    pub fn set_alert_key<S>(mut self, alert_key: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.alert_key = Some(alert_key.into());
+        self
+    }
+
+    /// Only return alerts with the given status; may be called more than once
+    pub fn add_status(mut self, status: AlertStatus) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    /// Include additional details in the response, for example `"services"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListAlerts<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListAlerts<'a> {
+    type Response = Response<Page<Alert>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref alert_key) = self.alert_key {
+            query.push("alert_key", alert_key);
+        }
+        query.push_array("statuses", self.statuses.iter().map(|status| status.as_str()));
+        query.push_array("include", &self.include);
+        self.pagination.push_to(&mut query);
+
+        query.url(&format!("/incidents/{}/alerts", segment(&self.incident_id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Alert>>> {
+        get_page(status, headers, body, "alerts")
+    }
+}
+
+/// Get a single alert of an incident
+#[derive(Debug)]
+pub struct GetAlert<'a> {
+    incident_id: Cow<'a, str>,
+    alert_id: Cow<'a, str>,
+}
+
+impl<'a> GetAlert<'a> {
+    /// Create a request for alert `alert_id` of incident `incident_id`
+    pub fn new<S>(incident_id: S, alert_id: S) -> GetAlert<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetAlert {
+            incident_id: incident_id.into(),
+            alert_id: alert_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetAlert<'a> {
+    type Response = Response<Alert>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/alerts/{}",
+                BASE_URL,
+                segment(&self.incident_id),
+                segment(&self.alert_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Alert>> {
+        get_response(status, headers, body, "alert")
+    }
+}
+
+/// Changes to make to an alert
+///
+/// Alerts can be resolved, or moved to a different incident by setting `incident`.
+#[derive(Debug, Serialize)]
+pub struct AlertUpdate<'a> {
+    id: Cow<'a, str>,
+
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    status: Option<AlertStatus>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    incident: Option<Reference>,
+}
+
+impl<'a> AlertUpdate<'a> {
+    /// Create an update for alert `alert_id` which doesn't change anything yet
+    pub fn new<S>(alert_id: S) -> AlertUpdate<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AlertUpdate {
+            id: alert_id.into(),
+            kind: "alert",
+            status: None,
+            incident: None,
+        }
+    }
+
+    /// Set the alert's status
+    ///
+    /// Only `AlertStatus::Resolved` is accepted by PagerDuty.
+    pub fn set_status(mut self, status: AlertStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Move the alert to incident `incident_id`
+    pub fn set_incident<S>(mut self, incident_id: S) -> Self
+        where S: Into<String>
+    {
+        self.incident = Some(Reference::new(incident_id, "incident_reference"));
+        self
+    }
+}
+
+/// Resolve or move a single alert of an incident
+#[derive(Debug, Serialize)]
+pub struct UpdateAlert<'a> {
+    #[serde(skip_serializing)]
+    incident_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    alert: AlertUpdate<'a>,
+}
+
+impl<'a> UpdateAlert<'a> {
+    /// Create a request applying `alert` to an alert of incident `incident_id`
+    pub fn new<S>(incident_id: S, alert: AlertUpdate<'a>) -> UpdateAlert<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateAlert {
+            incident_id: incident_id.into(),
+            from: None,
+            alert,
+        }
+    }
+
+    /// Set the email address of the user updating the alert
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for UpdateAlert<'a> {
+    type Response = Response<Alert>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/alerts/{}",
+                BASE_URL,
+                segment(&self.incident_id),
+                segment(&self.alert.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Alert>> {
+        get_response(status, headers, body, "alert")
+    }
+}
+
+/// Resolve or move multiple alerts of an incident at once
+#[derive(Debug, Serialize)]
+pub struct UpdateAlerts<'a> {
+    #[serde(skip_serializing)]
+    incident_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    alerts: Vec<AlertUpdate<'a>>,
+}
+
+impl<'a> UpdateAlerts<'a> {
+    /// Create a request updating alerts of incident `incident_id`
+    pub fn new<S>(incident_id: S) -> UpdateAlerts<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateAlerts {
+            incident_id: incident_id.into(),
+            from: None,
+            alerts: Vec::new(),
+        }
+    }
+
+    /// Add an alert update to the request
+    pub fn add_alert(mut self, alert: AlertUpdate<'a>) -> Self {
+        self.alerts.push(alert);
+        self
+    }
+
+    /// Set the email address of the user updating the alerts
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for UpdateAlerts<'a> {
+    type Response = Response<Vec<Alert>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/incidents/{}/alerts", BASE_URL, segment(&self.incident_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<Alert>>> {
+        get_response(status, headers, body, "alerts")
+    }
+}
+
+/// An incident similar to another, as determined by PagerDuty's machine learning
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct PastIncident {
+    /// The similar incident
+    pub incident: PastIncidentSummary,
+
+    /// How similar the incident is; higher scores are more similar
+    pub score: f64,
+}
+
+/// Summary of an incident returned by the past incidents endpoint
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct PastIncidentSummary {
+    /// The incident's ID
+    pub id: String,
+
+    /// The time at which the incident was created
+    pub created_at: String,
+
+    /// The API URL at which the incident is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// The incident's title
+    pub title: String,
+}
+
+/// List incidents from the past 6 months similar to the specified incident
+#[derive(Debug)]
+pub struct ListPastIncidents<'a> {
+    incident_id: Cow<'a, str>,
+    limit: Option<usize>,
+}
+
+impl<'a> ListPastIncidents<'a> {
+    /// Create a request for incidents similar to incident `incident_id`
+    pub fn new<S>(incident_id: S) -> ListPastIncidents<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListPastIncidents {
+            incident_id: incident_id.into(),
+            limit: None,
+        }
+    }
+
+    /// Set the maximum number of past incidents returned; PagerDuty defaults to 5
+    pub fn set_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl<'a> Requestable for ListPastIncidents<'a> {
+    type Response = Response<Vec<PastIncident>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(limit) = self.limit {
+            query.push("limit", limit.to_string());
+        }
+
+        query.url(&format!("/incidents/{}/past_incidents", segment(&self.incident_id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<PastIncident>>> {
+        get_response(status, headers, body, "past_incidents")
+    }
+}
+
+/// Everything recorded about an incident, for writing its postmortem
+///
+/// Serializing the bundle produces the same shapes the REST API returns for each part, so
+/// templates can be written against PagerDuty's API reference.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PostmortemBundle {
+    /// The incident itself
+    pub incident: Incident,
+
+    /// The alerts grouped into the incident
+    pub alerts: Vec<Alert>,
+
+    /// The incident's log entries, with their channels, from oldest to newest
+    pub log_entries: Vec<LogEntry>,
+
+    /// The notes added by responders
+    pub notes: Vec<Note>,
+
+    /// The status updates sent to stakeholders
+    pub status_updates: Vec<StatusUpdate>,
+}
+
+/// List incidents
+pub fn list(auth: &AuthToken,
+            request: &ListIncidents) -> request::Result<Response<Page<Incident>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single incident
+pub fn get(auth: &AuthToken, request: &GetIncident) -> request::Result<Response<Incident>> {
+    request::perform(auth, request)
+}
+
+/// Update an incident
+pub fn update(auth: &AuthToken, request: &UpdateIncident) -> request::Result<Response<Incident>> {
+    request::perform(auth, request)
+}
+
+/// List the notes of an incident
+pub fn list_notes(auth: &AuthToken, request: &ListNotes) -> request::Result<Response<Vec<Note>>> {
+    request::perform(auth, request)
+}
+
+/// Add a note to an incident
+pub fn create_note(auth: &AuthToken, request: &CreateNote) -> request::Result<Response<Note>> {
+    request::perform(auth, request)
+}
+
+/// Record structured facts about incident `incident_id`
+///
+/// The facts are rendered into a note, so they're recorded on any plan. Facts named after one of
+/// the incident's custom fields are then also stored in that field. Failing to read or store the
+/// custom field values, such as on plans without custom fields, doesn't prevent the note from
+/// being added; it's reported in [`Annotation::fields`](struct.Annotation.html#structfield.fields)
+/// instead.
+///
+/// # Panics
+/// Panics if `serde_json::to_value` on the facts type returns an error.
+pub fn annotate<'a, S, T>(auth: &AuthToken,
+                           incident_id: S,
+                           facts: &T) -> request::Result<Response<Annotation>>
+    where S: Into<Cow<'a, str>>,
+          T: ?Sized + Serialize
+{
+    annotate_with(auth, &Annotate::new(incident_id, facts))
+}
+
+/// Record structured facts about an incident, as described by `request`
+///
+/// This is [`annotate`](fn.annotate.html) for requests needing options such as `set_from`.
+pub fn annotate_with(auth: &AuthToken,
+                     request: &Annotate) -> request::Result<Response<Annotation>> {
+    annotate_using(auth, request, create_note, custom_fields::get_values, custom_fields::set_values)
+}
+
+type FieldValuesResult = request::Result<Response<Vec<CustomFieldValue>>>;
+
+/// `annotate_with`, making its requests through the given functions so they can be faked in tests
+fn annotate_using(auth: &AuthToken,
+                  request: &Annotate,
+                  create_note: fn(&AuthToken, &CreateNote) -> request::Result<Response<Note>>,
+                  get_values: fn(&AuthToken, &GetFieldValues) -> FieldValuesResult,
+                  set_values: fn(&AuthToken, &SetFieldValues) -> FieldValuesResult)
+                  -> request::Result<Response<Annotation>> {
+    let note = match create_note(auth, &request.note)?.into_result() {
+        Ok(note) => note,
+        Err(res) => return Ok(res),
+    };
+
+    let mut mismatched = Vec::new();
+    let values = GetFieldValues::new(&*request.note.incident_id);
+    let fields = match get_values(auth, &values) {
+        Ok(Response::Success(values)) => {
+            mismatched = request.mismatched_facts(&values);
+            match request.field_values(&values) {
+                Some(update) => match set_values(auth, &update) {
+                    Ok(res) => res.into_result().map_err(FieldsError::Response),
+                    Err(err) => Err(FieldsError::Request(err)),
+                },
+                None => Ok(Vec::new()),
+            }
+        },
+        Ok(res) => Err(FieldsError::Response(res.map(|_| ()))),
+        Err(err) => Err(FieldsError::Request(err)),
+    };
+
+    Ok(Response::Success(Annotation {
+        note,
+        fields,
+        mismatched,
+    }))
+}
+
+/// List the alerts of an incident
+pub fn list_alerts(auth: &AuthToken,
+                   request: &ListAlerts) -> request::Result<Response<Page<Alert>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single alert of an incident
+pub fn get_alert(auth: &AuthToken, request: &GetAlert) -> request::Result<Response<Alert>> {
+    request::perform(auth, request)
+}
+
+/// Resolve or move a single alert of an incident
+pub fn update_alert(auth: &AuthToken, request: &UpdateAlert) -> request::Result<Response<Alert>> {
+    request::perform(auth, request)
+}
+
+/// Resolve or move multiple alerts of an incident
+pub fn update_alerts(auth: &AuthToken,
+                     request: &UpdateAlerts) -> request::Result<Response<Vec<Alert>>> {
+    request::perform(auth, request)
+}
+
+/// List incidents similar to an incident, with their similarity scores
+pub fn list_past_incidents(auth: &AuthToken, request: &ListPastIncidents)
+    -> request::Result<Response<Vec<PastIncident>>>
+{
+    request::perform(auth, request)
+}
+
+/// List the status updates sent for an incident
+pub fn list_status_updates(auth: &AuthToken, request: &ListStatusUpdates)
+    -> request::Result<Response<Vec<StatusUpdate>>>
+{
+    request::perform(auth, request)
+}
+
+/// Gather everything recorded about incident `incident_id` into a
+/// [`PostmortemBundle`](struct.PostmortemBundle.html)
+///
+/// The incident, its alerts, log entries, notes and status updates are fetched in turn, and the
+/// first response other than a success is returned.
+///
+/// # Example
+///
+/// ```no_run
+/// extern crate pagerduty;
+/// extern crate serde_json;
+///
+/// use pagerduty::AuthToken;
+/// use pagerduty::rest::incidents;
+///
+/// # fn main() {
+/// let auth = AuthToken::new("token");
+/// let bundle = incidents::postmortem(&auth, "PT4KHLK").unwrap().success().unwrap();
+/// println!("{}", serde_json::to_string_pretty(&bundle).unwrap());
+/// # }
+/// ```
+pub fn postmortem(auth: &AuthToken,
+                  incident_id: &str) -> request::Result<Response<PostmortemBundle>> {
+    let incident = match get(auth, &GetIncident::new(incident_id))?.into_result() {
+        Ok(incident) => incident,
+        Err(res) => return Ok(res),
+    };
+
+    let mut alerts = Vec::new();
+    for res in ListAlerts::new(incident_id).paginate(auth) {
+        match res?.into_result() {
+            Ok(alert) => alerts.push(alert),
+            Err(res) => return Ok(res),
+        }
+    }
+
+    let mut log_entries = Vec::new();
+    let request = ListLogEntries::new().set_incident_id(incident_id).add_include("channels");
+    for res in request.paginate(auth) {
+        match res?.into_result() {
+            Ok(entry) => log_entries.push(entry),
+            Err(res) => return Ok(res),
+        }
+    }
+    log_entries.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let notes = match list_notes(auth, &ListNotes::new(incident_id))?.into_result() {
+        Ok(notes) => notes,
+        Err(res) => return Ok(res),
+    };
+
+    let request = ListStatusUpdates::new(incident_id);
+    let status_updates = match list_status_updates(auth, &request)?.into_result() {
+        Ok(status_updates) => status_updates,
+        Err(res) => return Ok(res),
+    };
+
+    Ok(Response::Success(PostmortemBundle {
+        incident,
+        alerts,
+        log_entries,
+        notes,
+        status_updates,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::method::Method;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, to_string, to_value, Value as Json};
+
+    use AuthToken;
+    use request::{self, Requestable};
+    use rest::{ListRequest, Page, Reference, Response};
+    use rest::custom_fields::{GetFieldValues, SetFieldValues};
+    use super::{annotate_using, AlertStatus, AlertUpdate, Annotate, CreateNote, FieldValuesResult,
+                FieldsError, GetAlert, GetIncident, IncidentStatus, IncidentUpdate, ListAlerts,
+                ListIncidents, ListNotes, ListPastIncidents, ListStatusUpdates, Note, Severity,
+                UpdateAlerts, UpdateIncident, Urgency};
+
+    #[test]
+    fn list_incidents_url() {
+        let request = ListIncidents::new()
+            .add_status(IncidentStatus::Triggered)
+            .add_status(IncidentStatus::Acknowledged)
+            .add_urgency(Urgency::High)
+            .add_service_id("PIJ90N7")
+            .set_since("2015-11-07T00:00:00Z")
+            .set_limit(25);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents?statuses%5B%5D=triggered&\
+                    statuses%5B%5D=acknowledged&urgencies%5B%5D=high&service_ids%5B%5D=PIJ90N7&\
+                    since=2015-11-07T00%3A00%3A00Z&limit=25");
+    }
+
+    #[test]
+    fn incident_from_json() {
+        let body = stringify!({
+            "incident": {
+                "id": "PT4KHLK",
+                "type": "incident",
+                "summary": "[#1234] The server is on fire.",
+                "self": "https://api.pagerduty.com/incidents/PT4KHLK",
+                "html_url": "https://subdomain.pagerduty.com/incidents/PT4KHLK",
+                "incident_number": 1234,
+                "title": "The server is on fire.",
+                "created_at": "2015-10-06T21:30:42Z",
+                "status": "acknowledged",
+                "incident_key": "baf7cf21b1da41b4b0db2524a15c1d50",
+                "service": { "id": "PIJ90N7", "type": "service_reference" },
+                "assignments": [
+                    {
+                        "at": "2015-11-10T00:31:52Z",
+                        "assignee": { "id": "PXPGF42", "type": "user_reference" }
+                    }
+                ],
+                "escalation_policy": { "id": "PT20YPA", "type": "escalation_policy_reference" },
+                "teams": [],
+                "priority": null,
+                "urgency": "high"
+            }
+        });
+
+        let res = GetIncident::get_response(StatusCode::Ok, &Headers::new(), body).unwrap();
+        let incident = res.success().unwrap();
+
+        assert_eq!(incident.incident_number, 1234);
+        assert_eq!(incident.status, IncidentStatus::Acknowledged);
+        assert_eq!(incident.urgency, Urgency::High);
+        assert_eq!(incident.service.id, "PIJ90N7");
+        assert_eq!(incident.assignments[0].assignee.id, "PXPGF42");
+        assert_eq!(incident.priority, None);
+
+        let json = to_value(&incident).unwrap();
+        assert_eq!(json["self"], "https://api.pagerduty.com/incidents/PT4KHLK");
+        assert_eq!(json["status"], "acknowledged");
+        assert_eq!(json["assignments"][0]["assignee"]["id"], "PXPGF42");
+    }
+
+    #[test]
+    fn status_updates_from_json() {
+        let body = stringify!({
+            "status_updates": [
+                {
+                    "id": "PWL7QXS",
+                    "message": "Payments are recovering after the failover.",
+                    "sender": { "id": "PXPGF42", "type": "user_reference" },
+                    "created_at": "2015-10-06T21:52:18Z"
+                }
+            ]
+        });
+
+        let res = ListStatusUpdates::get_response(StatusCode::Ok, &Headers::new(), body);
+        let updates = res.unwrap().success().unwrap();
+
+        assert_eq!(updates[0].message, "Payments are recovering after the failover.");
+        assert_eq!(updates[0].sender.as_ref().unwrap().id, "PXPGF42");
+        assert_eq!(updates[0].subject, None);
+        assert_eq!(ListStatusUpdates::new("PT4KHLK").url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/status_updates");
+    }
+
+    #[test]
+    fn create_note_to_json() {
+        let expected: Json = from_str(stringify!({
+            "note": {
+                "content": "Firefighters are on the scene."
+            }
+        })).expect("expected is valid json");
+
+        let content = String::from("Firefighters are on the scene.");
+        let request = CreateNote::new("PT4KHLK", content).set_from("user@example.com");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(), "https://api.pagerduty.com/incidents/PT4KHLK/notes");
+    }
+
+    #[test]
+    fn annotate_renders_facts() {
+        let facts: Json = from_str(r#"{"hosts": ["db01", "db02"], "region": "us-east-1"}"#)
+                              .unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+        assert_eq!(request.content(), "hosts: [\"db01\",\"db02\"]\nregion: us-east-1");
+
+        let request = Annotate::new("PT4KHLK", &["db01", "db02"]);
+        assert_eq!(request.content(), "[\"db01\",\"db02\"]");
+        assert!(request.field_values(&[]).is_none());
+    }
+
+    #[test]
+    fn annotate_stores_facts_in_custom_fields() {
+        let body = stringify!({
+            "custom_fields": [
+                {
+                    "id": "PT4KHLK",
+                    "name": "deploy",
+                    "data_type": "string",
+                    "field_type": "single_value",
+                    "value": null
+                },
+                {
+                    "id": "PXPGF42",
+                    "name": "rollback",
+                    "data_type": "boolean",
+                    "field_type": "single_value",
+                    "value": false
+                },
+                {
+                    "id": "PAM4FGS",
+                    "name": "region",
+                    "data_type": "string",
+                    "field_type": "single_value",
+                    "value": "us-east-1"
+                }
+            ]
+        });
+        let fields = GetFieldValues::get_response(StatusCode::Ok, &Headers::new(), body)
+            .unwrap()
+            .success()
+            .unwrap();
+
+        let facts: Json = from_str(r#"{"deploy": "v1.4.2", "rollback": true, "hosts": 3}"#)
+                              .unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+        let update = request.field_values(&fields).unwrap();
+
+        let expected: Json = from_str(stringify!({
+            "custom_fields": [
+                { "name": "deploy", "value": "v1.4.2" },
+                { "name": "rollback", "value": true }
+            ]
+        })).unwrap();
+        let actual: Json = from_str(&update.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(update.url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/custom_fields/values");
+
+        let facts: Json = from_str(r#"{"hosts": 3}"#).unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+        assert!(request.field_values(&fields).is_none());
+    }
+
+    #[test]
+    fn annotate_skips_mismatched_facts() {
+        let fields = GetFieldValues::get_response(StatusCode::Ok, &Headers::new(), FIELD_VALUES)
+            .unwrap()
+            .success()
+            .unwrap();
+
+        let facts: Json = from_str(r#"{"deploy": "v1.4.2", "rollback": "yes"}"#).unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+        let update = request.field_values(&fields).unwrap();
+
+        let expected: Json = from_str(stringify!({
+            "custom_fields": [
+                { "name": "deploy", "value": "v1.4.2" }
+            ]
+        })).unwrap();
+        let actual: Json = from_str(&update.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.mismatched_facts(&fields), vec!["rollback".to_owned()]);
+
+        let facts: Json = from_str(r#"{"rollback": 1}"#).unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+        assert!(request.field_values(&fields).is_none());
+    }
+
+    const FIELD_VALUES: &str = stringify!({
+        "custom_fields": [
+            {
+                "id": "PT4KHLK",
+                "name": "deploy",
+                "data_type": "string",
+                "field_type": "single_value",
+                "value": null
+            },
+            {
+                "id": "PXPGF42",
+                "name": "rollback",
+                "data_type": "boolean",
+                "field_type": "single_value",
+                "value": false
+            }
+        ]
+    });
+
+    fn fake_note(_auth: &AuthToken, request: &CreateNote) -> request::Result<Response<Note>> {
+        let body = format!(r#"{{"note": {{"id": "PWL7QXS",
+                                        "user": {{"id": "PT23IWX", "type": "user_reference"}},
+                                        "content": {},
+                                        "created_at": "2013-03-06T15:28:51-05:00"}}}}"#,
+                           to_string(&request.note.content).unwrap());
+        CreateNote::get_response(StatusCode::Created, &Headers::new(), &body)
+    }
+
+    fn fake_values(_auth: &AuthToken, _request: &GetFieldValues) -> FieldValuesResult {
+        GetFieldValues::get_response(StatusCode::Ok, &Headers::new(), FIELD_VALUES)
+    }
+
+    fn payment_required(_auth: &AuthToken, _request: &GetFieldValues) -> FieldValuesResult {
+        GetFieldValues::get_response(StatusCode::PaymentRequired, &Headers::new(), "")
+    }
+
+    fn forbidden(_auth: &AuthToken, _request: &GetFieldValues) -> FieldValuesResult {
+        GetFieldValues::get_response(StatusCode::Forbidden, &Headers::new(), "")
+    }
+
+    fn bad_request(_auth: &AuthToken, _request: &SetFieldValues) -> FieldValuesResult {
+        let body = r#"{"error": {"message": "Invalid Input Provided", "code": 2001}}"#;
+        SetFieldValues::get_response(StatusCode::BadRequest, &Headers::new(), body)
+    }
+
+    fn unreachable_set(_auth: &AuthToken, _request: &SetFieldValues) -> FieldValuesResult {
+        panic!("custom field values were stored without being read");
+    }
+
+    #[test]
+    fn annotate_adds_note_without_custom_fields() {
+        let auth = AuthToken::new("abc");
+        let facts: Json = from_str(r#"{"deploy": "v1.4.2"}"#).unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+
+        let res = annotate_using(&auth, &request, fake_note, payment_required, unreachable_set);
+        let annotation = res.unwrap().success().unwrap();
+        assert_eq!(annotation.note.content, "deploy: v1.4.2");
+        match annotation.fields {
+            Err(FieldsError::Request(request::Error::UnexpectedApiResponse)) => (),
+            other => panic!("unexpected fields: {:?}", other),
+        }
+
+        let res = annotate_using(&auth, &request, fake_note, forbidden, unreachable_set);
+        let annotation = res.unwrap().success().unwrap();
+        assert_eq!(annotation.note.content, "deploy: v1.4.2");
+        match annotation.fields {
+            Err(FieldsError::Response(Response::Forbidden)) => (),
+            other => panic!("unexpected fields: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn annotate_adds_note_when_custom_fields_are_rejected() {
+        let auth = AuthToken::new("abc");
+        let facts: Json = from_str(r#"{"deploy": "v1.4.2", "rollback": "no"}"#).unwrap();
+        let request = Annotate::new("PT4KHLK", &facts);
+
+        let res = annotate_using(&auth, &request, fake_note, fake_values, bad_request);
+        let annotation = res.unwrap().success().unwrap();
+        assert_eq!(annotation.note.content, "deploy: v1.4.2\nrollback: no");
+        assert_eq!(annotation.mismatched, vec!["rollback".to_owned()]);
+        match annotation.fields {
+            Err(FieldsError::Response(Response::BadRequest(ref err))) => {
+                assert_eq!(err.code, Some(2001));
+            },
+            other => panic!("unexpected fields: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn update_alerts_to_json() {
+        let expected: Json = from_str(stringify!({
+            "alerts": [
+                {
+                    "id": "PEYSGVF",
+                    "type": "alert",
+                    "status": "resolved"
+                },
+                {
+                    "id": "PXPGF42",
+                    "type": "alert",
+                    "incident": {
+                        "id": "PEYSGVA",
+                        "type": "incident_reference"
+                    }
+                }
+            ]
+        })).expect("expected is valid json");
+
+        let request = UpdateAlerts::new("PT4KHLK")
+                          .add_alert(AlertUpdate::new("PEYSGVF").set_status(AlertStatus::Resolved))
+                          .add_alert(AlertUpdate::new("PXPGF42").set_incident("PEYSGVA"));
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn update_incident_to_json() {
+        let expected: Json = from_str(stringify!({
+            "incident": {
+                "type": "incident_reference",
+                "priority": {
+                    "id": "PSO75BM",
+                    "type": "priority_reference"
+                }
+            }
+        })).expect("expected is valid json");
+
+        let update = IncidentUpdate::new()
+                         .set_priority(Reference::new("PSO75BM", "priority_reference"));
+        let request = UpdateIncident::new("PT4KHLK", update).set_from("ops@example.com");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(), "https://api.pagerduty.com/incidents/PT4KHLK");
+        assert_eq!(request.method(), Method::Put);
+    }
+
+    #[test]
+    fn notes_from_json() {
+        let body = stringify!({
+            "notes": [
+                {
+                    "id": "PWL7QXS",
+                    "user": {
+                        "id": "PXPGF42",
+                        "type": "user_reference",
+                        "summary": "Earline Greenholt"
+                    },
+                    "channel": {
+                        "summary": "The PagerDuty website or APIs"
+                    },
+                    "content": "Firefighters are on the scene.",
+                    "created_at": "2013-03-06T15:28:51-05:00"
+                }
+            ]
+        });
+
+        let res = ListNotes::get_response(StatusCode::Ok, &Headers::new(), body).unwrap();
+        let notes = res.success().unwrap();
+
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].id, "PWL7QXS");
+        assert_eq!(notes[0].user.id, "PXPGF42");
+        assert_eq!(notes[0].content, "Firefighters are on the scene.");
+    }
+
+    #[test]
+    fn list_alerts_url() {
+        let request = ListAlerts::new("PT4KHLK")
+                          .add_status(AlertStatus::Triggered)
+                          .set_limit(10);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/alerts?\
+                    statuses%5B%5D=triggered&limit=10");
+    }
+
+    #[test]
+    fn next_page_does_not_request_total() {
+        let request = ListAlerts::new("PT4KHLK").set_limit(1).set_total(true);
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/alerts?limit=1&total=true");
+
+        let page = Page { items: vec![()], limit: 1, offset: 0, more: true, total: Some(2) };
+        let request = request.next_page(&page).unwrap();
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/alerts?limit=1&offset=1");
+
+        let page = Page { items: vec![()], limit: 1, offset: 1, more: false, total: None };
+        assert!(request.next_page(&page).is_none());
+    }
+
+    #[test]
+    fn alert_from_json() {
+        let body = stringify!({
+            "alert": {
+                "id": "PT4KHLK",
+                "type": "alert",
+                "summary": "The server is on fire.",
+                "created_at": "2015-10-06T21:30:42Z",
+                "status": "resolved",
+                "alert_key": "baf7cf21b1da41b4b0221008339ff357",
+                "service": { "id": "PIJ90N7", "type": "service_reference" },
+                "incident": { "id": "PT4KHLK", "type": "incident_reference" },
+                "integration": { "id": "PQ12345", "type": "events_api_v2_inbound_integration" },
+                "suppressed": false,
+                "severity": "critical",
+                "body": {
+                    "type": "alert_body",
+                    "contexts": [{ "type": "link" }],
+                    "details": { "customKey": "Server is on fire!" }
+                }
+            }
+        });
+
+        let res = GetAlert::get_response(StatusCode::Ok, &Headers::new(), body).unwrap();
+        let alert = res.success().unwrap();
+
+        assert_eq!(alert.status, AlertStatus::Resolved);
+        assert_eq!(alert.severity, Some(Severity::Critical));
+        assert_eq!(alert.integration.unwrap().id, "PQ12345");
+        assert_eq!(alert.body.unwrap().contexts.len(), 1);
+    }
+
+    #[test]
+    fn past_incidents_from_json() {
+        let body = stringify!({
+            "past_incidents": [
+                {
+                    "incident": {
+                        "id": "P3PSIB3",
+                        "created_at": "2021-05-05T17:56:13Z",
+                        "self": "https://api.pagerduty.com/incidents/P3PSIB3",
+                        "title": "Server is on fire"
+                    },
+                    "score": 46.8249
+                }
+            ],
+            "limit": 5,
+            "total": 1
+        });
+
+        let res = ListPastIncidents::get_response(StatusCode::Ok, &Headers::new(), body).unwrap();
+        let past = res.success().unwrap();
+
+        assert_eq!(past.len(), 1);
+        assert_eq!(past[0].incident.id, "P3PSIB3");
+        assert_eq!(past[0].incident.title, "Server is on fire");
+        assert!(past[0].score > 46.0);
+    }
+}
@@ -0,0 +1,208 @@
+//! Licenses
+//!
+//! Licenses are the seats of an account. Each user is allocated a license, which determines the
+//! roles the user can be given.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_page, get_response, ListRequest, Page, Pagination, Query, Reference, Response,
+            BASE_URL};
+
+/// A kind of seat of the account
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct License {
+    /// The license's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the license
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The name of the license
+    pub name: String,
+
+    /// A description of the license
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The group of roles the license belongs to, such as `FullUser` or `Stakeholder`
+    #[serde(default)]
+    pub role_group: Option<String>,
+
+    /// The roles users allocated the license can be given
+    #[serde(default)]
+    pub valid_roles: Vec<String>,
+
+    /// The number of users allocated the license
+    #[serde(default)]
+    pub current_value: Option<u64>,
+
+    /// The number of seats still available, or `None` if the license isn't limited
+    #[serde(default)]
+    pub allocations_available: Option<u64>,
+}
+
+/// The allocation of a license to a user
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct LicenseAllocation {
+    /// The license allocated
+    pub license: License,
+
+    /// The user the license is allocated to
+    pub user: Reference,
+
+    /// The time at which the license was allocated
+    #[serde(default)]
+    pub allocated_at: Option<String>,
+}
+
+/// List the licenses of the account
+#[derive(Debug, Default)]
+pub struct ListLicenses;
+
+impl ListLicenses {
+    /// Create a request listing all licenses
+    pub fn new() -> ListLicenses {
+        ListLicenses
+    }
+}
+
+impl Requestable for ListLicenses {
+    type Response = Response<Vec<License>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/licenses", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<License>>> {
+        get_response(status, headers, body, "licenses")
+    }
+}
+
+/// List the licenses allocated to users
+#[derive(Debug, Default)]
+pub struct ListLicenseAllocations {
+    pagination: Pagination,
+}
+
+impl ListLicenseAllocations {
+    /// Create a request listing all license allocations
+    pub fn new() -> ListLicenseAllocations {
+        ListLicenseAllocations::default()
+    }
+}
+
+impl ListRequest for ListLicenseAllocations {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl Requestable for ListLicenseAllocations {
+    type Response = Response<Page<LicenseAllocation>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+
+        query.url("/license_allocations").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<LicenseAllocation>>> {
+        get_page(status, headers, body, "license_allocations")
+    }
+}
+
+/// List licenses
+pub fn list(auth: &AuthToken, request: &ListLicenses) -> request::Result<Response<Vec<License>>> {
+    request::perform(auth, request)
+}
+
+/// List license allocations
+pub fn list_allocations(auth: &AuthToken, request: &ListLicenseAllocations)
+    -> request::Result<Response<Page<LicenseAllocation>>>
+{
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use rest::ListRequest;
+    use super::ListLicenseAllocations;
+
+    #[test]
+    fn license_allocations_from_json() {
+        let request = ListLicenseAllocations::new().set_limit(1);
+        assert_eq!(request.url(), "https://api.pagerduty.com/license_allocations?limit=1");
+
+        let body = stringify!({
+            "license_allocations": [
+                {
+                    "license": {
+                        "id": "PIP248G",
+                        "type": "license",
+                        "name": "Business (Full User)",
+                        "description": "Business Plan",
+                        "role_group": "FullUser",
+                        "valid_roles": ["owner", "admin", "user", "limited_user"],
+                        "current_value": 234,
+                        "allocations_available": 4766
+                    },
+                    "user": { "id": "PXPGF42", "type": "user_reference" },
+                    "allocated_at": "2021-06-01T00:00:00-05:00"
+                }
+            ],
+            "limit": 1,
+            "offset": 0,
+            "total": null,
+            "more": true
+        });
+
+        let res = ListLicenseAllocations::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+        let allocation = &page.items[0];
+
+        assert_eq!(allocation.license.valid_roles.len(), 4);
+        assert_eq!(allocation.license.allocations_available, Some(4766));
+        assert_eq!(allocation.user.id, "PXPGF42");
+        assert!(page.more);
+    }
+}
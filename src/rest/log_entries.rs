@@ -0,0 +1,794 @@
+//! Log Entries
+//!
+//! A log entry records an event on an incident, such as it being triggered, a notification being
+//! sent to a responder, or the incident being acknowledged or resolved.
+//!
+//! # Auditing notifications
+//!
+//! Each attempt to reach a responder is recorded as a `notify_log_entry`. When log entries are
+//! requested with `include[]=channels`, their channels describe the contact method used and
+//! whether delivery succeeded. [`notification_attempts`](fn.notification_attempts.html) extracts
+//! these into [`NotificationAttempt`](struct.NotificationAttempt.html)s.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, Utc};
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::{to_string, Value as Json};
+
+use AuthToken;
+use request::{self, Requestable};
+use timestamp::Timestamp;
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// A record of an event on an incident
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct LogEntry {
+    /// The log entry's ID
+    pub id: String,
+
+    /// The kind of log entry, for example `trigger_log_entry` or `notify_log_entry`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// A short-form, server-generated string describing the log entry
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the log entry is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the log entry is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// Time at which the log entry was created
+    pub created_at: String,
+
+    /// The agent (user, service or integration) that caused the log entry
+    #[serde(default)]
+    pub agent: Option<Reference>,
+
+    /// Where the event described by the log entry came from
+    #[serde(default)]
+    pub channel: Option<Channel>,
+
+    /// The service of the incident the log entry belongs to
+    #[serde(default)]
+    pub service: Option<Reference>,
+
+    /// The incident the log entry belongs to
+    #[serde(default)]
+    pub incident: Option<Reference>,
+
+    /// Teams associated with the log entry
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// The user a notification was sent to, for `notify_log_entry` entries
+    #[serde(default)]
+    pub user: Option<Reference>,
+
+    /// Additional details about the event, when available
+    #[serde(default)]
+    pub event_details: Option<Json>,
+}
+
+#[cfg(feature = "chrono")]
+impl LogEntry {
+    /// Time at which the log entry was created, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// The origin of the event described by a log entry
+///
+/// The API discriminates channels with a `type` field; each variant carries the fields specific to
+/// that channel type. Channel types not known to this library deserialize as `Unknown`.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Channel {
+    /// An event sent through the integration API
+    Api(ApiChannel),
+
+    /// An email sent to an email integration
+    Email(EmailChannel),
+
+    /// An incident triggered from the web application
+    WebTrigger(WebTriggerChannel),
+
+    /// An event sent by Nagios
+    Nagios(NagiosChannel),
+
+    /// An SMS sent or received
+    Sms(NotificationChannel),
+
+    /// A phone call made or received
+    Phone(NotificationChannel),
+
+    /// A push notification sent to the mobile app
+    PushNotification(NotificationChannel),
+
+    /// The event was performed automatically by PagerDuty
+    Auto(BasicChannel),
+
+    /// The event was caused by a timeout, such as an acknowledgement expiring
+    Timeout(BasicChannel),
+
+    /// The event was performed through the website
+    Website(BasicChannel),
+
+    /// The event was caused by adding a note
+    Note(BasicChannel),
+
+    /// A channel type this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+impl Channel {
+    /// Get the channel's summary, if it has one
+    pub fn summary(&self) -> Option<&str> {
+        let summary = match *self {
+            Channel::Api(ref channel) => &channel.summary,
+            Channel::Email(ref channel) => &channel.summary,
+            Channel::WebTrigger(ref channel) => &channel.summary,
+            Channel::Nagios(ref channel) => &channel.summary,
+            Channel::Sms(ref channel) |
+            Channel::Phone(ref channel) |
+            Channel::PushNotification(ref channel) => &channel.summary,
+            Channel::Auto(ref channel) |
+            Channel::Timeout(ref channel) |
+            Channel::Website(ref channel) |
+            Channel::Note(ref channel) => &channel.summary,
+            Channel::Unknown => return None,
+        };
+
+        summary.as_ref().map(|s| &s[..])
+    }
+
+    /// Get the notification sent through this channel, if any
+    ///
+    /// Only present on channels of `notify_log_entry` entries requested with `include[]=channels`.
+    pub fn notification(&self) -> Option<&Notification> {
+        match *self {
+            Channel::Email(ref channel) => channel.notification.as_ref(),
+            Channel::Sms(ref channel) |
+            Channel::Phone(ref channel) |
+            Channel::PushNotification(ref channel) => channel.notification.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Channel fields for events sent through the integration API
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct ApiChannel {
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The integration key the event was sent to
+    #[serde(default)]
+    pub service_key: Option<String>,
+
+    /// The event's description
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The event's incident key
+    #[serde(default)]
+    pub incident_key: Option<String>,
+
+    /// The name of the monitoring client which sent the event
+    #[serde(default)]
+    pub client: Option<String>,
+
+    /// The URL of the monitoring client which sent the event
+    #[serde(default)]
+    pub client_url: Option<String>,
+
+    /// Arbitrary details included with the event
+    #[serde(default)]
+    pub details: Option<Json>,
+}
+
+/// Channel fields for emails sent to an email integration
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct EmailChannel {
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    #[serde(default)]
+    pub to: Option<String>,
+
+    #[serde(default)]
+    pub from: Option<String>,
+
+    #[serde(default)]
+    pub subject: Option<String>,
+
+    #[serde(default)]
+    pub body: Option<String>,
+
+    #[serde(default)]
+    pub body_content_type: Option<String>,
+
+    /// URL at which the raw email can be downloaded
+    #[serde(default)]
+    pub raw_url: Option<String>,
+
+    /// The notification email sent to a responder, for `notify_log_entry` entries
+    #[serde(default)]
+    pub notification: Option<Notification>,
+}
+
+/// Channel fields for incidents triggered from the web application
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct WebTriggerChannel {
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    #[serde(default)]
+    pub subject: Option<String>,
+
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+/// Channel fields for events sent by Nagios
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NagiosChannel {
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The host the check ran against
+    #[serde(default)]
+    pub host: Option<String>,
+
+    /// The Nagios service which changed state
+    #[serde(default)]
+    pub service: Option<String>,
+
+    /// The state reported by Nagios, for example `CRITICAL`
+    #[serde(default)]
+    pub state: Option<String>,
+
+    #[serde(default)]
+    pub details: Option<Json>,
+}
+
+/// Channel fields for contact methods used to notify responders
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NotificationChannel {
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The notification sent to a responder, for `notify_log_entry` entries
+    #[serde(default)]
+    pub notification: Option<Notification>,
+}
+
+/// Channel fields shared by channel types that carry no payload beyond a summary
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct BasicChannel {
+    #[serde(default)]
+    pub summary: Option<String>,
+}
+
+/// A notification sent to a responder
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Notification {
+    /// The kind of notification, for example `sms_notification`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The address the notification was sent to, such as a phone number or email address
+    #[serde(default)]
+    pub address: Option<String>,
+
+    /// The delivery status reported for the notification, for example `success`
+    #[serde(default)]
+    pub status: Option<String>,
+
+    /// Time at which the notification was sent
+    #[serde(default)]
+    pub started_at: Option<String>,
+}
+
+/// An attempt to notify a user, extracted from a `notify_log_entry`
+#[derive(Debug, PartialEq, Eq)]
+pub struct NotificationAttempt<'a> {
+    /// The user who was notified
+    pub user: &'a Reference,
+
+    /// The incident the user was notified about
+    pub incident: Option<&'a Reference>,
+
+    /// The notification which was sent
+    pub notification: &'a Notification,
+
+    /// Time at which the attempt was logged
+    pub created_at: &'a str,
+}
+
+impl<'a> NotificationAttempt<'a> {
+    /// Extract the notification attempt recorded by `entry`
+    ///
+    /// Returns `None` if the entry doesn't describe a notification, or if the entry was retrieved
+    /// without `include[]=channels`.
+    pub fn from_log_entry(entry: &'a LogEntry) -> Option<NotificationAttempt<'a>> {
+        let user = match entry.user {
+            Some(ref user) => user,
+            None => return None,
+        };
+
+        entry.channel
+            .as_ref()
+            .and_then(|channel| channel.notification())
+            .map(|notification| {
+                NotificationAttempt {
+                    user,
+                    incident: entry.incident.as_ref(),
+                    notification,
+                    created_at: &entry.created_at,
+                }
+            })
+    }
+
+    /// Whether PagerDuty reported the notification as delivered
+    pub fn succeeded(&self) -> bool {
+        self.notification.status.as_deref() == Some("success")
+    }
+}
+
+/// Extract all notification attempts from `entries`, in order
+pub fn notification_attempts<'a, I>(entries: I) -> Vec<NotificationAttempt<'a>>
+    where I: IntoIterator<Item = &'a LogEntry>
+{
+    entries.into_iter().filter_map(NotificationAttempt::from_log_entry).collect()
+}
+
+/// Extract all notification attempts from `entries`, grouped by the ID of the user notified
+pub fn notification_attempts_by_user<'a, I>(entries: I)
+    -> BTreeMap<&'a str, Vec<NotificationAttempt<'a>>>
+    where I: IntoIterator<Item = &'a LogEntry>
+{
+    let mut attempts = BTreeMap::new();
+    for attempt in notification_attempts(entries) {
+        attempts.entry(&attempt.user.id[..]).or_insert_with(Vec::new).push(attempt);
+    }
+
+    attempts
+}
+
+/// List log entries across the account, or of a single incident
+#[derive(Debug, Default)]
+pub struct ListLogEntries<'a> {
+    incident_id: Option<Cow<'a, str>>,
+    since: Option<Cow<'a, str>>,
+    until: Option<Cow<'a, str>>,
+    team_ids: Vec<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    is_overview: bool,
+    pagination: Pagination,
+}
+
+impl<'a> ListLogEntries<'a> {
+    /// Create a request listing all log entries
+    pub fn new() -> ListLogEntries<'a> {
+        ListLogEntries::default()
+    }
+
+    /// Only return the log entries of incident `incident_id`
+    ///
+    /// Team filters don't apply to the log entries of a single incident.
+    pub fn set_incident_id<S>(mut self, incident_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.incident_id = Some(incident_id.into());
+        self
+    }
+
+    /// Only return log entries created at or after `since`, in ISO 8601
+    pub fn set_since<S>(mut self, since: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.since = Some(since.into_timestamp());
+        self
+    }
+
+    /// Only return log entries created before `until`, in ISO 8601
+    pub fn set_until<S>(mut self, until: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.until = Some(until.into_timestamp());
+        self
+    }
+
+    /// Only return log entries of incidents belonging to team `team_id`; may be called more than
+    /// once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Include additional models in the response, such as `"channels"` or `"incidents"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+
+    /// Only return the most important changes to incidents, omitting entries such as
+    /// notifications
+    pub fn set_is_overview(mut self, is_overview: bool) -> Self {
+        self.is_overview = is_overview;
+        self
+    }
+}
+
+impl<'a> ListRequest for ListLogEntries<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListLogEntries<'a> {
+    type Response = Response<Page<LogEntry>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref since) = self.since {
+            query.push("since", since);
+        }
+        if let Some(ref until) = self.until {
+            query.push("until", until);
+        }
+        query.push_array("team_ids", &self.team_ids);
+        query.push_array("include", &self.include);
+        if self.is_overview {
+            query.push_bool("is_overview", true);
+        }
+        self.pagination.push_to(&mut query);
+
+        match self.incident_id {
+            Some(ref incident_id) => {
+                query.url(&format!("/incidents/{}/log_entries", segment(incident_id))).into()
+            },
+            None => query.url("/log_entries").into(),
+        }
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<LogEntry>>> {
+        get_page(status, headers, body, "log_entries")
+    }
+}
+
+/// Get details for a specific log entry
+#[derive(Debug)]
+pub struct GetLogEntry<'a> {
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetLogEntry<'a> {
+    /// Create a request for log entry `id`
+    pub fn new<S>(id: S) -> GetLogEntry<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetLogEntry {
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional details in the response
+    ///
+    /// Passing `"channels"` includes the full channel of the log entry, which is required to
+    /// inspect notification delivery.
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetLogEntry<'a> {
+    type Response = Response<LogEntry>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url(&format!("/log_entries/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<LogEntry>> {
+        get_response(status, headers, body, "log_entry")
+    }
+}
+
+/// Change the channel of a trigger log entry
+///
+/// This rewrites the summary shown for the event which triggered an incident, for example to
+/// replace noisy alert text with something more readable.
+#[derive(Debug, Serialize)]
+pub struct UpdateLogEntryChannel<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    channel: ChannelUpdate<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChannelUpdate<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    summary: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    details: Option<Cow<'a, str>>,
+}
+
+impl<'a> UpdateLogEntryChannel<'a> {
+    /// Create a request setting the channel summary of log entry `id` to `summary`
+    pub fn new<S>(id: S, summary: S) -> UpdateLogEntryChannel<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateLogEntryChannel {
+            id: id.into(),
+            from: None,
+            channel: ChannelUpdate {
+                kind: "web_trigger",
+                summary: summary.into(),
+                details: None,
+            },
+        }
+    }
+
+    /// Set the details of the channel
+    pub fn set_details<S>(mut self, details: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.channel.details = Some(details.into());
+        self
+    }
+
+    /// Set the email address of the user making the change
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for UpdateLogEntryChannel<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/log_entries/{}/channel", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List log entries
+pub fn list(auth: &AuthToken,
+            request: &ListLogEntries) -> request::Result<Response<Page<LogEntry>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single log entry
+pub fn get(auth: &AuthToken, request: &GetLogEntry) -> request::Result<Response<LogEntry>> {
+    request::perform(auth, request)
+}
+
+/// Change the channel of a trigger log entry
+pub fn update_channel(auth: &AuthToken,
+                      request: &UpdateLogEntryChannel) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::from_str;
+
+    use request::Requestable;
+    use rest::ListRequest;
+    use super::{notification_attempts_by_user, Channel, ListLogEntries, LogEntry,
+                UpdateLogEntryChannel};
+
+    #[test]
+    fn update_channel_body() {
+        let request = UpdateLogEntryChannel::new("Q02JTSNZWHSEKV", "Disk full on db-1")
+            .set_from("bot@example.com");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/log_entries/Q02JTSNZWHSEKV/channel");
+        assert_eq!(request.body(),
+                   r#"{"channel":{"type":"web_trigger","summary":"Disk full on db-1"}}"#);
+    }
+
+    #[test]
+    fn list_log_entries_url() {
+        let request = ListLogEntries::new()
+            .set_since("2015-11-07T00:00:00Z")
+            .add_team_id("PQ9K7I8")
+            .add_include("channels")
+            .set_is_overview(true)
+            .set_limit(100);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/log_entries?since=2015-11-07T00%3A00%3A00Z&\
+                    team_ids%5B%5D=PQ9K7I8&include%5B%5D=channels&is_overview=true&limit=100");
+
+        let request = ListLogEntries::new().set_incident_id("PT4KHLK").add_include("channels");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/incidents/PT4KHLK/log_entries?\
+                    include%5B%5D=channels");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn list_since_chrono_time() {
+        use chrono::{TimeZone, Utc};
+
+        let since = Utc.with_ymd_and_hms(2015, 11, 7, 0, 0, 0).unwrap();
+        let request = ListLogEntries::new().set_since(since);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/log_entries?since=2015-11-07T00%3A00%3A00%2B00%3A00");
+    }
+
+    #[test]
+    fn api_channel_from_json() {
+        let entry: LogEntry = from_str(stringify!({
+            "id": "Q02JTSNZWHSEKV",
+            "type": "trigger_log_entry",
+            "summary": "Triggered through the API",
+            "created_at": "2015-11-07T00:14:20Z",
+            "channel": {
+                "type": "api",
+                "summary": "Houston, we have a problem",
+                "service_key": "0123456789abcdef0123456789abcdef",
+                "description": "Houston, we have a problem",
+                "incident_key": "KEY123",
+                "details": { "ping time": "1500ms" }
+            }
+        })).unwrap();
+
+        match entry.channel {
+            Some(Channel::Api(ref channel)) => {
+                assert_eq!(channel.incident_key, Some("KEY123".to_owned()));
+                assert_eq!(channel.client, None);
+            },
+            ref other => panic!("expected api channel, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unknown_channel_type_from_json() {
+        let channel: Channel = from_str(stringify!({
+            "type": "carrier_pigeon",
+            "summary": "coo"
+        })).unwrap();
+
+        assert_eq!(channel, Channel::Unknown);
+        assert_eq!(channel.summary(), None);
+    }
+
+    #[test]
+    fn notification_attempts_from_json() {
+        let entries: Vec<LogEntry> = from_str(stringify!([
+            {
+                "id": "R2XGXEI3W0FHMRB0M8IXPGS8TW",
+                "type": "notify_log_entry",
+                "created_at": "2015-11-07T00:14:25Z",
+                "user": { "id": "PXPGF42", "type": "user_reference" },
+                "channel": {
+                    "type": "sms",
+                    "notification": {
+                        "type": "sms_notification",
+                        "address": "+15555550100",
+                        "status": "success"
+                    }
+                }
+            },
+            {
+                "id": "R3ZHQ9ANSHL6W5DL3VZ8NBY1CW",
+                "type": "notify_log_entry",
+                "created_at": "2015-11-07T00:15:25Z",
+                "user": { "id": "PXPGF42", "type": "user_reference" },
+                "channel": {
+                    "type": "phone",
+                    "notification": {
+                        "type": "phone_notification",
+                        "address": "+15555550100",
+                        "status": "no_answer"
+                    }
+                }
+            },
+            {
+                "id": "Q02JTSNZWHSEKV",
+                "type": "acknowledge_log_entry",
+                "created_at": "2015-11-07T00:16:00Z",
+                "channel": { "type": "website" }
+            }
+        ])).unwrap();
+
+        let attempts = notification_attempts_by_user(&entries);
+        let user_attempts = &attempts["PXPGF42"];
+
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(user_attempts.len(), 2);
+        assert!(user_attempts[0].succeeded());
+        assert!(!user_attempts[1].succeeded());
+        assert_eq!(user_attempts[1].notification.kind, "phone_notification");
+    }
+}
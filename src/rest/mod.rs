@@ -0,0 +1,824 @@
+//! REST API
+//!
+//! The PagerDuty REST API provides access to the objects in a PagerDuty account: incidents,
+//! services, users, schedules, and so on. Each submodule of `rest` covers one resource family and
+//! follows the same pattern as the integration API; a request type describing the call, and a free
+//! function which performs it.
+//!
+//! # Responses
+//!
+//! Every call returns a [`Response`](enum.Response.html). Successful calls yield the typed
+//! resource, and the common failure modes of the API are represented as variants rather than
+//! errors so callers can decide whether to retry.
+//!
+//! # Pagination
+//!
+//! List endpoints return a [`Page`](struct.Page.html) of results. The page size and position are
+//! controlled with the methods of the [`ListRequest`](trait.ListRequest.html) trait, which is
+//! implemented by every paginated request type.
+//!
+//! Computing the total number of results is expensive for PagerDuty, so it is only requested when
+//! asked for with `ListRequest::set_total`. Walking through results with `ListRequest::next_page`
+//! relies on the `more` flag alone and never requests the total after the first page.
+//! `ListRequest::paginate` does this walk with an iterator over the results of every page.
+//! `ListRequest::count` requests just the total, for callers which only need the number of results.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use pagerduty::AuthToken;
+//! use pagerduty::rest::{self, incidents};
+//!
+//! let token = AuthToken::new("token");
+//! let request = incidents::ListNotes::new("PT4KHLK");
+//!
+//! match incidents::list_notes(&token, &request).unwrap() {
+//!     rest::Response::Success(notes) => {
+//!         for note in notes {
+//!             println!("{}: {}", note.user.id, note.content);
+//!         }
+//!     },
+//!     other => println!("Failed to list notes: {:?}", other),
+//! }
+//! ```
+use std::time::Duration;
+
+use hyper::header::Headers;
+use hyper::status::StatusCode;
+
+use serde::de::DeserializeOwned;
+use serde_json::{from_str, from_value, Value as Json};
+
+use AuthToken;
+use request::{self, Requestable, ServerError};
+
+pub use self::paginate::Paginated;
+
+mod duration;
+mod paginate;
+
+pub mod analytics;
+pub mod audit;
+pub mod automation_actions;
+pub mod business_services;
+pub mod custom_fields;
+pub mod escalation_policies;
+pub mod event_orchestrations;
+pub mod extensions;
+pub mod incident_workflows;
+pub mod incidents;
+pub mod licenses;
+pub mod log_entries;
+pub mod notification_subscriptions;
+pub mod oncalls;
+pub mod priorities;
+pub mod response_plays;
+pub mod rulesets;
+pub mod schedules;
+pub mod services;
+pub mod status_dashboards;
+pub mod tags;
+pub mod teams;
+pub mod users;
+pub mod webhook_subscriptions;
+
+/// Base URL for all REST API requests
+pub const BASE_URL: &str = "https://api.pagerduty.com";
+
+/// Media type requesting version 2 of the REST API
+const ACCEPT: &str = "application/vnd.pagerduty+json;version=2";
+
+/// Headers sent with every REST API request
+///
+/// `from` is the email address of the user performing the action. It is required by several write
+/// endpoints when using an account-level API token.
+fn headers(from: Option<&str>) -> Headers {
+    let mut headers = Headers::new();
+    headers.set_raw("Accept", vec![ACCEPT.as_bytes().to_vec()]);
+
+    if let Some(from) = from {
+        headers.set_raw("From", vec![from.as_bytes().to_vec()]);
+    }
+
+    headers
+}
+
+/// Query string builder for request URLs
+///
+/// Values are percent-encoded as they are added. Array parameters such as `include[]` are added
+/// with `push_array`, which repeats the key once per value.
+///
+/// Every list request builds its URL with a `Query`. It's also useful for endpoints this library
+/// doesn't cover yet, along with [`WithHeaders`](../struct.WithHeaders.html) and a custom
+/// `Requestable`.
+///
+/// # Example
+///
+/// ```
+/// use pagerduty::rest::Query;
+///
+/// let mut query = Query::new();
+/// query.push("query", "db & cache");
+/// query.push_array("include", &["teams", "escalation_policies"]);
+/// query.push_bool("total", true);
+///
+/// assert_eq!(query.as_str(), "query=db%20%26%20cache&include%5B%5D=teams\
+///                             &include%5B%5D=escalation_policies&total=true");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query(String);
+
+impl Query {
+    /// Create an empty query string
+    pub fn new() -> Query {
+        Query::default()
+    }
+
+    /// Add a `key=value` pair
+    pub fn push<V>(&mut self, key: &str, value: V)
+        where V: AsRef<str>
+    {
+        if !self.0.is_empty() {
+            self.0.push('&');
+        }
+
+        encode(key, &mut self.0);
+        self.0.push('=');
+        encode(value.as_ref(), &mut self.0);
+    }
+
+    /// Add a `key=value` pair for each of `values`
+    pub fn push_all<I, V>(&mut self, key: &str, values: I)
+        where I: IntoIterator<Item = V>,
+              V: AsRef<str>
+    {
+        for value in values {
+            self.push(key, value);
+        }
+    }
+
+    /// Add array parameter `key[]` with each of `values`
+    pub fn push_array<I, V>(&mut self, key: &str, values: I)
+        where I: IntoIterator<Item = V>,
+              V: AsRef<str>
+    {
+        self.push_all(&format!("{}[]", key), values);
+    }
+
+    /// Add boolean parameter `key` as `true` or `false`
+    pub fn push_bool(&mut self, key: &str, value: bool) {
+        self.push(key, if value { "true" } else { "false" });
+    }
+
+    /// Whether no parameters have been added
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The encoded query string, without a leading `?`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Build the full REST API URL for `path` with this query string
+    pub fn url(&self, path: &str) -> String {
+        if self.0.is_empty() {
+            format!("{}{}", BASE_URL, path)
+        } else {
+            format!("{}{}?{}", BASE_URL, path, self.0)
+        }
+    }
+}
+
+/// Percent-encode `s` for use as a single segment of a URL path
+///
+/// IDs are supplied by callers, so a `/`, `?` or `#` in one must not change which resource a
+/// request is sent to.
+fn segment(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    encode(s, &mut out);
+    out
+}
+
+/// Percent-encode `s` for use in a query string or path, appending the result to `out`
+fn encode(s: &str, out: &mut String) {
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            },
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+}
+
+/// Pagination parameters for list endpoints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pagination {
+    /// The number of results per page
+    pub limit: Option<usize>,
+
+    /// Offset to start pagination search results
+    pub offset: Option<usize>,
+
+    /// Whether to compute the total number of results
+    pub total: bool,
+}
+
+impl Pagination {
+    fn push_to(&self, query: &mut Query) {
+        if let Some(limit) = self.limit {
+            query.push("limit", limit.to_string());
+        }
+
+        if let Some(offset) = self.offset {
+            query.push("offset", offset.to_string());
+        }
+
+        if self.total {
+            query.push_bool("total", true);
+        }
+    }
+}
+
+/// Requests for list endpoints which return a [`Page`](struct.Page.html) of results
+pub trait ListRequest: Requestable + Sized {
+    /// Access the pagination parameters of this request
+    fn pagination_mut(&mut self) -> &mut Pagination;
+
+    /// Set the number of results per page
+    ///
+    /// PagerDuty caps this at 100.
+    fn set_limit(mut self, limit: usize) -> Self {
+        self.pagination_mut().limit = Some(limit);
+        self
+    }
+
+    /// Set the offset of the first result
+    fn set_offset(mut self, offset: usize) -> Self {
+        self.pagination_mut().offset = Some(offset);
+        self
+    }
+
+    /// Request the total number of results in `Page::total`
+    ///
+    /// This is off by default since it makes the request considerably more expensive for
+    /// PagerDuty to serve.
+    fn set_total(mut self, total: bool) -> Self {
+        self.pagination_mut().total = total;
+        self
+    }
+
+    /// Turn this request into the request for the page following `page`
+    ///
+    /// Returns `None` when `page` was the last page. The total is never requested for subsequent
+    /// pages, even if it was requested for the first.
+    fn next_page<T>(mut self, page: &Page<T>) -> Option<Self> {
+        page.next_offset().map(|offset| {
+            let pagination = self.pagination_mut();
+            pagination.offset = Some(offset);
+            pagination.total = false;
+            self
+        })
+    }
+
+    /// Iterate over every result of this request, fetching pages with `auth` as they're needed
+    ///
+    /// See [`Paginated`](struct.Paginated.html).
+    fn paginate<'a, T>(self, auth: &'a AuthToken<'a>) -> Paginated<'a, Self, T>
+        where Self: Requestable<Response = Response<Page<T>>>
+    {
+        Paginated::new(auth, self)
+    }
+
+    /// Count the results of this request with `auth`, without fetching them
+    ///
+    /// A single result is fetched and the total is requested, so this is suited to dashboards
+    /// which only need numbers. The count is `None` when PagerDuty doesn't compute the total;
+    /// see [`Page::count`](struct.Page.html#method.count).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use pagerduty::AuthToken;
+    /// use pagerduty::rest::ListRequest;
+    /// use pagerduty::rest::incidents::{IncidentStatus, ListIncidents};
+    ///
+    /// let auth = AuthToken::new("token");
+    /// let open = ListIncidents::new()
+    ///     .add_service_id("PIJ90N7")
+    ///     .add_status(IncidentStatus::Triggered)
+    ///     .add_status(IncidentStatus::Acknowledged)
+    ///     .count(&auth)
+    ///     .unwrap();
+    /// ```
+    fn count<T>(self, auth: &AuthToken) -> request::Result<Response<Option<usize>>>
+        where Self: Requestable<Response = Response<Page<T>>>
+    {
+        let request = self.set_offset(0).set_limit(1).set_total(true);
+        request::perform(auth, &request).map(|res| res.map(|page| page.count()))
+    }
+}
+
+/// A page of results from a list endpoint
+#[derive(Debug, PartialEq, Eq)]
+pub struct Page<T> {
+    /// The results on this page
+    pub items: Vec<T>,
+
+    /// The number of results requested per page
+    pub limit: usize,
+
+    /// Offset of the first result on this page
+    pub offset: usize,
+
+    /// Whether more results are available after this page
+    pub more: bool,
+
+    /// The total number of results, if it was computed
+    pub total: Option<usize>,
+}
+
+impl<T> Page<T> {
+    /// The offset of the next page, or `None` if this is the last page
+    ///
+    /// A page without results is treated as the last page even if `more` is set, since the next
+    /// page would start at the same offset.
+    pub fn next_offset(&self) -> Option<usize> {
+        if self.more && !self.items.is_empty() {
+            Some(self.offset + self.items.len())
+        } else {
+            None
+        }
+    }
+
+    /// The total number of results, or `None` if it's unknown
+    ///
+    /// This is `total` when it was computed. Otherwise, the total is only known on the last page.
+    pub fn count(&self) -> Option<usize> {
+        match self.total {
+            Some(total) => Some(total),
+            None if !self.more => Some(self.offset + self.items.len()),
+            None => None,
+        }
+    }
+}
+
+/// The pagination fields of a list response
+#[derive(Deserialize)]
+struct PageInfo {
+    #[serde(default)]
+    limit: usize,
+
+    #[serde(default)]
+    offset: usize,
+
+    #[serde(default)]
+    more: bool,
+
+    #[serde(default)]
+    total: Option<usize>,
+}
+
+/// A reference to another object in the PagerDuty account
+///
+/// Most objects returned by the API refer to related objects this way rather than embedding them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Reference {
+    /// The object's ID
+    pub id: String,
+
+    /// The object's type, for example `user_reference`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// A short-form, server-generated string describing the object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    /// The API URL at which the object is accessible
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub self_url: Option<String>,
+
+    /// A URL at which the object is rendered in the web application
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html_url: Option<String>,
+}
+
+impl Reference {
+    /// Create a reference suitable for sending to the API
+    ///
+    /// Only `id` and `type` are required when referring to objects in request bodies.
+    pub fn new<S, T>(id: S, kind: T) -> Reference
+        where S: Into<String>,
+              T: Into<String>
+    {
+        Reference {
+            id: id.into(),
+            kind: kind.into(),
+            summary: None,
+            self_url: None,
+            html_url: None,
+        }
+    }
+}
+
+/// The ID of an object, as given by a user
+///
+/// People tend to copy whatever they have at hand to identify an object; the ID itself, the `self`
+/// URL from an API response, or the URL of the page showing it in the web application.
+/// `ResourceRef::parse` accepts any of these.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRef {
+    /// The collection the object belongs to, such as `users` or `services`, when known
+    pub collection: Option<String>,
+
+    /// The object's ID
+    pub id: String,
+}
+
+impl ResourceRef {
+    /// Parse a bare ID, an API URL, or a web application URL
+    ///
+    /// URLs of nested objects, such as a user's contact method, yield the innermost object. Web
+    /// URLs which identify the object in the fragment, like `/schedules#PI7DH85`, are understood.
+    /// Returns `None` if no ID can be found in `input`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use pagerduty::rest::ResourceRef;
+    /// let bare = ResourceRef::parse("PIJ90N7").unwrap();
+    /// let url = "https://acme.pagerduty.com/service-directory/PIJ90N7";
+    /// let web = ResourceRef::parse(url).unwrap();
+    ///
+    /// assert_eq!(bare.id, web.id);
+    /// assert_eq!(web.collection, Some("services".to_owned()));
+    /// ```
+    pub fn parse(input: &str) -> Option<ResourceRef> {
+        let input = input.trim();
+
+        let rest = match input.find("://") {
+            Some(index) => &input[index + 3..],
+            None => {
+                return if is_id(input) {
+                    Some(ResourceRef { collection: None, id: input.to_owned() })
+                } else {
+                    None
+                };
+            },
+        };
+
+        // Drop the host, then split off the query string and fragment
+        let path = rest.find('/').map(|index| &rest[index..]).unwrap_or("");
+        let (path, fragment) = match path.find('#') {
+            Some(index) => (&path[..index], &path[index + 1..]),
+            None => (path, ""),
+        };
+        let path = path.split('?').next().unwrap_or("");
+        let segments = path.split('/').filter(|s| !s.is_empty()).collect::<Vec<_>>();
+
+        if is_id(fragment) {
+            return Some(ResourceRef {
+                collection: segments.last().map(|c| collection_name(c)),
+                id: fragment.to_owned(),
+            });
+        }
+
+        segments.iter().enumerate().rev()
+            .find(|&(_, segment)| is_id(segment))
+            .map(|(index, id)| {
+                ResourceRef {
+                    collection: index.checked_sub(1).map(|i| collection_name(segments[i])),
+                    id: (*id).to_owned(),
+                }
+            })
+    }
+}
+
+/// Whether `s` looks like an object ID; IDs are made of uppercase letters and digits
+fn is_id(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+}
+
+/// Name of the API collection for a URL path segment of the web application
+fn collection_name(segment: &str) -> String {
+    match segment {
+        "service-directory" => "services".to_owned(),
+        other => other.replace('-', "_"),
+    }
+}
+
+/// Error details returned by the REST API when a request is rejected
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ApiError {
+    /// A description of the problem
+    pub message: String,
+
+    /// PagerDuty's numeric error code
+    #[serde(default)]
+    pub code: Option<u32>,
+
+    /// Specific error messages, typically describing invalid fields
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// A Response from the REST API
+///
+/// A union of the possible responses for a REST API call, where `T` is the resource returned on
+/// success.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Response<T> {
+    Success(T),
+    BadRequest(ApiError),
+    Unauthorized,
+    Forbidden,
+    NotFound,
+
+    /// The rate limit was exceeded; retry later, after `retry_after` if PagerDuty specified it
+    Throttled { retry_after: Option<Duration> },
+
+    InternalServerError(ServerError),
+}
+
+impl<T> Response<T> {
+    /// Transform the value contained in a `Success` response
+    pub fn map<U, F>(self, f: F) -> Response<U>
+        where F: FnOnce(T) -> U
+    {
+        match self.into_result() {
+            Ok(value) => Response::Success(f(value)),
+            Err(res) => res,
+        }
+    }
+
+    /// Split a `Success` response from the others, which are converted to a `Response<U>`
+    ///
+    /// This is useful when a call made of several requests returns the first one that failed.
+    pub fn into_result<U>(self) -> ::std::result::Result<T, Response<U>> {
+        match self {
+            Response::Success(value) => Ok(value),
+            Response::BadRequest(err) => Err(Response::BadRequest(err)),
+            Response::Unauthorized => Err(Response::Unauthorized),
+            Response::Forbidden => Err(Response::Forbidden),
+            Response::NotFound => Err(Response::NotFound),
+            Response::Throttled { retry_after } => {
+                Err(Response::Throttled { retry_after })
+            },
+            Response::InternalServerError(err) => Err(Response::InternalServerError(err)),
+        }
+    }
+
+    /// Get the success value, if any
+    pub fn success(self) -> Option<T> {
+        match self {
+            Response::Success(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Wraps the error object in REST API error responses
+#[derive(Deserialize)]
+struct ErrorBody {
+    error: ApiError,
+}
+
+/// Classify a non-successful response
+fn failure<T>(status: StatusCode, headers: &Headers, body: &str) -> request::Result<Response<T>> {
+    // Server errors may come with an HTML body; classify them before any parsing happens.
+    if status.is_server_error() {
+        return Ok(Response::InternalServerError(ServerError::new(status, body)));
+    }
+
+    match status {
+        StatusCode::BadRequest => {
+            let res: ErrorBody = from_str(body)?;
+            Ok(Response::BadRequest(res.error))
+        },
+        StatusCode::Unauthorized => Ok(Response::Unauthorized),
+        StatusCode::Forbidden => Ok(Response::Forbidden),
+        StatusCode::NotFound => Ok(Response::NotFound),
+        StatusCode::TooManyRequests => {
+            Ok(Response::Throttled { retry_after: request::retry_after(headers) })
+        },
+        _ => Err(request::Error::UnexpectedApiResponse),
+    }
+}
+
+/// Parse a response whose body wraps the resource in an object under `key`
+///
+/// For example, `GET /incidents/{id}/notes` returns `{"notes": [...]}`, so `key` would be
+/// `"notes"`.
+fn get_response<T>(status: StatusCode,
+                   headers: &Headers,
+                   body: &str,
+                   key: &str) -> request::Result<Response<T>>
+    where T: DeserializeOwned
+{
+    if !status.is_success() {
+        return failure(status, headers, body);
+    }
+
+    let mut res: Json = from_str(body)?;
+    let value = match res.as_object_mut().and_then(|object| object.remove(key)) {
+        Some(value) => value,
+        None => return Err(request::Error::UnexpectedApiResponse),
+    };
+
+    Ok(Response::Success(from_value(value)?))
+}
+
+/// Parse a response whose body is the resource itself, rather than an object wrapping it
+fn get_body_response<T>(status: StatusCode,
+                        headers: &Headers,
+                        body: &str) -> request::Result<Response<T>>
+    where T: DeserializeOwned
+{
+    if !status.is_success() {
+        return failure(status, headers, body);
+    }
+
+    Ok(Response::Success(from_str(body)?))
+}
+
+/// Parse a response which has no body on success, such as the response to a `DELETE`
+fn get_empty_response(status: StatusCode,
+                      headers: &Headers,
+                      body: &str) -> request::Result<Response<()>> {
+    if status.is_success() {
+        Ok(Response::Success(()))
+    } else {
+        failure(status, headers, body)
+    }
+}
+
+/// Parse a list response whose results are stored under `key`
+fn get_page<T>(status: StatusCode,
+               headers: &Headers,
+               body: &str,
+               key: &str) -> request::Result<Response<Page<T>>>
+    where T: DeserializeOwned
+{
+    if !status.is_success() {
+        return failure(status, headers, body);
+    }
+
+    let mut res: Json = from_str(body)?;
+    let items = match res.as_object_mut().and_then(|object| object.remove(key)) {
+        Some(items) => items,
+        None => return Err(request::Error::UnexpectedApiResponse),
+    };
+
+    let info: PageInfo = from_value(res)?;
+
+    Ok(Response::Success(Page {
+        items: from_value(items)?,
+        limit: info.limit,
+        offset: info.offset,
+        more: info.more,
+        total: info.total,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use super::{get_page, get_response, segment, ApiError, Page, Query, Reference, ResourceRef,
+                Response};
+
+    #[test]
+    fn resource_refs_are_parsed() {
+        let parse = |input| {
+            ResourceRef::parse(input).map(|r| (r.collection.unwrap_or_default(), r.id))
+        };
+        let expected = |collection: &str, id: &str| Some((collection.to_owned(), id.to_owned()));
+
+        assert_eq!(parse(" PXPGF42 "), expected("", "PXPGF42"));
+        assert_eq!(parse("https://api.pagerduty.com/users/PXPGF42"), expected("users", "PXPGF42"));
+        assert_eq!(parse("https://api.pagerduty.com/users/PXPGF42/contact_methods/PTDVERC"),
+                   expected("contact_methods", "PTDVERC"));
+        assert_eq!(parse("https://acme.pagerduty.com/incidents/Q0RIJJZL24RC6W?tab=notes"),
+                   expected("incidents", "Q0RIJJZL24RC6W"));
+        assert_eq!(parse("https://acme.pagerduty.com/schedules#PI7DH85"),
+                   expected("schedules", "PI7DH85"));
+        assert_eq!(parse("https://acme.pagerduty.com/escalation-policies/PANZZEQ"),
+                   expected("escalation_policies", "PANZZEQ"));
+        assert_eq!(parse("not an id"), None);
+        assert_eq!(parse("https://acme.pagerduty.com/users"), None);
+    }
+
+    #[test]
+    fn query_values_are_encoded() {
+        let mut query = Query::default();
+        query.push("query", "db & cache");
+        query.push_array("include", ["channels", "services"]);
+
+        assert_eq!(query.url("/log_entries"),
+                   "https://api.pagerduty.com/log_entries?query=db%20%26%20cache&\
+                    include%5B%5D=channels&include%5B%5D=services");
+    }
+
+    #[test]
+    fn path_segments_are_encoded() {
+        assert_eq!(segment("PXPGF42"), "PXPGF42");
+        assert_eq!(segment("../users/P?x#y"), "..%2Fusers%2FP%3Fx%23y");
+    }
+
+    #[test]
+    fn wrapped_resource_is_extracted() {
+        let body = stringify!({
+            "user": {
+                "id": "PXPGF42",
+                "type": "user_reference",
+                "summary": "Earline Greenholt"
+            }
+        });
+
+        let res: Response<Reference> =
+            get_response(StatusCode::Ok, &Headers::new(), body, "user").unwrap();
+        let user = res.success().unwrap();
+
+        assert_eq!(user.id, "PXPGF42");
+        assert_eq!(user.kind, "user_reference");
+        assert_eq!(user.summary, Some("Earline Greenholt".to_owned()));
+    }
+
+    #[test]
+    fn page_is_parsed() {
+        let body = stringify!({
+            "users": [
+                { "id": "PXPGF42", "type": "user_reference" },
+                { "id": "PAM4FGS", "type": "user_reference" }
+            ],
+            "limit": 2,
+            "offset": 0,
+            "more": true,
+            "total": null
+        });
+
+        let res = get_page::<Reference>(StatusCode::Ok, &Headers::new(), body, "users").unwrap();
+        let page = res.success().unwrap();
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[1].id, "PAM4FGS");
+        assert_eq!(page.limit, 2);
+        assert!(page.more);
+        assert_eq!(page.total, None);
+    }
+
+    #[test]
+    fn page_count() {
+        let page = |items: Vec<u8>, offset, more, total| {
+            Page { items, limit: 2, offset, more, total }
+        };
+
+        assert_eq!(page(vec![1], 0, true, Some(42)).count(), Some(42));
+        assert_eq!(page(vec![1, 2], 0, true, None).count(), None);
+        assert_eq!(page(vec![5], 4, false, None).count(), Some(5));
+        assert_eq!(page(vec![], 0, false, None).count(), Some(0));
+    }
+
+    #[test]
+    fn bad_request_error_is_parsed() {
+        let body = stringify!({
+            "error": {
+                "message": "Invalid Input Provided",
+                "code": 2001,
+                "errors": ["Content cannot be empty."]
+            }
+        });
+
+        let res: Response<Reference> =
+            get_response(StatusCode::BadRequest, &Headers::new(), body, "note").unwrap();
+        let expected = ApiError {
+            message: "Invalid Input Provided".to_owned(),
+            code: Some(2001),
+            errors: vec!["Content cannot be empty.".to_owned()],
+        };
+
+        assert_eq!(res, Response::BadRequest(expected));
+    }
+
+    #[test]
+    fn not_found_does_not_parse_body() {
+        let res: Response<Reference> =
+            get_response(StatusCode::NotFound, &Headers::new(), "", "note").unwrap();
+        assert_eq!(res, Response::NotFound);
+    }
+
+    #[test]
+    fn too_many_requests_is_throttled() {
+        let mut headers = Headers::new();
+        headers.set_raw("Retry-After", vec![b"5".to_vec()]);
+
+        let res: Response<Reference> =
+            get_response(StatusCode::TooManyRequests, &headers, "", "note").unwrap();
+        assert_eq!(res, Response::Throttled { retry_after: Some(Duration::from_secs(5)) });
+    }
+}
@@ -0,0 +1,348 @@
+//! Notification Subscriptions
+//!
+//! Users and teams can subscribe to business services to be notified of status updates on
+//! incidents impacting them, without being responders themselves.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_body_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Response, BASE_URL};
+
+/// A user or team which can subscribe to notifications
+#[derive(Debug, Clone)]
+pub enum Subscriber<'a> {
+    User(Cow<'a, str>),
+    Team(Cow<'a, str>),
+}
+
+impl<'a> Subscriber<'a> {
+    /// User `id`
+    pub fn user<S>(id: S) -> Subscriber<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Subscriber::User(id.into())
+    }
+
+    /// Team `id`
+    pub fn team<S>(id: S) -> Subscriber<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Subscriber::Team(id.into())
+    }
+
+    /// The `subscriber_type` of the subscriber in the API
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            Subscriber::User(_) => "user",
+            Subscriber::Team(_) => "team",
+        }
+    }
+
+    /// The subscriber's ID
+    pub fn id(&self) -> &str {
+        match *self {
+            Subscriber::User(ref id) | Subscriber::Team(ref id) => id,
+        }
+    }
+
+    fn path(&self) -> String {
+        format!("/{}s/{}/notification_subscriptions", self.kind(), segment(self.id()))
+    }
+}
+
+/// A subscription of a user or team to notifications about a business service
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Subscription {
+    /// The ID of the subscribed user or team
+    pub subscriber_id: String,
+
+    /// Either `user` or `team`
+    pub subscriber_type: String,
+
+    /// The ID of the entity subscribed to
+    pub subscribable_id: String,
+
+    /// The type of the entity subscribed to, such as `business_service`
+    pub subscribable_type: String,
+
+    /// The ID of the account the subscription belongs to
+    #[serde(default)]
+    pub account_id: Option<String>,
+
+    /// When subscribing, whether the subscription was created; `success`, `duplicate`, or
+    /// `unauthorized`
+    #[serde(default)]
+    pub result: Option<String>,
+}
+
+/// The outcome of unsubscribing
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct UnsubscribeResult {
+    /// The number of subscriptions removed
+    pub deleted_count: u64,
+
+    /// The number of subscriptions which couldn't be removed due to insufficient permissions
+    pub unauthorized_count: u64,
+
+    /// The number of subscriptions which didn't exist
+    pub non_existent_count: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Subscribable<'a> {
+    subscribable_id: Cow<'a, str>,
+    subscribable_type: &'static str,
+}
+
+/// List the notification subscriptions of a user or team
+#[derive(Debug)]
+pub struct ListSubscriptions<'a> {
+    subscriber: Subscriber<'a>,
+    pagination: Pagination,
+}
+
+impl<'a> ListSubscriptions<'a> {
+    /// Create a request listing the subscriptions of `subscriber`
+    pub fn new(subscriber: Subscriber<'a>) -> ListSubscriptions<'a> {
+        ListSubscriptions {
+            subscriber,
+            pagination: Pagination::default(),
+        }
+    }
+}
+
+impl<'a> ListRequest for ListSubscriptions<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListSubscriptions<'a> {
+    type Response = Response<Page<Subscription>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url(&self.subscriber.path()).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Subscription>>> {
+        get_page(status, headers, body, "subscriptions")
+    }
+}
+
+/// Subscribe a user or team to notifications about business services
+#[derive(Debug, Serialize)]
+pub struct Subscribe<'a> {
+    #[serde(skip_serializing)]
+    subscriber: Subscriber<'a>,
+
+    subscribables: Vec<Subscribable<'a>>,
+}
+
+impl<'a> Subscribe<'a> {
+    /// Create a request subscribing `subscriber`
+    pub fn new(subscriber: Subscriber<'a>) -> Subscribe<'a> {
+        Subscribe {
+            subscriber,
+            subscribables: Vec::new(),
+        }
+    }
+
+    /// Subscribe to business service `id`; may be called more than once
+    pub fn add_business_service<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.subscribables.push(Subscribable {
+            subscribable_id: id.into(),
+            subscribable_type: "business_service",
+        });
+        self
+    }
+}
+
+impl<'a> Requestable for Subscribe<'a> {
+    type Response = Response<Vec<Subscription>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}{}", BASE_URL, self.subscriber.path()).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<Subscription>>> {
+        get_response(status, headers, body, "subscriptions")
+    }
+}
+
+/// Unsubscribe a user or team from notifications about business services
+#[derive(Debug, Serialize)]
+pub struct Unsubscribe<'a> {
+    #[serde(skip_serializing)]
+    subscriber: Subscriber<'a>,
+
+    subscribables: Vec<Subscribable<'a>>,
+}
+
+impl<'a> Unsubscribe<'a> {
+    /// Create a request unsubscribing `subscriber`
+    pub fn new(subscriber: Subscriber<'a>) -> Unsubscribe<'a> {
+        Unsubscribe {
+            subscriber,
+            subscribables: Vec::new(),
+        }
+    }
+
+    /// Unsubscribe from business service `id`; may be called more than once
+    pub fn add_business_service<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.subscribables.push(Subscribable {
+            subscribable_id: id.into(),
+            subscribable_type: "business_service",
+        });
+        self
+    }
+}
+
+impl<'a> Requestable for Unsubscribe<'a> {
+    type Response = Response<UnsubscribeResult>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}{}/unsubscribe", BASE_URL, self.subscriber.path()).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<UnsubscribeResult>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// List notification subscriptions
+pub fn list(auth: &AuthToken,
+            request: &ListSubscriptions) -> request::Result<Response<Page<Subscription>>> {
+    request::perform(auth, request)
+}
+
+/// Subscribe to notifications
+pub fn subscribe(auth: &AuthToken,
+                 request: &Subscribe) -> request::Result<Response<Vec<Subscription>>> {
+    request::perform(auth, request)
+}
+
+/// Unsubscribe from notifications
+pub fn unsubscribe(auth: &AuthToken,
+                   request: &Unsubscribe) -> request::Result<Response<UnsubscribeResult>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{Subscribe, Subscriber, Unsubscribe};
+
+    #[test]
+    fn subscribe_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "subscribables": [
+                { "subscribable_id": "PD1234", "subscribable_type": "business_service" }
+            ]
+        })).expect("expected is valid json");
+
+        let request = Subscribe::new(Subscriber::team("PQ9K7I8")).add_business_service("PD1234");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/teams/PQ9K7I8/notification_subscriptions");
+
+        let body = stringify!({
+            "subscriptions": [
+                {
+                    "account_id": "PD1234",
+                    "subscribable_id": "PD1234",
+                    "subscribable_type": "business_service",
+                    "subscriber_id": "PQ9K7I8",
+                    "subscriber_type": "team",
+                    "result": "success"
+                }
+            ]
+        });
+
+        let res = Subscribe::get_response(StatusCode::Ok, &Headers::new(), body);
+        let subscriptions = res.unwrap().success().unwrap();
+        assert_eq!(subscriptions[0].result, Some("success".to_owned()));
+    }
+
+    #[test]
+    fn unsubscribe_result() {
+        let request = Unsubscribe::new(Subscriber::user("PXPGF42"));
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/users/PXPGF42/notification_subscriptions/\
+                    unsubscribe");
+
+        let request = Unsubscribe::new(Subscriber::user("../teams/PQ9K7I8"));
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/users/..%2Fteams%2FPQ9K7I8/\
+                    notification_subscriptions/unsubscribe");
+
+        let body = r#"{"deleted_count": 1, "unauthorized_count": 0, "non_existent_count": 2}"#;
+        let res = Unsubscribe::get_response(StatusCode::Ok, &Headers::new(), body);
+        let result = res.unwrap().success().unwrap();
+
+        assert_eq!(result.deleted_count, 1);
+        assert_eq!(result.non_existent_count, 2);
+    }
+}
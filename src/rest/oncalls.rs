@@ -0,0 +1,377 @@
+//! On-calls
+//!
+//! An on-call is a user being on call at a level of an escalation policy for a span of time,
+//! either directly or through one of the level's schedules. [`preview`](fn.preview.html) uses them
+//! to tell who an event would notify before it's sent.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, Utc};
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use AuthToken;
+use request::{self, Requestable};
+use timestamp::Timestamp;
+use super::{get_page, ListRequest, Page, Pagination, Query, Reference, Response};
+use super::services::{ListServices, Service};
+
+/// A user on call at a level of an escalation policy
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct OnCall {
+    /// The user on call
+    pub user: Reference,
+
+    /// The schedule the user is on call through, or `None` if they're a target of the level
+    #[serde(default)]
+    pub schedule: Option<Reference>,
+
+    /// The escalation policy the user is on call for
+    pub escalation_policy: Reference,
+
+    /// The level of the escalation policy, starting at 1
+    pub escalation_level: u32,
+
+    /// The time at which the on-call starts, or `None` if the user is always on call
+    #[serde(default)]
+    pub start: Option<String>,
+
+    /// The time at which the on-call ends, or `None` if the user is always on call
+    #[serde(default)]
+    pub end: Option<String>,
+}
+
+#[cfg(feature = "chrono")]
+impl OnCall {
+    /// The time at which the on-call starts, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn start_utc(&self) -> Option<Result<DateTime<Utc>, ParseError>> {
+        self.start.as_ref().map(|start| ::timestamp::parse(start))
+    }
+
+    /// The time at which the on-call ends, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn end_utc(&self) -> Option<Result<DateTime<Utc>, ParseError>> {
+        self.end.as_ref().map(|end| ::timestamp::parse(end))
+    }
+}
+
+/// List on-calls, optionally filtered
+///
+/// Without a date range, only the current on-calls are returned.
+#[derive(Debug, Default)]
+pub struct ListOnCalls<'a> {
+    escalation_policy_ids: Vec<Cow<'a, str>>,
+    schedule_ids: Vec<Cow<'a, str>>,
+    user_ids: Vec<Cow<'a, str>>,
+    since: Option<Cow<'a, str>>,
+    until: Option<Cow<'a, str>>,
+    earliest: bool,
+    include: Vec<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListOnCalls<'a> {
+    /// Create a request listing all current on-calls
+    pub fn new() -> ListOnCalls<'a> {
+        ListOnCalls::default()
+    }
+
+    /// Only return on-calls for escalation policy `escalation_policy_id`; may be called more than
+    /// once
+    pub fn add_escalation_policy_id<S>(mut self, escalation_policy_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.escalation_policy_ids.push(escalation_policy_id.into());
+        self
+    }
+
+    /// Only return on-calls through schedule `schedule_id`; may be called more than once
+    pub fn add_schedule_id<S>(mut self, schedule_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.schedule_ids.push(schedule_id.into());
+        self
+    }
+
+    /// Only return on-calls of user `user_id`; may be called more than once
+    pub fn add_user_id<S>(mut self, user_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.user_ids.push(user_id.into());
+        self
+    }
+
+    /// Only return on-calls ending after `since`, in ISO 8601
+    pub fn set_since<S>(mut self, since: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.since = Some(since.into_timestamp());
+        self
+    }
+
+    /// Only return on-calls starting before `until`, in ISO 8601
+    pub fn set_until<S>(mut self, until: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.until = Some(until.into_timestamp());
+        self
+    }
+
+    /// Only return the earliest on-call of each combination of user, escalation policy and level
+    pub fn set_earliest(mut self, earliest: bool) -> Self {
+        self.earliest = earliest;
+        self
+    }
+
+    /// Include additional details in the response, for example `"users"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListOnCalls<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListOnCalls<'a> {
+    type Response = Response<Page<OnCall>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("escalation_policy_ids", &self.escalation_policy_ids);
+        query.push_array("schedule_ids", &self.schedule_ids);
+        query.push_array("user_ids", &self.user_ids);
+        if let Some(ref since) = self.since {
+            query.push("since", since);
+        }
+        if let Some(ref until) = self.until {
+            query.push("until", until);
+        }
+        if self.earliest {
+            query.push_bool("earliest", true);
+        }
+        query.push_array("include", &self.include);
+        self.pagination.push_to(&mut query);
+
+        query.url("/oncalls").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<OnCall>>> {
+        get_page(status, headers, body, "oncalls")
+    }
+}
+
+/// Who an event sent with a service key would notify first
+#[derive(Debug)]
+pub struct Preview {
+    /// The service receiving events sent with the key
+    pub service: Service,
+
+    /// The users on call at the first level of the service's escalation policy
+    pub on_call: Vec<OnCall>,
+}
+
+impl Preview {
+    /// Whether nobody is on call at the first level, so an incident would notify no one
+    ///
+    /// This happens when, for example, the first level only targets a schedule with a gap.
+    pub fn is_empty(&self) -> bool {
+        self.on_call.is_empty()
+    }
+}
+
+/// List on-calls
+pub fn list(auth: &AuthToken, request: &ListOnCalls) -> request::Result<Response<Page<OnCall>>> {
+    request::perform(auth, request)
+}
+
+/// Preview who would be notified first of an event sent with `service_key` right now
+///
+/// `service_key` is the integration key events are sent with, which is also the routing key of
+/// the Events API v2. The service with an integration using the key is looked up, along with the
+/// users currently on call at the first level of its escalation policy. `None` is returned if no
+/// integration uses the key.
+///
+/// Finding the service lists every service in the account, so this is best done once, before
+/// events are sent, rather than for every event.
+///
+/// # Example
+///
+/// ```no_run
+/// use pagerduty::AuthToken;
+/// use pagerduty::rest::{oncalls, Response};
+///
+/// let auth = AuthToken::new("token");
+///
+/// match oncalls::preview(&auth, "f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b").unwrap() {
+///     Response::Success(Some(ref preview)) if preview.is_empty() => {
+///         println!("Nobody is on call for {}", preview.service.name);
+///     },
+///     Response::Success(Some(preview)) => {
+///         for on_call in preview.on_call {
+///             println!("{:?} would be paged", on_call.user.summary);
+///         }
+///     },
+///     Response::Success(None) => println!("No service uses this key"),
+///     other => println!("Failed to preview: {:?}", other),
+/// }
+/// ```
+pub fn preview(auth: &AuthToken, service_key: &str) -> request::Result<Response<Option<Preview>>> {
+    let services = ListServices::new().add_include("integrations");
+
+    let mut found = None;
+    for res in services.paginate(auth) {
+        match res?.into_result() {
+            Ok(service) => {
+                if uses_key(&service, service_key) {
+                    found = Some(service);
+                    break;
+                }
+            },
+            Err(res) => return Ok(res),
+        }
+    }
+
+    let service = match found {
+        Some(service) => service,
+        None => return Ok(Response::Success(None)),
+    };
+
+    let mut on_call = Vec::new();
+    let request = ListOnCalls::new().add_escalation_policy_id(&*service.escalation_policy.id);
+    for res in request.paginate(auth) {
+        match res?.into_result() {
+            Ok(entry) => {
+                if entry.escalation_level == 1 {
+                    on_call.push(entry);
+                }
+            },
+            Err(res) => return Ok(res),
+        }
+    }
+
+    Ok(Response::Success(Some(Preview { service, on_call })))
+}
+
+/// Whether one of the integrations of `service` receives events sent with `service_key`
+fn uses_key(service: &Service, service_key: &str) -> bool {
+    service.integrations
+        .iter()
+        .any(|integration| integration.integration_key.as_ref().map(|key| &key[..]) ==
+                           Some(service_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use rest::services::ListServices;
+    use super::{uses_key, ListOnCalls};
+
+    #[test]
+    fn list_on_calls_url() {
+        let request = ListOnCalls::new()
+                          .add_escalation_policy_id("PT20YPA")
+                          .set_since("2015-11-07T00:00:00Z")
+                          .set_earliest(true);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/oncalls?escalation_policy_ids%5B%5D=PT20YPA&\
+                    since=2015-11-07T00%3A00%3A00Z&earliest=true");
+    }
+
+    #[test]
+    fn on_calls_from_json() {
+        let body = stringify!({
+            "oncalls": [
+                {
+                    "user": { "id": "PT23IWX", "type": "user_reference", "summary": "Tim Wright" },
+                    "schedule": { "id": "PI7DH85", "type": "schedule_reference" },
+                    "escalation_policy": { "id": "PT20YPA", "type": "escalation_policy_reference" },
+                    "escalation_level": 1,
+                    "start": "2015-03-06T15:28:51-05:00",
+                    "end": "2015-03-07T15:28:51-05:00"
+                },
+                {
+                    "user": { "id": "PXPGF42", "type": "user_reference" },
+                    "schedule": null,
+                    "escalation_policy": { "id": "PT20YPA", "type": "escalation_policy_reference" },
+                    "escalation_level": 2,
+                    "start": null,
+                    "end": null
+                }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false
+        });
+
+        let res = ListOnCalls::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].user.summary, Some("Tim Wright".to_owned()));
+        assert_eq!(page.items[0].schedule.as_ref().unwrap().id, "PI7DH85");
+        assert_eq!(page.items[1].escalation_level, 2);
+        assert_eq!(page.items[1].schedule, None);
+        assert_eq!(page.items[1].end, None);
+    }
+
+    #[test]
+    fn service_found_by_integration_key() {
+        let body = stringify!({
+            "services": [
+                {
+                    "id": "PIJ90N7",
+                    "type": "service",
+                    "name": "My Application Service",
+                    "status": "active",
+                    "escalation_policy": { "id": "PT20YPA", "type": "escalation_policy_reference" },
+                    "integrations": [
+                        { "id": "PQ12345", "type": "generic_email_inbound_integration_reference" },
+                        {
+                            "id": "PE1U9CH",
+                            "type": "events_api_v2_inbound_integration",
+                            "integration_key": "f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b"
+                        }
+                    ]
+                }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false
+        });
+
+        let res = ListServices::get_response(StatusCode::Ok, &Headers::new(), body);
+        let service = &res.unwrap().success().unwrap().items[0];
+
+        assert!(uses_key(service, "f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b"));
+        assert!(!uses_key(service, "0123456789abcdef0123456789abcdef"));
+    }
+}
@@ -0,0 +1,195 @@
+//! Iterating over every result of a list request
+
+use std::thread;
+use std::time::Duration;
+use std::vec;
+
+use AuthToken;
+use request;
+use super::{ListRequest, Page, Response};
+
+/// Number of times a throttled page is retried before the `Throttled` response is yielded
+const MAX_THROTTLED_RETRIES: usize = 3;
+
+/// Delay before retrying a throttled page when PagerDuty didn't specify one, in seconds
+const DEFAULT_RETRY_AFTER: u64 = 1;
+
+/// Iterator over the results of a list request, fetching pages as they're needed
+///
+/// Created with [`ListRequest::paginate`](trait.ListRequest.html#method.paginate). Each result is
+/// yielded as a `Response::Success`. Iteration stops after the last page, after a page without
+/// results, or after a failed request, whose error or response is yielded first.
+///
+/// Before fetching a page, the iterator waits for the REST API's rate limit window to reset if
+/// it has been used up. Throttled pages are retried a few times, after the delay PagerDuty asks
+/// for.
+///
+/// # Example
+///
+/// ```no_run
+/// use pagerduty::AuthToken;
+/// use pagerduty::rest::{ListRequest, Response};
+/// use pagerduty::rest::incidents::{IncidentStatus, ListIncidents};
+///
+/// let auth = AuthToken::new("token");
+/// let request = ListIncidents::new().add_status(IncidentStatus::Triggered).set_limit(100);
+///
+/// for res in request.paginate(&auth) {
+///     match res.unwrap() {
+///         Response::Success(incident) => println!("#{}", incident.incident_number),
+///         other => println!("Failed to list incidents: {:?}", other),
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Paginated<'a, R, T> {
+    auth: &'a AuthToken<'a>,
+    request: Option<R>,
+    items: vec::IntoIter<T>,
+    fetch: fn(&AuthToken, &R) -> request::Result<Response<Page<T>>>,
+}
+
+impl<'a, R, T> Paginated<'a, R, T>
+    where R: ListRequest<Response = Response<Page<T>>>
+{
+    /// Create an iterator over the results of `request`
+    pub fn new(auth: &'a AuthToken<'a>, request: R) -> Paginated<'a, R, T> {
+        Paginated {
+            auth,
+            request: Some(request),
+            items: Vec::new().into_iter(),
+            fetch: request::perform::<R>,
+        }
+    }
+
+    fn fetch_page(&self, request: &R) -> request::Result<Response<Page<T>>> {
+        let mut retries = 0;
+
+        loop {
+            wait_for_quota();
+
+            match (self.fetch)(self.auth, request)? {
+                Response::Throttled { retry_after } if retries < MAX_THROTTLED_RETRIES => {
+                    retries += 1;
+                    thread::sleep(retry_after.unwrap_or(Duration::from_secs(DEFAULT_RETRY_AFTER)));
+                },
+                res => return Ok(res),
+            }
+        }
+    }
+}
+
+impl<'a, R, T> Iterator for Paginated<'a, R, T>
+    where R: ListRequest<Response = Response<Page<T>>>
+{
+    type Item = request::Result<Response<T>>;
+
+    fn next(&mut self) -> Option<request::Result<Response<T>>> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(Ok(Response::Success(item)));
+            }
+
+            let request = self.request.take()?;
+
+            let page = match self.fetch_page(&request) {
+                Ok(res) => match res.into_result() {
+                    Ok(page) => page,
+                    Err(res) => return Some(Ok(res)),
+                },
+                Err(err) => return Some(Err(err)),
+            };
+
+            self.request = request.next_page(&page);
+            self.items = page.items.into_iter();
+        }
+    }
+}
+
+/// Wait for the REST API's rate limit window to reset if no requests are left in it
+fn wait_for_quota() {
+    if let Some(rate_limit) = request::quota().rest {
+        if rate_limit.remaining == Some(0) {
+            if let Some(reset) = rate_limit.reset_in() {
+                thread::sleep(reset);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use AuthToken;
+    use request::{self, Requestable};
+    use rest::{ListRequest, Page, Response};
+    use rest::licenses::{LicenseAllocation, ListLicenseAllocations};
+    use super::Paginated;
+
+    fn fake_fetch(_auth: &AuthToken, request: &ListLicenseAllocations)
+        -> request::Result<Response<Page<LicenseAllocation>>>
+    {
+        let (user, offset, more) = if request.url().contains("offset=1") {
+            ("PB", 1, false)
+        } else {
+            ("PA", 0, true)
+        };
+
+        let body = format!(r#"{{
+            "license_allocations": [
+                {{ "license": {{ "id": "PIP248G", "name": "Full User" }},
+                   "user": {{ "id": "{}", "type": "user_reference" }} }}
+            ],
+            "limit": 1, "offset": {}, "more": {}
+        }}"#, user, offset, more);
+
+        ListLicenseAllocations::get_response(StatusCode::Ok, &Headers::new(), &body)
+    }
+
+    #[test]
+    fn iterate_through_pages() {
+        let auth = AuthToken::new("token");
+        let mut paginated = Paginated::new(&auth, ListLicenseAllocations::new().set_limit(1));
+        paginated.fetch = fake_fetch;
+
+        let users: Vec<String> = paginated
+            .map(|res| res.unwrap().success().unwrap().user.id)
+            .collect();
+
+        assert_eq!(users, vec!["PA", "PB"]);
+    }
+
+    fn empty_fetch(_auth: &AuthToken, _request: &ListLicenseAllocations)
+        -> request::Result<Response<Page<LicenseAllocation>>>
+    {
+        let body = r#"{ "license_allocations": [], "limit": 1, "offset": 0, "more": true }"#;
+        ListLicenseAllocations::get_response(StatusCode::Ok, &Headers::new(), body)
+    }
+
+    #[test]
+    fn stop_at_empty_page() {
+        let auth = AuthToken::new("token");
+        let mut paginated = Paginated::new(&auth, ListLicenseAllocations::new().set_limit(1));
+        paginated.fetch = empty_fetch;
+
+        assert!(paginated.next().is_none());
+    }
+
+    fn forbidden_fetch(_auth: &AuthToken, _request: &ListLicenseAllocations)
+        -> request::Result<Response<Page<LicenseAllocation>>>
+    {
+        ListLicenseAllocations::get_response(StatusCode::Forbidden, &Headers::new(), "")
+    }
+
+    #[test]
+    fn yield_failed_response() {
+        let auth = AuthToken::new("token");
+        let mut paginated = Paginated::new(&auth, ListLicenseAllocations::new());
+        paginated.fetch = forbidden_fetch;
+
+        assert_eq!(paginated.next().unwrap().unwrap(), Response::Forbidden);
+        assert!(paginated.next().is_none());
+    }
+}
@@ -0,0 +1,358 @@
+//! Priorities
+//!
+//! Priorities rank incidents by their impact on the business, such as `P1` through `P5`. The set
+//! of priorities is configured per account, so they must be looked up before an incident can be
+//! given one.
+//!
+//! Accounts not yet using event orchestration to prioritize incidents can use a
+//! [`PriorityPolicy`](struct.PriorityPolicy.html) to set the priority of incidents opened by
+//! Events API v2 triggers, based on the event's severity and component.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use AuthToken;
+use events_v2::{Severity, TriggerEvent};
+use request::{self, Requestable};
+use super::{get_page, ListRequest, Page, Pagination, Query, Reference, Response};
+use super::incidents::{Incident, IncidentUpdate, ListIncidents, UpdateIncident};
+
+/// A priority incidents can be assigned
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Priority {
+    /// The priority's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the priority
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the priority is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the priority is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The user-provided short name of the priority, such as `P1`
+    pub name: String,
+
+    /// The user-provided description of the priority
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl Priority {
+    /// A reference to this priority, for setting it on incidents
+    pub fn to_reference(&self) -> Reference {
+        Reference::new(self.id.clone(), "priority_reference")
+    }
+}
+
+/// List the priorities of the account, from most to least severe
+#[derive(Debug, Default)]
+pub struct ListPriorities {
+    pagination: Pagination,
+}
+
+impl ListPriorities {
+    /// Create a request listing all priorities
+    pub fn new() -> ListPriorities {
+        ListPriorities::default()
+    }
+}
+
+impl ListRequest for ListPriorities {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl Requestable for ListPriorities {
+    type Response = Response<Page<Priority>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url("/priorities").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Priority>>> {
+        get_page(status, headers, body, "priorities")
+    }
+}
+
+/// List priorities
+pub fn list(auth: &AuthToken,
+            request: &ListPriorities) -> request::Result<Response<Page<Priority>>> {
+    request::perform(auth, request)
+}
+
+/// Which priority to give incidents, by the severity and component of the triggering event
+///
+/// Rules are checked in the order they were added, and the first matching rule decides the
+/// priority. Priorities are named as in the account, such as `P1`.
+///
+/// # Example
+///
+/// ```
+/// use pagerduty::events_v2::Severity;
+/// use pagerduty::rest::priorities::PriorityPolicy;
+///
+/// let policy = PriorityPolicy::new()
+///     .add_rule(Severity::Critical, "payments", "P1")
+///     .add_severity(Severity::Critical, "P2")
+///     .add_component("payments", "P3");
+///
+/// assert_eq!(policy.priority_for(Severity::Critical, Some("payments")), Some("P1"));
+/// assert_eq!(policy.priority_for(Severity::Critical, None), Some("P2"));
+/// assert_eq!(policy.priority_for(Severity::Info, Some("payments")), Some("P3"));
+/// assert_eq!(policy.priority_for(Severity::Info, None), None);
+/// ```
+#[derive(Debug, Default)]
+pub struct PriorityPolicy<'a> {
+    rules: Vec<Rule<'a>>,
+}
+
+#[derive(Debug)]
+struct Rule<'a> {
+    severity: Option<Severity>,
+    component: Option<Cow<'a, str>>,
+    priority: Cow<'a, str>,
+}
+
+impl<'a> Rule<'a> {
+    fn matches(&self, severity: Severity, component: Option<&str>) -> bool {
+        self.severity.is_none_or(|expected| expected == severity) &&
+        self.component.as_ref().is_none_or(|expected| Some(&expected[..]) == component)
+    }
+}
+
+impl<'a> PriorityPolicy<'a> {
+    /// Create a policy without any rules
+    pub fn new() -> PriorityPolicy<'a> {
+        PriorityPolicy::default()
+    }
+
+    /// Give `priority` to incidents triggered by events of `severity` about `component`
+    pub fn add_rule<S, P>(mut self, severity: Severity, component: S, priority: P) -> Self
+        where S: Into<Cow<'a, str>>,
+              P: Into<Cow<'a, str>>
+    {
+        self.rules.push(Rule {
+            severity: Some(severity),
+            component: Some(component.into()),
+            priority: priority.into(),
+        });
+        self
+    }
+
+    /// Give `priority` to incidents triggered by events of `severity`, about any component
+    pub fn add_severity<P>(mut self, severity: Severity, priority: P) -> Self
+        where P: Into<Cow<'a, str>>
+    {
+        self.rules.push(Rule {
+            severity: Some(severity),
+            component: None,
+            priority: priority.into(),
+        });
+        self
+    }
+
+    /// Give `priority` to incidents triggered by events about `component`, of any severity
+    pub fn add_component<S, P>(mut self, component: S, priority: P) -> Self
+        where S: Into<Cow<'a, str>>,
+              P: Into<Cow<'a, str>>
+    {
+        self.rules.push(Rule {
+            severity: None,
+            component: Some(component.into()),
+            priority: priority.into(),
+        });
+        self
+    }
+
+    /// The name of the priority for an event of `severity` about `component`, if a rule matches
+    pub fn priority_for(&self, severity: Severity, component: Option<&str>) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(severity, component))
+            .map(|rule| &rule.priority[..])
+    }
+}
+
+/// The outcome of applying a [`PriorityPolicy`](struct.PriorityPolicy.html) to an incident
+#[derive(Debug)]
+pub enum Prioritized {
+    /// The incident was given the priority
+    Set(Incident),
+
+    /// The incident already had a priority, which was left alone
+    AlreadySet(Incident),
+
+    /// No rule of the policy matches the event
+    NoRule,
+
+    /// The account has no priority of the name given by the matching rule
+    UnknownPriority(String),
+
+    /// No incident has the event's dedup key
+    ///
+    /// PagerDuty opens incidents shortly after accepting the event, so the incident may not exist
+    /// yet; trying again after a few seconds usually finds it.
+    NoIncident,
+}
+
+/// Give the incident opened by `event` the priority `policy` assigns it
+///
+/// `dedup_key` is the one PagerDuty answered the trigger with, which is also the incident's key.
+/// `from` is the email address of the user making the change, required with account-level API
+/// tokens.
+///
+/// Incidents which already have a priority, such as one set by a responder or by event
+/// orchestration, are left alone.
+///
+/// # Example
+///
+/// ```no_run
+/// use pagerduty::AuthToken;
+/// use pagerduty::events_v2::{self, Severity, TriggerEvent};
+/// use pagerduty::rest::priorities::{self, PriorityPolicy};
+///
+/// let auth = AuthToken::new("token");
+/// let policy = PriorityPolicy::new().add_severity(Severity::Critical, "P1");
+/// let event = TriggerEvent::new("routing key", "Disk full", "db01", Severity::Critical);
+///
+/// if let events_v2::Response::Success(accepted) = events_v2::trigger(&auth, &event).unwrap() {
+///     let res = priorities::assign(&auth, &policy, &event, &accepted.dedup_key,
+///                                  Some("ops@example.com"));
+///     println!("{:?}", res);
+/// }
+/// ```
+pub fn assign(auth: &AuthToken,
+              policy: &PriorityPolicy,
+              event: &TriggerEvent,
+              dedup_key: &str,
+              from: Option<&str>) -> request::Result<Response<Prioritized>> {
+    let name = match policy.priority_for(event.severity(), event.component()) {
+        Some(name) => name,
+        None => return Ok(Response::Success(Prioritized::NoRule)),
+    };
+
+    let mut priority = None;
+    for res in ListPriorities::new().paginate(auth) {
+        match res?.into_result() {
+            Ok(found) => {
+                if found.name == name {
+                    priority = Some(found);
+                    break;
+                }
+            },
+            Err(res) => return Ok(res),
+        }
+    }
+
+    let priority = match priority {
+        Some(priority) => priority,
+        None => return Ok(Response::Success(Prioritized::UnknownPriority(name.to_owned()))),
+    };
+
+    let incidents = ListIncidents::new().set_incident_key(dedup_key);
+    let incident = match request::perform(auth, &incidents)?.into_result() {
+        Ok(page) => page.items.into_iter().next(),
+        Err(res) => return Ok(res),
+    };
+
+    let incident = match incident {
+        Some(incident) => incident,
+        None => return Ok(Response::Success(Prioritized::NoIncident)),
+    };
+
+    if incident.priority.is_some() {
+        return Ok(Response::Success(Prioritized::AlreadySet(incident)));
+    }
+
+    let update = IncidentUpdate::new().set_priority(priority.to_reference());
+    let mut request = UpdateIncident::new(&*incident.id, update);
+    if let Some(from) = from {
+        request = request.set_from(from);
+    }
+
+    Ok(request::perform(auth, &request)?.map(Prioritized::Set))
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use events_v2::{Severity, TriggerEvent};
+    use request::Requestable;
+    use rest::Reference;
+    use super::{ListPriorities, PriorityPolicy};
+
+    #[test]
+    fn priorities_from_json() {
+        let body = stringify!({
+            "priorities": [
+                {
+                    "id": "PSO75BM",
+                    "type": "priority",
+                    "summary": "P1",
+                    "self": "https://api.pagerduty.com/priorities/PSO75BM",
+                    "html_url": null,
+                    "name": "P1",
+                    "description": "Critical production outage"
+                }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false,
+            "total": null
+        });
+
+        let res = ListPriorities::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].name, "P1");
+        assert_eq!(page.items[0].to_reference(), Reference::new("PSO75BM", "priority_reference"));
+    }
+
+    #[test]
+    fn policy_matches_trigger_events() {
+        let policy = PriorityPolicy::new()
+                         .add_component("payments", "P1")
+                         .add_severity(Severity::Error, "P3");
+
+        let event = TriggerEvent::new("key", "Checkout failing", "web01", Severity::Error)
+                        .set_component("payments");
+        assert_eq!(policy.priority_for(event.severity(), event.component()), Some("P1"));
+
+        let event = event.set_severity(Severity::Warning);
+        assert_eq!(policy.priority_for(event.severity(), event.component()), Some("P1"));
+
+        let event = TriggerEvent::new("key", "Disk full", "db01", Severity::Error);
+        assert_eq!(policy.priority_for(event.severity(), event.component()), Some("P3"));
+
+        let event = event.set_severity(Severity::Info);
+        assert_eq!(policy.priority_for(event.severity(), event.component()), None);
+    }
+}
@@ -0,0 +1,361 @@
+//! Response Plays
+//!
+//! A response play is a predefined set of actions run on an incident: adding responders,
+//! subscribing stakeholders to status updates, and attaching a conference bridge.
+//!
+//! The response play endpoints act on behalf of a user identified by email address. It is given
+//! with `set_from` when listing or getting plays with an account-level API token, and is always
+//! required to run a play.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// How a conference bridge is attached to incidents by a response play
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConferenceType {
+    /// No conference bridge is attached
+    None,
+
+    /// The number and URL configured on the response play are attached
+    Manual,
+
+    /// A Zoom meeting is created and attached
+    Zoom,
+
+    /// A conference type this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// A predefined set of actions which can be run on an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ResponsePlay {
+    /// The response play's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the response play
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the response play is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the response play is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the response play
+    pub name: String,
+
+    /// A description of the response play
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The team the response play belongs to, if any
+    #[serde(default)]
+    pub team: Option<Reference>,
+
+    /// Users and escalation policies added as responders to the incident
+    #[serde(default)]
+    pub responders: Vec<Reference>,
+
+    /// The message sent to responders
+    #[serde(default)]
+    pub responders_message: Option<String>,
+
+    /// Users and teams subscribed to status updates of the incident
+    #[serde(default)]
+    pub subscribers: Vec<Reference>,
+
+    /// The message sent to subscribers
+    #[serde(default)]
+    pub subscribers_message: Option<String>,
+
+    /// Who may run the response play; `services`, `teams_services`, or `responders`
+    #[serde(default)]
+    pub runnability: Option<String>,
+
+    /// How a conference bridge is attached to the incident
+    #[serde(default)]
+    pub conference_type: Option<ConferenceType>,
+
+    /// The phone number of the conference bridge, for `ConferenceType::Manual`
+    #[serde(default)]
+    pub conference_number: Option<String>,
+
+    /// The URL of the conference bridge, for `ConferenceType::Manual`
+    #[serde(default)]
+    pub conference_url: Option<String>,
+}
+
+/// List response plays, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListResponsePlays<'a> {
+    from: Option<Cow<'a, str>>,
+    query: Option<Cow<'a, str>>,
+    filter_for_manual_run: bool,
+    pagination: Pagination,
+}
+
+impl<'a> ListResponsePlays<'a> {
+    /// Create a request listing all response plays
+    pub fn new() -> ListResponsePlays<'a> {
+        ListResponsePlays::default()
+    }
+
+    /// Only return response plays whose names contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Only return response plays which can be run manually
+    pub fn set_filter_for_manual_run(mut self, filter: bool) -> Self {
+        self.filter_for_manual_run = filter;
+        self
+    }
+
+    /// Set the email address of the user listing response plays
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListResponsePlays<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListResponsePlays<'a> {
+    type Response = Response<Page<ResponsePlay>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        if self.filter_for_manual_run {
+            query.push_bool("filter_for_manual_run", true);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/response_plays").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<ResponsePlay>>> {
+        get_page(status, headers, body, "response_plays")
+    }
+}
+
+/// Get details about an existing response play
+#[derive(Debug)]
+pub struct GetResponsePlay<'a> {
+    id: Cow<'a, str>,
+    from: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetResponsePlay<'a> {
+    /// Create a request for response play `id`
+    pub fn new<S>(id: S) -> GetResponsePlay<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetResponsePlay {
+            id: id.into(),
+            from: None,
+        }
+    }
+
+    /// Set the email address of the user getting the response play
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetResponsePlay<'a> {
+    type Response = Response<ResponsePlay>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/response_plays/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<ResponsePlay>> {
+        get_response(status, headers, body, "response_play")
+    }
+}
+
+/// Run a response play on an incident
+#[derive(Debug, Serialize)]
+pub struct RunResponsePlay<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    from: Cow<'a, str>,
+
+    incident: Reference,
+}
+
+impl<'a> RunResponsePlay<'a> {
+    /// Create a request running response play `id` on incident `incident_id`
+    ///
+    /// The play is run on behalf of the user with email address `from`, which is required by
+    /// this endpoint regardless of the kind of API token used.
+    pub fn new<S, I>(id: S, incident_id: I, from: S) -> RunResponsePlay<'a>
+        where S: Into<Cow<'a, str>>,
+              I: Into<String>
+    {
+        RunResponsePlay {
+            id: id.into(),
+            from: from.into(),
+            incident: Reference::new(incident_id, "incident_reference"),
+        }
+    }
+}
+
+impl<'a> Requestable for RunResponsePlay<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(Some(&self.from))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/response_plays/{}/run", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List response plays
+pub fn list(auth: &AuthToken,
+            request: &ListResponsePlays) -> request::Result<Response<Page<ResponsePlay>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single response play
+pub fn get(auth: &AuthToken,
+           request: &GetResponsePlay) -> request::Result<Response<ResponsePlay>> {
+    request::perform(auth, request)
+}
+
+/// Run a response play
+pub fn run(auth: &AuthToken, request: &RunResponsePlay) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use super::{ConferenceType, GetResponsePlay, RunResponsePlay};
+
+    #[test]
+    fn run_response_play() {
+        let request = RunResponsePlay::new("P3ZQXDF", "PT4KHLK", "jane@example.com");
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/response_plays/P3ZQXDF/run");
+        assert_eq!(request.body(), r#"{"incident":{"id":"PT4KHLK","type":"incident_reference"}}"#);
+        assert_eq!(request.headers().get_raw("From").unwrap()[0], b"jane@example.com".to_vec());
+    }
+
+    #[test]
+    fn response_play_from_json() {
+        let body = stringify!({
+            "response_play": {
+                "id": "P3ZQXDF",
+                "type": "response_play",
+                "summary": "Major incident",
+                "name": "Major incident",
+                "description": "Assemble the major incident team",
+                "team": null,
+                "responders": [
+                    { "id": "PANZZEQ", "type": "escalation_policy_reference" }
+                ],
+                "responders_message": "Join the bridge",
+                "subscribers": [
+                    { "id": "PQ9K7I8", "type": "team_reference" }
+                ],
+                "subscribers_message": "We are investigating",
+                "runnability": "services",
+                "conference_type": "manual",
+                "conference_number": "+1 555-555-0100,,123456#",
+                "conference_url": "https://example.com/bridge"
+            }
+        });
+
+        let res = GetResponsePlay::get_response(StatusCode::Ok, &Headers::new(), body);
+        let play = res.unwrap().success().unwrap();
+
+        assert_eq!(play.responders[0].kind, "escalation_policy_reference");
+        assert_eq!(play.subscribers[0].id, "PQ9K7I8");
+        assert_eq!(play.conference_type, Some(ConferenceType::Manual));
+        assert_eq!(play.conference_url, Some("https://example.com/bridge".to_owned()));
+    }
+}
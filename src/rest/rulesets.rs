@@ -0,0 +1,1049 @@
+//! Rulesets
+//!
+//! Rulesets route and transform events sent to the events API before they reach a service. Each
+//! ruleset has its own routing keys; events sent with one of them are evaluated against the
+//! ruleset's event rules.
+//!
+//! # Event rules
+//!
+//! Rules are evaluated in order of their `position`, and the first rule whose conditions match an
+//! event has its actions applied. Rules, their conditions and their actions are plain data which
+//! can be both read from and sent to the API, so rule definitions can be kept alongside code:
+//!
+//! ```
+//! use pagerduty::events_v2::Severity;
+//! use pagerduty::rest::rulesets::{Actions, Conditions, EventRuleFields, Subcondition,
+//!                                 SubconditionOperator};
+//!
+//! let rule = EventRuleFields::new()
+//!     .set_position(0)
+//!     .set_conditions(Conditions::all(vec![
+//!         Subcondition::new(SubconditionOperator::Contains, "payload.source", "db-"),
+//!     ]))
+//!     .set_actions(Actions::new().set_route("PIJ90N7").set_severity(Severity::Critical));
+//! ```
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use events_v2::Severity;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// A set of event rules with its own routing keys
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Ruleset {
+    /// The ruleset's ID
+    pub id: String,
+
+    /// The kind of ruleset; `global` for user-created rulesets, or `default_global` for the
+    /// account's default
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The API URL at which the ruleset is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// The name of the ruleset
+    pub name: String,
+
+    /// Routing keys which send events to this ruleset
+    #[serde(default)]
+    pub routing_keys: Vec<String>,
+
+    /// The team owning the ruleset, if any
+    #[serde(default)]
+    pub team: Option<Reference>,
+
+    /// The time at which the ruleset was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+
+    /// The user who created the ruleset
+    #[serde(default)]
+    pub creator: Option<Reference>,
+
+    /// The time at which the ruleset was last updated
+    #[serde(default)]
+    pub updated_at: Option<String>,
+
+    /// The user who last updated the ruleset
+    #[serde(default)]
+    pub updater: Option<Reference>,
+}
+
+/// A rule routing or transforming the events which match its conditions
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct EventRule {
+    /// The rule's ID
+    pub id: String,
+
+    /// The API URL at which the rule is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// The position of the rule within the ruleset, starting at 0
+    #[serde(default)]
+    pub position: Option<u64>,
+
+    /// Whether the rule is disabled
+    #[serde(default)]
+    pub disabled: bool,
+
+    /// Whether this is the ruleset's catch-all rule, applied to events matching no other rule
+    #[serde(default)]
+    pub catch_all: bool,
+
+    /// The conditions events must match; absent for the catch-all rule
+    #[serde(default)]
+    pub conditions: Option<Conditions>,
+
+    /// The actions applied to matching events
+    #[serde(default)]
+    pub actions: Actions,
+}
+
+/// How the subconditions of a rule are combined
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ConditionOperator {
+    /// Every subcondition must match
+    And,
+
+    /// At least one subcondition must match
+    Or,
+}
+
+/// The conditions under which a rule applies to an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Conditions {
+    /// How the subconditions are combined
+    pub operator: ConditionOperator,
+
+    /// The individual checks made against the event
+    pub subconditions: Vec<Subcondition>,
+}
+
+impl Conditions {
+    /// Conditions matching events which match every one of `subconditions`
+    pub fn all(subconditions: Vec<Subcondition>) -> Conditions {
+        Conditions {
+            operator: ConditionOperator::And,
+            subconditions,
+        }
+    }
+
+    /// Conditions matching events which match any of `subconditions`
+    pub fn any(subconditions: Vec<Subcondition>) -> Conditions {
+        Conditions {
+            operator: ConditionOperator::Or,
+            subconditions,
+        }
+    }
+}
+
+/// How a subcondition compares a field of the event with its value
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubconditionOperator {
+    Exists,
+    Nexists,
+    Equals,
+    Nequals,
+    Contains,
+    Ncontains,
+    Matches,
+    Nmatches,
+}
+
+/// A check made against a single field of an event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Subcondition {
+    /// How the field is compared with the value
+    pub operator: SubconditionOperator,
+
+    /// The field and value compared
+    pub parameters: SubconditionParameters,
+}
+
+impl Subcondition {
+    /// Compare the event field at `path`, such as `payload.summary`, with `value`
+    pub fn new<P, V>(operator: SubconditionOperator, path: P, value: V) -> Subcondition
+        where P: Into<String>,
+              V: Into<String>
+    {
+        Subcondition {
+            operator,
+            parameters: SubconditionParameters {
+                path: path.into(),
+                value: value.into(),
+            },
+        }
+    }
+}
+
+/// The field and value of a subcondition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubconditionParameters {
+    /// The path of the event field, such as `payload.summary`
+    pub path: String,
+
+    /// The value the field is compared with; a regular expression for `Matches` and `Nmatches`
+    #[serde(default)]
+    pub value: String,
+}
+
+/// An action parameter, wrapped in an object as the API expects
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ActionValue<T> {
+    pub value: T,
+}
+
+/// A value extracted from one event field into another
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Extraction {
+    /// The field the extracted value is written to, such as `summary`
+    pub target: String,
+
+    /// The path of the field the value is extracted from
+    pub source: String,
+
+    /// A regular expression whose first capture group is extracted
+    pub regex: String,
+}
+
+/// The actions a rule applies to matching events
+///
+/// Actions which are `None` are left alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Actions {
+    /// Add a note to the incident created for the event
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub annotate: Option<ActionValue<String>>,
+
+    /// Change the event action, `trigger` or `resolve`
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub event_action: Option<ActionValue<String>>,
+
+    /// Rewrite fields of the event with values extracted from other fields
+    #[serde(default, skip_serializing_if="Vec::is_empty")]
+    pub extractions: Vec<Extraction>,
+
+    /// Set the priority of the incident, by priority ID
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub priority: Option<ActionValue<String>>,
+
+    /// Send the event to a service, by service ID
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub route: Option<ActionValue<String>>,
+
+    /// Set the severity of the event
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub severity: Option<ActionValue<Severity>>,
+
+    /// Whether to suppress the event, so it creates no incident
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub suppress: Option<ActionValue<bool>>,
+
+    /// The number of seconds to hold the event before creating an incident
+    #[serde(default, skip_serializing_if="Option::is_none")]
+    pub suspend: Option<ActionValue<u64>>,
+}
+
+impl Actions {
+    /// Create a set of actions leaving events unchanged
+    pub fn new() -> Actions {
+        Actions::default()
+    }
+
+    /// Add `note` to the incident created for the event
+    pub fn set_annotate<S>(mut self, note: S) -> Self
+        where S: Into<String>
+    {
+        self.annotate = Some(ActionValue { value: note.into() });
+        self
+    }
+
+    /// Change the event action to `trigger` or `resolve`
+    pub fn set_event_action<S>(mut self, event_action: S) -> Self
+        where S: Into<String>
+    {
+        self.event_action = Some(ActionValue { value: event_action.into() });
+        self
+    }
+
+    /// Rewrite a field of the event; may be called more than once
+    pub fn add_extraction(mut self, extraction: Extraction) -> Self {
+        self.extractions.push(extraction);
+        self
+    }
+
+    /// Set the priority of the incident to priority `priority_id`
+    pub fn set_priority<S>(mut self, priority_id: S) -> Self
+        where S: Into<String>
+    {
+        self.priority = Some(ActionValue { value: priority_id.into() });
+        self
+    }
+
+    /// Send the event to service `service_id`
+    pub fn set_route<S>(mut self, service_id: S) -> Self
+        where S: Into<String>
+    {
+        self.route = Some(ActionValue { value: service_id.into() });
+        self
+    }
+
+    /// Set the severity of the event
+    pub fn set_severity(mut self, severity: Severity) -> Self {
+        self.severity = Some(ActionValue { value: severity });
+        self
+    }
+
+    /// Set whether the event is suppressed
+    pub fn set_suppress(mut self, suppress: bool) -> Self {
+        self.suppress = Some(ActionValue { value: suppress });
+        self
+    }
+
+    /// Hold the event for `seconds` before creating an incident
+    pub fn set_suspend(mut self, seconds: u64) -> Self {
+        self.suspend = Some(ActionValue { value: seconds });
+        self
+    }
+}
+
+/// List rulesets
+#[derive(Debug, Default)]
+pub struct ListRulesets {
+    pagination: Pagination,
+}
+
+impl ListRulesets {
+    /// Create a request listing all rulesets
+    pub fn new() -> ListRulesets {
+        ListRulesets::default()
+    }
+}
+
+impl ListRequest for ListRulesets {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl Requestable for ListRulesets {
+    type Response = Response<Page<Ruleset>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url("/rulesets").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Ruleset>>> {
+        get_page(status, headers, body, "rulesets")
+    }
+}
+
+/// Get details about an existing ruleset
+#[derive(Debug)]
+pub struct GetRuleset<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetRuleset<'a> {
+    /// Create a request for ruleset `id`
+    pub fn new<S>(id: S) -> GetRuleset<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetRuleset {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetRuleset<'a> {
+    type Response = Response<Ruleset>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Ruleset>> {
+        get_response(status, headers, body, "ruleset")
+    }
+}
+
+/// Writable fields of a ruleset
+#[derive(Debug, Default, Serialize)]
+pub struct RulesetFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    team: Option<Reference>,
+}
+
+impl<'a> RulesetFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> RulesetFields<'a> {
+        RulesetFields::default()
+    }
+
+    /// Set the name of the ruleset
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the team owning the ruleset
+    pub fn set_team<S>(mut self, team_id: S) -> Self
+        where S: Into<String>
+    {
+        self.team = Some(Reference::new(team_id, "team_reference"));
+        self
+    }
+}
+
+/// Create a new ruleset
+#[derive(Debug, Serialize)]
+pub struct CreateRuleset<'a> {
+    ruleset: RulesetFields<'a>,
+}
+
+impl<'a> CreateRuleset<'a> {
+    /// Create a request for a new ruleset described by `fields`
+    ///
+    /// PagerDuty requires at least a name.
+    pub fn new(fields: RulesetFields<'a>) -> CreateRuleset<'a> {
+        CreateRuleset {
+            ruleset: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateRuleset<'a> {
+    type Response = Response<Ruleset>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Ruleset>> {
+        get_response(status, headers, body, "ruleset")
+    }
+}
+
+/// Update an existing ruleset
+#[derive(Debug, Serialize)]
+pub struct UpdateRuleset<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    ruleset: RulesetFields<'a>,
+}
+
+impl<'a> UpdateRuleset<'a> {
+    /// Create a request applying `fields` to ruleset `id`
+    pub fn new<S>(id: S, fields: RulesetFields<'a>) -> UpdateRuleset<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateRuleset {
+            id: id.into(),
+            ruleset: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateRuleset<'a> {
+    type Response = Response<Ruleset>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Ruleset>> {
+        get_response(status, headers, body, "ruleset")
+    }
+}
+
+/// Delete an existing ruleset
+#[derive(Debug)]
+pub struct DeleteRuleset<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteRuleset<'a> {
+    /// Create a request deleting ruleset `id`
+    pub fn new<S>(id: S) -> DeleteRuleset<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteRuleset {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteRuleset<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List the event rules of a ruleset, in order of position
+#[derive(Debug)]
+pub struct ListEventRules<'a> {
+    ruleset_id: Cow<'a, str>,
+    pagination: Pagination,
+}
+
+impl<'a> ListEventRules<'a> {
+    /// Create a request listing the rules of ruleset `ruleset_id`
+    pub fn new<S>(ruleset_id: S) -> ListEventRules<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListEventRules {
+            ruleset_id: ruleset_id.into(),
+            pagination: Pagination::default(),
+        }
+    }
+}
+
+impl<'a> ListRequest for ListEventRules<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListEventRules<'a> {
+    type Response = Response<Page<EventRule>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url(&format!("/rulesets/{}/rules", segment(&self.ruleset_id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<EventRule>>> {
+        get_page(status, headers, body, "rules")
+    }
+}
+
+/// Get details about an event rule
+#[derive(Debug)]
+pub struct GetEventRule<'a> {
+    ruleset_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetEventRule<'a> {
+    /// Create a request for rule `id` of ruleset `ruleset_id`
+    pub fn new<S>(ruleset_id: S, id: S) -> GetEventRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetEventRule {
+            ruleset_id: ruleset_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetEventRule<'a> {
+    type Response = Response<EventRule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}/rules/{}",
+                BASE_URL,
+                segment(&self.ruleset_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EventRule>> {
+        get_response(status, headers, body, "rule")
+    }
+}
+
+/// Writable fields of an event rule
+#[derive(Debug, Default, Serialize)]
+pub struct EventRuleFields {
+    #[serde(skip_serializing_if="Option::is_none")]
+    position: Option<u64>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    disabled: Option<bool>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    conditions: Option<Conditions>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    actions: Option<Actions>,
+}
+
+impl EventRuleFields {
+    /// Create an empty set of fields
+    pub fn new() -> EventRuleFields {
+        EventRuleFields::default()
+    }
+
+    /// Set the position of the rule within the ruleset
+    ///
+    /// Rules at or after `position` are moved down to make room. New rules are added at the end
+    /// of the ruleset, just before the catch-all rule, unless a position is set.
+    pub fn set_position(mut self, position: u64) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Set whether the rule is disabled
+    pub fn set_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    /// Set the conditions events must match
+    pub fn set_conditions(mut self, conditions: Conditions) -> Self {
+        self.conditions = Some(conditions);
+        self
+    }
+
+    /// Set the actions applied to matching events
+    pub fn set_actions(mut self, actions: Actions) -> Self {
+        self.actions = Some(actions);
+        self
+    }
+}
+
+/// Add an event rule to a ruleset
+#[derive(Debug, Serialize)]
+pub struct CreateEventRule<'a> {
+    #[serde(skip_serializing)]
+    ruleset_id: Cow<'a, str>,
+
+    rule: EventRuleFields,
+}
+
+impl<'a> CreateEventRule<'a> {
+    /// Create a request adding the rule described by `fields` to ruleset `ruleset_id`
+    ///
+    /// PagerDuty requires at least conditions and actions.
+    pub fn new<S>(ruleset_id: S, fields: EventRuleFields) -> CreateEventRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateEventRule {
+            ruleset_id: ruleset_id.into(),
+            rule: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateEventRule<'a> {
+    type Response = Response<EventRule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}/rules", BASE_URL, segment(&self.ruleset_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EventRule>> {
+        get_response(status, headers, body, "rule")
+    }
+}
+
+/// Update an event rule
+#[derive(Debug, Serialize)]
+pub struct UpdateEventRule<'a> {
+    #[serde(skip_serializing)]
+    ruleset_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    rule: EventRuleFields,
+}
+
+impl<'a> UpdateEventRule<'a> {
+    /// Create a request applying `fields` to rule `id` of ruleset `ruleset_id`
+    pub fn new<S>(ruleset_id: S, id: S, fields: EventRuleFields) -> UpdateEventRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateEventRule {
+            ruleset_id: ruleset_id.into(),
+            id: id.into(),
+            rule: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateEventRule<'a> {
+    type Response = Response<EventRule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}/rules/{}",
+                BASE_URL,
+                segment(&self.ruleset_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<EventRule>> {
+        get_response(status, headers, body, "rule")
+    }
+}
+
+/// Delete an event rule
+#[derive(Debug)]
+pub struct DeleteEventRule<'a> {
+    ruleset_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteEventRule<'a> {
+    /// Create a request deleting rule `id` of ruleset `ruleset_id`
+    pub fn new<S>(ruleset_id: S, id: S) -> DeleteEventRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteEventRule {
+            ruleset_id: ruleset_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteEventRule<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/rulesets/{}/rules/{}",
+                BASE_URL,
+                segment(&self.ruleset_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List rulesets
+pub fn list(auth: &AuthToken, request: &ListRulesets) -> request::Result<Response<Page<Ruleset>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single ruleset
+pub fn get(auth: &AuthToken, request: &GetRuleset) -> request::Result<Response<Ruleset>> {
+    request::perform(auth, request)
+}
+
+/// Create a ruleset
+pub fn create(auth: &AuthToken, request: &CreateRuleset) -> request::Result<Response<Ruleset>> {
+    request::perform(auth, request)
+}
+
+/// Update a ruleset
+pub fn update(auth: &AuthToken, request: &UpdateRuleset) -> request::Result<Response<Ruleset>> {
+    request::perform(auth, request)
+}
+
+/// Delete a ruleset
+pub fn delete(auth: &AuthToken, request: &DeleteRuleset) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List the event rules of a ruleset
+pub fn list_rules(auth: &AuthToken,
+                  request: &ListEventRules) -> request::Result<Response<Page<EventRule>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single event rule
+pub fn get_rule(auth: &AuthToken, request: &GetEventRule) -> request::Result<Response<EventRule>> {
+    request::perform(auth, request)
+}
+
+/// Add an event rule to a ruleset
+pub fn create_rule(auth: &AuthToken,
+                   request: &CreateEventRule) -> request::Result<Response<EventRule>> {
+    request::perform(auth, request)
+}
+
+/// Update an event rule
+pub fn update_rule(auth: &AuthToken,
+                   request: &UpdateEventRule) -> request::Result<Response<EventRule>> {
+    request::perform(auth, request)
+}
+
+/// Delete an event rule
+pub fn delete_rule(auth: &AuthToken, request: &DeleteEventRule) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use events_v2::Severity;
+    use request::Requestable;
+    use super::{Actions, Conditions, CreateEventRule, CreateRuleset, EventRuleFields,
+                GetEventRule, RulesetFields, Subcondition, SubconditionOperator};
+
+    #[test]
+    fn create_event_rule_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "rule": {
+                "position": 0,
+                "conditions": {
+                    "operator": "and",
+                    "subconditions": [
+                        {
+                            "operator": "contains",
+                            "parameters": { "path": "payload.source", "value": "db-" }
+                        }
+                    ]
+                },
+                "actions": {
+                    "route": { "value": "PIJ90N7" },
+                    "severity": { "value": "critical" }
+                }
+            }
+        })).expect("expected is valid json");
+
+        let conditions = Conditions::all(vec![
+            Subcondition::new(SubconditionOperator::Contains, "payload.source", "db-"),
+        ]);
+        let actions = Actions::new().set_route("PIJ90N7").set_severity(Severity::Critical);
+        let fields = EventRuleFields::new()
+            .set_position(0)
+            .set_conditions(conditions.clone())
+            .set_actions(actions.clone());
+
+        let request = CreateEventRule::new("0e84de00", fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(), "https://api.pagerduty.com/rulesets/0e84de00/rules");
+
+        let body = stringify!({
+            "rule": {
+                "id": "14e56445-ebab-4dc6-8d63-6bcae2e5e1c3",
+                "self": "https://api.pagerduty.com/rulesets/0e84de00/rules/14e56445",
+                "position": 0,
+                "disabled": false,
+                "catch_all": false,
+                "conditions": {
+                    "operator": "and",
+                    "subconditions": [
+                        {
+                            "operator": "contains",
+                            "parameters": { "path": "payload.source", "value": "db-" }
+                        }
+                    ]
+                },
+                "actions": {
+                    "annotate": null,
+                    "route": { "value": "PIJ90N7" },
+                    "severity": { "value": "critical" },
+                    "extractions": []
+                }
+            }
+        });
+
+        let res = GetEventRule::get_response(StatusCode::Ok, &Headers::new(), body);
+        let rule = res.unwrap().success().unwrap();
+
+        assert_eq!(rule.position, Some(0));
+        assert_eq!(rule.conditions, Some(conditions));
+        assert_eq!(rule.actions, actions);
+    }
+
+    #[test]
+    fn create_ruleset_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "ruleset": {
+                "name": "Edge",
+                "team": { "id": "PQ9K7I8", "type": "team_reference" }
+            }
+        })).expect("expected is valid json");
+
+        let request = CreateRuleset::new(RulesetFields::new().set_name("Edge").set_team("PQ9K7I8"));
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "ruleset": {
+                "id": "0e84de00-9511-4380-9f4f-a7b568bb49a0",
+                "type": "global",
+                "self": "https://api.pagerduty.com/rulesets/0e84de00-9511-4380-9f4f-a7b568bb49a0",
+                "name": "Edge",
+                "routing_keys": ["R0213QP3W0QN3NCS1H2YLRGNRNEHEW1F"],
+                "team": { "id": "PQ9K7I8", "type": "team_reference" },
+                "created_at": "2019-12-05T19:14:48Z",
+                "creator": { "id": "PXPGF42", "type": "user_reference" }
+            }
+        });
+
+        let res = CreateRuleset::get_response(StatusCode::Created, &Headers::new(), body);
+        let ruleset = res.unwrap().success().unwrap();
+
+        assert_eq!(ruleset.kind, "global");
+        assert_eq!(ruleset.routing_keys, vec!["R0213QP3W0QN3NCS1H2YLRGNRNEHEW1F".to_owned()]);
+    }
+}
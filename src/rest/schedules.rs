@@ -0,0 +1,1228 @@
+//! Schedules
+//!
+//! A schedule determines the time periods that users are on call. Schedules are made of layers
+//! which rotate through their users; PagerDuty renders the layers, together with any overrides,
+//! into the final schedule.
+//!
+//! Rendered entries are only included when a schedule is requested with a `since` and `until`
+//! window.
+//!
+//! Schedules can also be provisioned from code with `create` and `update`, describing the
+//! schedule with [`ScheduleFields`](struct.ScheduleFields.html) and its layers with
+//! [`ScheduleLayerFields`](struct.ScheduleLayerFields.html).
+//!
+//! Overrides temporarily replace whoever is on call for a period of time, for example when
+//! someone covers another responder's shift.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, Utc};
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use timestamp::Timestamp;
+use super::{get_body_response, get_empty_response, get_page, get_response, segment, ListRequest,
+            Page, Pagination, Query, Reference, Response, BASE_URL};
+
+/// An on-call schedule
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Schedule {
+    /// The schedule's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the schedule
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the schedule is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the schedule is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the schedule
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The time zone of the schedule
+    pub time_zone: String,
+
+    /// The description of the schedule
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The layers making up the schedule
+    #[serde(default)]
+    pub schedule_layers: Vec<ScheduleLayer>,
+
+    /// The rendered schedule, combining all layers and overrides
+    #[serde(default)]
+    pub final_schedule: Option<SubSchedule>,
+
+    /// The rendered overrides of the schedule
+    #[serde(default)]
+    pub overrides_subschedule: Option<SubSchedule>,
+
+    /// Escalation policies which use this schedule
+    #[serde(default)]
+    pub escalation_policies: Vec<Reference>,
+
+    /// Users on this schedule
+    #[serde(default)]
+    pub users: Vec<Reference>,
+
+    /// Teams associated with the schedule
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+}
+
+/// A layer of a schedule, rotating through a set of users
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ScheduleLayer {
+    /// The schedule layer's ID
+    #[serde(default)]
+    pub id: Option<String>,
+
+    /// The name of the schedule layer
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The start time of this layer
+    pub start: String,
+
+    /// The end time of this layer, or `None` if the layer runs indefinitely
+    #[serde(default)]
+    pub end: Option<String>,
+
+    /// The effective start time of the layer, which can be before `start`
+    pub rotation_virtual_start: String,
+
+    /// The duration of each on-call shift
+    #[serde(rename = "rotation_turn_length_seconds", with = "super::duration::seconds")]
+    pub rotation_turn_length: Duration,
+
+    /// The users in the rotation, in order
+    #[serde(default)]
+    pub users: Vec<ScheduleLayerUser>,
+
+    /// Restrictions limiting when the layer is on call
+    #[serde(default)]
+    pub restrictions: Vec<Restriction>,
+
+    /// The entries of the rendered layer, when requested with `since` and `until`
+    #[serde(default)]
+    pub rendered_schedule_entries: Vec<RenderedScheduleEntry>,
+
+    /// The percentage of the requested time range covered by this layer
+    #[serde(default)]
+    pub rendered_coverage_percentage: Option<f64>,
+}
+
+/// A user in a schedule layer's rotation
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ScheduleLayerUser {
+    pub user: Reference,
+}
+
+/// A restriction on when a schedule layer is on call
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Restriction {
+    /// Either `daily_restriction` or `weekly_restriction`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The time of day the restriction starts, for example `08:00:00`
+    pub start_time_of_day: String,
+
+    /// The day of the week the restriction starts, 1 (Monday) through 7, for weekly restrictions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_day_of_week: Option<u8>,
+
+    /// The duration of the restriction
+    #[serde(rename = "duration_seconds", with = "super::duration::seconds")]
+    pub duration: Duration,
+}
+
+impl Restriction {
+    /// Restrict the layer to a window starting at `start_time_of_day` every day
+    pub fn daily<S>(start_time_of_day: S, duration: Duration) -> Restriction
+        where S: Into<String>
+    {
+        Restriction {
+            kind: "daily_restriction".to_owned(),
+            start_time_of_day: start_time_of_day.into(),
+            start_day_of_week: None,
+            duration,
+        }
+    }
+
+    /// Restrict the layer to a window starting at `start_time_of_day` on `start_day_of_week`
+    /// every week, where days are numbered 1 (Monday) through 7
+    pub fn weekly<S>(start_day_of_week: u8,
+                     start_time_of_day: S,
+                     duration: Duration) -> Restriction
+        where S: Into<String>
+    {
+        Restriction {
+            kind: "weekly_restriction".to_owned(),
+            start_time_of_day: start_time_of_day.into(),
+            start_day_of_week: Some(start_day_of_week),
+            duration,
+        }
+    }
+}
+
+/// A rendered schedule, made of consecutive entries
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct SubSchedule {
+    /// The name of the subschedule
+    pub name: String,
+
+    /// The entries of the rendered schedule
+    #[serde(default)]
+    pub rendered_schedule_entries: Vec<RenderedScheduleEntry>,
+
+    /// The percentage of the requested time range covered by the subschedule
+    #[serde(default)]
+    pub rendered_coverage_percentage: Option<f64>,
+}
+
+/// A period of time during which a user is on call
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct RenderedScheduleEntry {
+    /// The start time of the entry
+    pub start: String,
+
+    /// The end time of the entry
+    pub end: String,
+
+    /// The user on call for the entry
+    pub user: Reference,
+}
+
+#[cfg(feature = "chrono")]
+impl RenderedScheduleEntry {
+    /// The start time of the entry, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn start_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.start)
+    }
+
+    /// The end time of the entry, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn end_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.end)
+    }
+}
+
+/// List on-call schedules
+#[derive(Debug, Default)]
+pub struct ListSchedules<'a> {
+    query: Option<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    time_zone: Option<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListSchedules<'a> {
+    /// Create a request listing all schedules
+    pub fn new() -> ListSchedules<'a> {
+        ListSchedules::default()
+    }
+
+    /// Only return schedules whose names contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Include additional models in the response, for example `"schedule_layers"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+
+    /// Render times in `time_zone` rather than the account's time zone
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListSchedules<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListSchedules<'a> {
+    type Response = Response<Page<Schedule>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        query.push_array("include", &self.include);
+        if let Some(ref time_zone) = self.time_zone {
+            query.push("time_zone", time_zone);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/schedules").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Schedule>>> {
+        get_page(status, headers, body, "schedules")
+    }
+}
+
+/// Get a schedule, optionally rendering its entries over a time window
+#[derive(Debug)]
+pub struct GetSchedule<'a> {
+    id: Cow<'a, str>,
+    since: Option<Cow<'a, str>>,
+    until: Option<Cow<'a, str>>,
+    time_zone: Option<Cow<'a, str>>,
+}
+
+impl<'a> GetSchedule<'a> {
+    /// Create a request for schedule `id`
+    pub fn new<S>(id: S) -> GetSchedule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetSchedule {
+            id: id.into(),
+            since: None,
+            until: None,
+            time_zone: None,
+        }
+    }
+
+    /// Set the start of the window over which entries are rendered, in ISO 8601
+    pub fn set_since<S>(mut self, since: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.since = Some(since.into_timestamp());
+        self
+    }
+
+    /// Set the end of the window over which entries are rendered, in ISO 8601
+    pub fn set_until<S>(mut self, until: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.until = Some(until.into_timestamp());
+        self
+    }
+
+    /// Render times in `time_zone` rather than the schedule's time zone
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetSchedule<'a> {
+    type Response = Response<Schedule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref since) = self.since {
+            query.push("since", since);
+        }
+        if let Some(ref until) = self.until {
+            query.push("until", until);
+        }
+        if let Some(ref time_zone) = self.time_zone {
+            query.push("time_zone", time_zone);
+        }
+
+        query.url(&format!("/schedules/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Schedule>> {
+        get_response(status, headers, body, "schedule")
+    }
+}
+
+/// Writable fields of a schedule
+///
+/// Used to describe a new schedule with `CreateSchedule`, or changes to an existing one with
+/// `UpdateSchedule`. Fields which aren't set are left out of the request.
+#[derive(Debug, Serialize)]
+pub struct ScheduleFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    time_zone: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    schedule_layers: Option<Vec<ScheduleLayerFields<'a>>>,
+}
+
+impl<'a> Default for ScheduleFields<'a> {
+    fn default() -> ScheduleFields<'a> {
+        ScheduleFields::new()
+    }
+}
+
+impl<'a> ScheduleFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> ScheduleFields<'a> {
+        ScheduleFields {
+            kind: "schedule",
+            name: None,
+            time_zone: None,
+            description: None,
+            schedule_layers: None,
+        }
+    }
+
+    /// Set the name of the schedule
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the time zone of the schedule, for example `America/New_York`
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+
+    /// Set the description of the schedule
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a layer to the schedule
+    ///
+    /// When updating a schedule, the layers added replace all of the schedule's existing layers;
+    /// give existing layers their ID with `ScheduleLayerFields::set_id` to modify them in place.
+    pub fn add_schedule_layer(mut self, layer: ScheduleLayerFields<'a>) -> Self {
+        self.schedule_layers.get_or_insert_with(Vec::new).push(layer);
+        self
+    }
+}
+
+/// Writable fields of a schedule layer
+#[derive(Debug, Serialize)]
+pub struct ScheduleLayerFields<'a> {
+    #[serde(skip_serializing_if="Option::is_none")]
+    id: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    start: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    end: Option<Cow<'a, str>>,
+
+    rotation_virtual_start: Cow<'a, str>,
+
+    #[serde(rename = "rotation_turn_length_seconds", with = "super::duration::seconds")]
+    rotation_turn_length: Duration,
+
+    users: Vec<LayerUser>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    restrictions: Vec<Restriction>,
+}
+
+#[derive(Debug, Serialize)]
+struct LayerUser {
+    user: Reference,
+}
+
+impl<'a> ScheduleLayerFields<'a> {
+    /// Create a layer
+    ///
+    /// * **start**: The start time of the layer, in ISO 8601.
+    ///
+    /// * **rotation_virtual_start**: The effective start time of the layer, which can be before
+    ///   `start`; the rotation is computed as though it started at this time.
+    ///
+    /// * **rotation_turn_length**: The duration of each on-call shift, in whole seconds.
+    pub fn new<S, T>(start: S,
+                     rotation_virtual_start: T,
+                     rotation_turn_length: Duration) -> ScheduleLayerFields<'a>
+        where S: Timestamp<'a>,
+              T: Timestamp<'a>
+    {
+        ScheduleLayerFields {
+            id: None,
+            name: None,
+            start: start.into_timestamp(),
+            end: None,
+            rotation_virtual_start: rotation_virtual_start.into_timestamp(),
+            rotation_turn_length,
+            users: Vec::new(),
+            restrictions: Vec::new(),
+        }
+    }
+
+    /// Set the ID of an existing layer being modified
+    pub fn set_id<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Set the name of the layer
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the end time of the layer; layers without one run indefinitely
+    pub fn set_end<S>(mut self, end: S) -> Self
+        where S: Timestamp<'a>
+    {
+        self.end = Some(end.into_timestamp());
+        self
+    }
+
+    /// Add user `user_id` to the end of the rotation
+    pub fn add_user<S>(mut self, user_id: S) -> Self
+        where S: Into<String>
+    {
+        self.users.push(LayerUser { user: Reference::new(user_id, "user_reference") });
+        self
+    }
+
+    /// Restrict when the layer is on call
+    pub fn add_restriction(mut self, restriction: Restriction) -> Self {
+        self.restrictions.push(restriction);
+        self
+    }
+}
+
+/// Create a new on-call schedule
+#[derive(Debug, Serialize)]
+pub struct CreateSchedule<'a> {
+    schedule: ScheduleFields<'a>,
+}
+
+impl<'a> CreateSchedule<'a> {
+    /// Create a request for a new schedule described by `fields`
+    ///
+    /// PagerDuty requires at least a time zone and one layer.
+    pub fn new(fields: ScheduleFields<'a>) -> CreateSchedule<'a> {
+        CreateSchedule {
+            schedule: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateSchedule<'a> {
+    type Response = Response<Schedule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/schedules", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Schedule>> {
+        get_response(status, headers, body, "schedule")
+    }
+}
+
+/// Update an existing on-call schedule
+#[derive(Debug, Serialize)]
+pub struct UpdateSchedule<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    schedule: ScheduleFields<'a>,
+}
+
+impl<'a> UpdateSchedule<'a> {
+    /// Create a request applying `fields` to schedule `id`
+    pub fn new<S>(id: S, fields: ScheduleFields<'a>) -> UpdateSchedule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateSchedule {
+            id: id.into(),
+            schedule: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateSchedule<'a> {
+    type Response = Response<Schedule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/schedules/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Schedule>> {
+        get_response(status, headers, body, "schedule")
+    }
+}
+
+/// Delete an on-call schedule
+#[derive(Debug)]
+pub struct DeleteSchedule<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteSchedule<'a> {
+    /// Create a request deleting schedule `id`
+    pub fn new<S>(id: S) -> DeleteSchedule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteSchedule {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteSchedule<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/schedules/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// An override of a schedule, putting a user on call for a period of time
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Override {
+    /// The override's ID
+    #[serde(default)]
+    pub id: Option<String>,
+
+    /// The start time of the override
+    pub start: String,
+
+    /// The end time of the override
+    pub end: String,
+
+    /// The user on call during the override
+    pub user: Reference,
+}
+
+#[cfg(feature = "chrono")]
+impl Override {
+    /// The start time of the override, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn start_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.start)
+    }
+
+    /// The end time of the override, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn end_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.end)
+    }
+}
+
+/// The outcome of creating one override in a bulk request
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct OverrideResult {
+    /// The HTTP status for this override; 201 if it was created
+    pub status: u16,
+
+    /// The created override
+    #[serde(rename = "override", default)]
+    pub schedule_override: Option<Override>,
+
+    /// Why the override couldn't be created
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// Writable fields of an override
+#[derive(Debug, Serialize)]
+pub struct OverrideFields<'a> {
+    start: Cow<'a, str>,
+    end: Cow<'a, str>,
+    user: Reference,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    time_zone: Option<Cow<'a, str>>,
+}
+
+impl<'a> OverrideFields<'a> {
+    /// Put user `user_id` on call from `start` until `end`, both in ISO 8601
+    pub fn new<S, U>(start: S, end: S, user_id: U) -> OverrideFields<'a>
+        where S: Timestamp<'a>,
+              U: Into<String>
+    {
+        OverrideFields {
+            start: start.into_timestamp(),
+            end: end.into_timestamp(),
+            user: Reference::new(user_id, "user_reference"),
+            time_zone: None,
+        }
+    }
+
+    /// Set the time zone `start` and `end` are interpreted in, if they don't include an offset
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+}
+
+/// List overrides of a schedule in a given time range
+#[derive(Debug)]
+pub struct ListOverrides<'a> {
+    schedule_id: Cow<'a, str>,
+    since: Cow<'a, str>,
+    until: Cow<'a, str>,
+    editable: bool,
+}
+
+impl<'a> ListOverrides<'a> {
+    /// Create a request listing the overrides of schedule `schedule_id` between `since` and
+    /// `until`, both in ISO 8601
+    pub fn new<S, T>(schedule_id: S, since: T, until: T) -> ListOverrides<'a>
+        where S: Into<Cow<'a, str>>,
+              T: Timestamp<'a>
+    {
+        ListOverrides {
+            schedule_id: schedule_id.into(),
+            since: since.into_timestamp(),
+            until: until.into_timestamp(),
+            editable: false,
+        }
+    }
+
+    /// Only return overrides which have not yet ended and can be edited
+    pub fn set_editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+}
+
+impl<'a> Requestable for ListOverrides<'a> {
+    type Response = Response<Vec<Override>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push("since", &self.since);
+        query.push("until", &self.until);
+        if self.editable {
+            query.push_bool("editable", true);
+        }
+
+        query.url(&format!("/schedules/{}/overrides", segment(&self.schedule_id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<Override>>> {
+        get_response(status, headers, body, "overrides")
+    }
+}
+
+/// Create an override on a schedule
+#[derive(Debug, Serialize)]
+pub struct CreateOverride<'a> {
+    #[serde(skip_serializing)]
+    schedule_id: Cow<'a, str>,
+
+    #[serde(rename = "override")]
+    schedule_override: OverrideFields<'a>,
+}
+
+impl<'a> CreateOverride<'a> {
+    /// Create a request adding `fields` as an override of schedule `schedule_id`
+    pub fn new<S>(schedule_id: S, fields: OverrideFields<'a>) -> CreateOverride<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateOverride {
+            schedule_id: schedule_id.into(),
+            schedule_override: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateOverride<'a> {
+    type Response = Response<Override>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/schedules/{}/overrides", BASE_URL, segment(&self.schedule_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Override>> {
+        get_response(status, headers, body, "override")
+    }
+}
+
+/// Create several overrides on a schedule at once
+///
+/// Each override succeeds or fails independently; the response has one
+/// [`OverrideResult`](struct.OverrideResult.html) per override, in order.
+#[derive(Debug, Serialize)]
+pub struct CreateOverrides<'a> {
+    #[serde(skip_serializing)]
+    schedule_id: Cow<'a, str>,
+
+    overrides: Vec<OverrideFields<'a>>,
+}
+
+impl<'a> CreateOverrides<'a> {
+    /// Create a request adding overrides to schedule `schedule_id`
+    pub fn new<S>(schedule_id: S) -> CreateOverrides<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateOverrides {
+            schedule_id: schedule_id.into(),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Add an override to the request
+    pub fn add_override(mut self, fields: OverrideFields<'a>) -> Self {
+        self.overrides.push(fields);
+        self
+    }
+}
+
+impl<'a> Requestable for CreateOverrides<'a> {
+    type Response = Response<Vec<OverrideResult>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/schedules/{}/overrides", BASE_URL, segment(&self.schedule_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<OverrideResult>>> {
+        get_body_response(status, headers, body)
+    }
+}
+
+/// Remove an override from a schedule
+///
+/// Overrides which are in progress are truncated rather than removed.
+#[derive(Debug)]
+pub struct DeleteOverride<'a> {
+    schedule_id: Cow<'a, str>,
+    override_id: Cow<'a, str>,
+}
+
+impl<'a> DeleteOverride<'a> {
+    /// Create a request deleting override `override_id` of schedule `schedule_id`
+    pub fn new<S, T>(schedule_id: S, override_id: T) -> DeleteOverride<'a>
+        where S: Into<Cow<'a, str>>,
+              T: Into<Cow<'a, str>>
+    {
+        DeleteOverride {
+            schedule_id: schedule_id.into(),
+            override_id: override_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteOverride<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/schedules/{}/overrides/{}",
+                BASE_URL,
+                segment(&self.schedule_id),
+                segment(&self.override_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List schedules
+pub fn list(auth: &AuthToken,
+            request: &ListSchedules) -> request::Result<Response<Page<Schedule>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single schedule
+pub fn get(auth: &AuthToken, request: &GetSchedule) -> request::Result<Response<Schedule>> {
+    request::perform(auth, request)
+}
+
+/// Create a schedule
+pub fn create(auth: &AuthToken, request: &CreateSchedule) -> request::Result<Response<Schedule>> {
+    request::perform(auth, request)
+}
+
+/// Update a schedule
+pub fn update(auth: &AuthToken, request: &UpdateSchedule) -> request::Result<Response<Schedule>> {
+    request::perform(auth, request)
+}
+
+/// Delete a schedule
+pub fn delete(auth: &AuthToken, request: &DeleteSchedule) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List the overrides of a schedule
+pub fn list_overrides(auth: &AuthToken,
+                      request: &ListOverrides) -> request::Result<Response<Vec<Override>>> {
+    request::perform(auth, request)
+}
+
+/// Create an override on a schedule
+pub fn create_override(auth: &AuthToken,
+                       request: &CreateOverride) -> request::Result<Response<Override>> {
+    request::perform(auth, request)
+}
+
+/// Create several overrides on a schedule
+pub fn create_overrides(auth: &AuthToken, request: &CreateOverrides)
+    -> request::Result<Response<Vec<OverrideResult>>>
+{
+    request::perform(auth, request)
+}
+
+/// Delete an override from a schedule
+pub fn delete_override(auth: &AuthToken,
+                       request: &DeleteOverride) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{CreateOverrides, CreateSchedule, DeleteOverride, GetSchedule, OverrideFields,
+                Restriction, ScheduleFields, ScheduleLayerFields};
+
+    #[test]
+    fn create_overrides_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "overrides": [
+                {
+                    "start": "2012-07-01T00:00:00-04:00",
+                    "end": "2012-07-02T00:00:00-04:00",
+                    "user": { "id": "PEYSGVA", "type": "user_reference" }
+                }
+            ]
+        })).expect("expected is valid json");
+
+        let request = CreateOverrides::new("PI7DH85")
+                          .add_override(OverrideFields::new("2012-07-01T00:00:00-04:00",
+                                                            "2012-07-02T00:00:00-04:00",
+                                                            "PEYSGVA"));
+        let actual: Json = from_str(&request.body()).unwrap();
+        assert_eq!(actual, expected);
+
+        let body = stringify!([
+            {
+                "status": 201,
+                "override": {
+                    "id": "PQ47DCP",
+                    "start": "2012-07-01T00:00:00-04:00",
+                    "end": "2012-07-02T00:00:00-04:00",
+                    "user": { "id": "PEYSGVA", "type": "user_reference" }
+                }
+            },
+            {
+                "status": 400,
+                "errors": ["Override must end after its start"]
+            }
+        ]);
+
+        let res = CreateOverrides::get_response(StatusCode::Created, &Headers::new(), body);
+        let results = res.unwrap().success().unwrap();
+
+        assert_eq!(results[0].schedule_override.as_ref().unwrap().id, Some("PQ47DCP".to_owned()));
+        assert_eq!(results[1].status, 400);
+        assert_eq!(results[1].errors.len(), 1);
+    }
+
+    #[test]
+    fn delete_override_url() {
+        let override_id = String::from("PEYSGVA");
+        let request = DeleteOverride::new("PI7DH85", override_id);
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/schedules/PI7DH85/overrides/PEYSGVA");
+    }
+
+    #[test]
+    fn create_schedule_to_json() {
+        let expected: Json = from_str(stringify!({
+            "schedule": {
+                "type": "schedule",
+                "name": "Daily Engineering Rotation",
+                "time_zone": "America/New_York",
+                "schedule_layers": [
+                    {
+                        "name": "Night Shift",
+                        "start": "2015-11-06T20:00:00-05:00",
+                        "rotation_virtual_start": "2015-11-06T20:00:00-05:00",
+                        "rotation_turn_length_seconds": 86400,
+                        "users": [
+                            { "user": { "id": "PXPGF42", "type": "user_reference" } }
+                        ],
+                        "restrictions": [
+                            {
+                                "type": "weekly_restriction",
+                                "start_day_of_week": 1,
+                                "start_time_of_day": "08:00:00",
+                                "duration_seconds": 32400
+                            }
+                        ]
+                    }
+                ]
+            }
+        })).expect("expected is valid json");
+
+        let layer = ScheduleLayerFields::new("2015-11-06T20:00:00-05:00",
+                                             String::from("2015-11-06T20:00:00-05:00"),
+                                             Duration::from_secs(86400))
+                        .set_name("Night Shift")
+                        .add_user("PXPGF42")
+                        .add_restriction(Restriction::weekly(1,
+                                                             "08:00:00",
+                                                             Duration::from_secs(32400)));
+        let fields = ScheduleFields::new()
+                         .set_name("Daily Engineering Rotation")
+                         .set_time_zone("America/New_York")
+                         .add_schedule_layer(layer);
+        let actual: Json = from_str(&CreateSchedule::new(fields).body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn get_schedule_url() {
+        let request = GetSchedule::new("PI7DH85")
+                          .set_since("2015-11-08T00:00:00-05:00")
+                          .set_until("2015-11-09T00:00:00-05:00");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/schedules/PI7DH85?\
+                    since=2015-11-08T00%3A00%3A00-05%3A00&until=2015-11-09T00%3A00%3A00-05%3A00");
+    }
+
+    #[test]
+    fn schedule_from_json() {
+        let body = stringify!({
+            "schedule": {
+                "id": "PI7DH85",
+                "type": "schedule",
+                "summary": "Daily Engineering Rotation",
+                "name": "Daily Engineering Rotation",
+                "time_zone": "America/New_York",
+                "schedule_layers": [
+                    {
+                        "name": "Layer 1",
+                        "start": "2015-11-06T20:00:00-05:00",
+                        "end": null,
+                        "rotation_virtual_start": "2015-11-06T20:00:00-05:00",
+                        "rotation_turn_length_seconds": 86400,
+                        "users": [
+                            { "user": { "id": "PXPGF42", "type": "user_reference" } }
+                        ],
+                        "restrictions": [
+                            {
+                                "type": "daily_restriction",
+                                "start_time_of_day": "08:00:00",
+                                "duration_seconds": 32400
+                            }
+                        ]
+                    }
+                ],
+                "final_schedule": {
+                    "name": "Final Schedule",
+                    "rendered_schedule_entries": [
+                        {
+                            "start": "2015-11-08T08:00:00-05:00",
+                            "end": "2015-11-08T17:00:00-05:00",
+                            "user": { "id": "PXPGF42", "type": "user_reference" }
+                        }
+                    ],
+                    "rendered_coverage_percentage": 37.5
+                },
+                "escalation_policies": [{ "id": "PT20YPA", "type": "escalation_policy_reference" }]
+            }
+        });
+
+        let res = GetSchedule::get_response(StatusCode::Ok, &Headers::new(), body).unwrap();
+        let schedule = res.success().unwrap();
+
+        let layer = &schedule.schedule_layers[0];
+        assert_eq!(layer.rotation_turn_length, Duration::from_secs(86400));
+        assert_eq!(layer.end, None);
+        assert_eq!(layer.users[0].user.id, "PXPGF42");
+        assert_eq!(layer.restrictions[0].duration, Duration::from_secs(32400));
+
+        let entries = &schedule.final_schedule.as_ref().unwrap().rendered_schedule_entries;
+        assert_eq!(entries[0].user.id, "PXPGF42");
+    }
+}
@@ -0,0 +1,1232 @@
+//! Services
+//!
+//! A service represents something you monitor, like a web service, email service, or database
+//! service. Incidents are opened on services, and a service's escalation policy decides who is
+//! notified about them.
+//!
+//! Events reach a service through its integrations. Creating an integration generates the
+//! integration key used as the routing key of the [events API](../../events_v2/index.html).
+//! [`routing_key`](fn.routing_key.html) finds or creates the key of a service by its name.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// The current state of a service
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceStatus {
+    /// No open incidents
+    Active,
+
+    /// Open incidents, all of which are low urgency or acknowledged
+    Warning,
+
+    /// Open, unacknowledged high urgency incidents
+    Critical,
+
+    /// In a maintenance window; no incidents are opened
+    Maintenance,
+
+    /// Disabled; no incidents are opened
+    Disabled,
+}
+
+/// What a service creates from the events it receives
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertCreation {
+    /// Each event opens or updates an incident directly
+    CreateIncidents,
+
+    /// Events create alerts, which are grouped into incidents
+    CreateAlertsAndIncidents,
+}
+
+/// The urgency given to incidents
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentUrgency {
+    High,
+    Low,
+
+    /// Urgency is derived from the severity of the alert
+    SeverityBased,
+}
+
+/// How a service decides the urgency of its incidents
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IncidentUrgencyRule {
+    /// Every incident has the same urgency
+    Constant {
+        urgency: IncidentUrgency,
+    },
+
+    /// Urgency depends on whether the incident is opened within the service's support hours
+    UseSupportHours {
+        during_support_hours: Box<IncidentUrgencyRule>,
+        outside_support_hours: Box<IncidentUrgencyRule>,
+    },
+}
+
+impl IncidentUrgencyRule {
+    /// Give every incident urgency `urgency`
+    pub fn constant(urgency: IncidentUrgency) -> IncidentUrgencyRule {
+        IncidentUrgencyRule::Constant { urgency }
+    }
+
+    /// Give incidents urgency `during` within support hours, and `outside` otherwise
+    pub fn use_support_hours(during: IncidentUrgency,
+                             outside: IncidentUrgency) -> IncidentUrgencyRule {
+        IncidentUrgencyRule::UseSupportHours {
+            during_support_hours: Box::new(IncidentUrgencyRule::constant(during)),
+            outside_support_hours: Box::new(IncidentUrgencyRule::constant(outside)),
+        }
+    }
+}
+
+/// The hours during which a service is supported
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SupportHours {
+    /// Always `fixed_time_per_day`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The time zone of `start_time` and `end_time`, such as `America/Lima`
+    pub time_zone: String,
+
+    /// The time of day support starts, for example `09:00:00`
+    pub start_time: String,
+
+    /// The time of day support ends, for example `17:00:00`
+    pub end_time: String,
+
+    /// The days of the week with support, 1 (Monday) through 7
+    pub days_of_week: Vec<u8>,
+}
+
+impl SupportHours {
+    /// Support from `start_time` until `end_time` in `time_zone` on `days_of_week`, numbered 1
+    /// (Monday) through 7
+    pub fn new<S>(time_zone: S, start_time: S, end_time: S, days_of_week: Vec<u8>) -> SupportHours
+        where S: Into<String>
+    {
+        SupportHours {
+            kind: "fixed_time_per_day".to_owned(),
+            time_zone: time_zone.into(),
+            start_time: start_time.into(),
+            end_time: end_time.into(),
+            days_of_week,
+        }
+    }
+}
+
+/// A service
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Service {
+    /// The service's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the service
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the service is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the service is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the service
+    pub name: String,
+
+    /// The user-provided description of the service
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The current state of the service
+    pub status: ServiceStatus,
+
+    /// How long incidents stay open before being resolved automatically, or `None` if they never
+    /// are
+    #[serde(default, deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub auto_resolve_timeout: Option<Duration>,
+
+    /// How long acknowledged incidents stay acknowledged before returning to triggered, or `None`
+    /// if they never do
+    #[serde(default, deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub acknowledgement_timeout: Option<Duration>,
+
+    /// The time at which the service was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+
+    /// The time at which the most recent incident on the service was opened
+    #[serde(default)]
+    pub last_incident_timestamp: Option<String>,
+
+    /// The escalation policy used by the service
+    pub escalation_policy: Reference,
+
+    /// What the service creates from the events it receives
+    #[serde(default)]
+    pub alert_creation: Option<AlertCreation>,
+
+    /// How alerts are grouped into incidents, such as `time` or `intelligent`, if at all
+    #[serde(default)]
+    pub alert_grouping: Option<String>,
+
+    /// How long alerts are grouped into the same incident, when grouping by time
+    #[serde(default, deserialize_with = "super::duration::optional_seconds::deserialize")]
+    pub alert_grouping_timeout: Option<Duration>,
+
+    /// How the urgency of incidents is decided
+    #[serde(default)]
+    pub incident_urgency_rule: Option<IncidentUrgencyRule>,
+
+    /// The hours during which the service is supported
+    #[serde(default)]
+    pub support_hours: Option<SupportHours>,
+
+    /// Integrations sending events to the service
+    ///
+    /// Only the ID and summary of each are present unless `"integrations"` is included in the
+    /// request.
+    #[serde(default)]
+    pub integrations: Vec<Integration>,
+
+    /// Teams associated with the service
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+}
+
+impl Service {
+    /// The routing key of the service's first Events API v2 integration, if it has one
+    ///
+    /// Keys are only present when the service was requested with `"integrations"` included.
+    pub fn routing_key(&self) -> Option<&str> {
+        self.integrations
+            .iter()
+            .filter(|integration| integration.kind == EVENTS_API_V2_INTEGRATION)
+            .filter_map(|integration| integration.integration_key.as_ref())
+            .map(|key| &key[..])
+            .next()
+    }
+}
+
+/// The type of integrations receiving events from the Events API v2
+pub const EVENTS_API_V2_INTEGRATION: &str = "events_api_v2_inbound_integration";
+
+/// An integration through which a service receives events
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Integration {
+    /// The integration's ID
+    pub id: String,
+
+    /// The type of integration, such as `events_api_v2_inbound_integration`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// A short-form, server-generated string describing the integration
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the integration is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the integration is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the integration
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The service the integration belongs to
+    #[serde(default)]
+    pub service: Option<Reference>,
+
+    /// The time at which the integration was created
+    #[serde(default)]
+    pub created_at: Option<String>,
+
+    /// The vendor the integration is for, such as a monitoring tool
+    #[serde(default)]
+    pub vendor: Option<Reference>,
+
+    /// The key events are sent with, for integrations receiving events through the API
+    #[serde(default)]
+    pub integration_key: Option<String>,
+
+    /// The address events are sent to, for email integrations
+    #[serde(default)]
+    pub integration_email: Option<String>,
+}
+
+/// List services, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListServices<'a> {
+    query: Option<Cow<'a, str>>,
+    team_ids: Vec<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    sort_by: Option<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListServices<'a> {
+    /// Create a request listing all services
+    pub fn new() -> ListServices<'a> {
+        ListServices::default()
+    }
+
+    /// Only return services whose names contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Only return services belonging to team `team_id`; may be called more than once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Include additional models in the response, such as `"integrations"` or `"teams"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+
+    /// Sort results by `name` or `name:asc` or `name:desc`
+    pub fn set_sort_by<S>(mut self, sort_by: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.sort_by = Some(sort_by.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListServices<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListServices<'a> {
+    type Response = Response<Page<Service>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        query.push_array("team_ids", &self.team_ids);
+        query.push_array("include", &self.include);
+        if let Some(ref sort_by) = self.sort_by {
+            query.push("sort_by", sort_by);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/services").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Service>>> {
+        get_page(status, headers, body, "services")
+    }
+}
+
+/// Get details about an existing service
+#[derive(Debug)]
+pub struct GetService<'a> {
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetService<'a> {
+    /// Create a request for service `id`
+    pub fn new<S>(id: S) -> GetService<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetService {
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional models in the response, such as `"integrations"` or `"teams"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetService<'a> {
+    type Response = Response<Service>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url(&format!("/services/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Service>> {
+        get_response(status, headers, body, "service")
+    }
+}
+
+/// Writable fields of a service
+///
+/// Used to describe a new service with `CreateService`, or changes to an existing one with
+/// `UpdateService`. Fields which aren't set are left out of the request.
+#[derive(Debug, Serialize)]
+pub struct ServiceFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    escalation_policy: Option<Reference>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    auto_resolve_timeout: Option<Option<u64>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    acknowledgement_timeout: Option<Option<u64>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    alert_creation: Option<AlertCreation>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    alert_grouping: Option<Option<Cow<'a, str>>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    alert_grouping_timeout: Option<Option<u64>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    incident_urgency_rule: Option<IncidentUrgencyRule>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    support_hours: Option<SupportHours>,
+}
+
+impl<'a> Default for ServiceFields<'a> {
+    fn default() -> ServiceFields<'a> {
+        ServiceFields::new()
+    }
+}
+
+impl<'a> ServiceFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> ServiceFields<'a> {
+        ServiceFields {
+            kind: "service",
+            name: None,
+            description: None,
+            escalation_policy: None,
+            auto_resolve_timeout: None,
+            acknowledgement_timeout: None,
+            alert_creation: None,
+            alert_grouping: None,
+            alert_grouping_timeout: None,
+            incident_urgency_rule: None,
+            support_hours: None,
+        }
+    }
+
+    /// Set the name of the service
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the service description
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the escalation policy used by the service
+    pub fn set_escalation_policy<S>(mut self, escalation_policy_id: S) -> Self
+        where S: Into<String>
+    {
+        let policy = Reference::new(escalation_policy_id, "escalation_policy_reference");
+        self.escalation_policy = Some(policy);
+        self
+    }
+
+    /// Set how long incidents stay open before being resolved automatically, in whole seconds;
+    /// `None` disables automatic resolution
+    pub fn set_auto_resolve_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.auto_resolve_timeout = Some(timeout.map(|timeout| timeout.as_secs()));
+        self
+    }
+
+    /// Set how long acknowledged incidents stay acknowledged before returning to triggered, in
+    /// whole seconds; `None` keeps them acknowledged
+    pub fn set_acknowledgement_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.acknowledgement_timeout = Some(timeout.map(|timeout| timeout.as_secs()));
+        self
+    }
+
+    /// Set what the service creates from the events it receives
+    pub fn set_alert_creation(mut self, alert_creation: AlertCreation) -> Self {
+        self.alert_creation = Some(alert_creation);
+        self
+    }
+
+    /// Set how alerts are grouped into incidents, such as `time` or `intelligent`; `None`
+    /// disables grouping
+    ///
+    /// Grouping requires `AlertCreation::CreateAlertsAndIncidents`.
+    pub fn set_alert_grouping<S>(mut self, alert_grouping: Option<S>) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.alert_grouping = Some(alert_grouping.map(|grouping| grouping.into()));
+        self
+    }
+
+    /// Set how long alerts are grouped into the same incident when grouping by time, in whole
+    /// seconds; `None` groups them until the incident is resolved
+    pub fn set_alert_grouping_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.alert_grouping_timeout = Some(timeout.map(|timeout| timeout.as_secs()));
+        self
+    }
+
+    /// Set how the urgency of incidents is decided
+    pub fn set_incident_urgency_rule(mut self, rule: IncidentUrgencyRule) -> Self {
+        self.incident_urgency_rule = Some(rule);
+        self
+    }
+
+    /// Set the hours during which the service is supported
+    ///
+    /// Required when the urgency rule is `IncidentUrgencyRule::UseSupportHours`.
+    pub fn set_support_hours(mut self, support_hours: SupportHours) -> Self {
+        self.support_hours = Some(support_hours);
+        self
+    }
+}
+
+/// Create a new service
+#[derive(Debug, Serialize)]
+pub struct CreateService<'a> {
+    service: ServiceFields<'a>,
+}
+
+impl<'a> CreateService<'a> {
+    /// Create a request for a new service described by `fields`
+    ///
+    /// PagerDuty requires at least a name and an escalation policy.
+    pub fn new(fields: ServiceFields<'a>) -> CreateService<'a> {
+        CreateService {
+            service: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateService<'a> {
+    type Response = Response<Service>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/services", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Service>> {
+        get_response(status, headers, body, "service")
+    }
+}
+
+/// Update an existing service
+#[derive(Debug, Serialize)]
+pub struct UpdateService<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    service: ServiceFields<'a>,
+}
+
+impl<'a> UpdateService<'a> {
+    /// Create a request applying `fields` to service `id`
+    pub fn new<S>(id: S, fields: ServiceFields<'a>) -> UpdateService<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateService {
+            id: id.into(),
+            service: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateService<'a> {
+    type Response = Response<Service>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/services/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Service>> {
+        get_response(status, headers, body, "service")
+    }
+}
+
+/// Delete an existing service
+///
+/// Incidents on the service are deleted along with it.
+#[derive(Debug)]
+pub struct DeleteService<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteService<'a> {
+    /// Create a request deleting service `id`
+    pub fn new<S>(id: S) -> DeleteService<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteService {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteService<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/services/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Writable fields of an integration
+#[derive(Debug, Serialize)]
+pub struct IntegrationFields<'a> {
+    #[serde(rename = "type")]
+    kind: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    vendor: Option<Reference>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    integration_email: Option<Cow<'a, str>>,
+}
+
+impl<'a> IntegrationFields<'a> {
+    /// Create fields for an integration of type `kind`, such as
+    /// `events_api_v2_inbound_integration` or `generic_email_inbound_integration`
+    pub fn new<S>(kind: S) -> IntegrationFields<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        IntegrationFields {
+            kind: kind.into(),
+            name: None,
+            vendor: None,
+            integration_email: None,
+        }
+    }
+
+    /// Set the name of the integration
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the vendor the integration is for
+    pub fn set_vendor<S>(mut self, vendor_id: S) -> Self
+        where S: Into<String>
+    {
+        self.vendor = Some(Reference::new(vendor_id, "vendor_reference"));
+        self
+    }
+
+    /// Set the address events are sent to, for email integrations
+    pub fn set_integration_email<S>(mut self, integration_email: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.integration_email = Some(integration_email.into());
+        self
+    }
+}
+
+/// Create an integration on a service
+#[derive(Debug, Serialize)]
+pub struct CreateIntegration<'a> {
+    #[serde(skip_serializing)]
+    service_id: Cow<'a, str>,
+
+    integration: IntegrationFields<'a>,
+}
+
+impl<'a> CreateIntegration<'a> {
+    /// Create a request adding the integration described by `fields` to service `service_id`
+    pub fn new<S>(service_id: S, fields: IntegrationFields<'a>) -> CreateIntegration<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateIntegration {
+            service_id: service_id.into(),
+            integration: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateIntegration<'a> {
+    type Response = Response<Integration>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/services/{}/integrations", BASE_URL, segment(&self.service_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Integration>> {
+        get_response(status, headers, body, "integration")
+    }
+}
+
+/// Get details about an integration of a service
+#[derive(Debug)]
+pub struct GetIntegration<'a> {
+    service_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetIntegration<'a> {
+    /// Create a request for integration `id` of service `service_id`
+    pub fn new<S>(service_id: S, id: S) -> GetIntegration<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetIntegration {
+            service_id: service_id.into(),
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional models in the response, such as `"services"` or `"vendors"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetIntegration<'a> {
+    type Response = Response<Integration>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url(&format!("/services/{}/integrations/{}",
+                           segment(&self.service_id),
+                           segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Integration>> {
+        get_response(status, headers, body, "integration")
+    }
+}
+
+/// Update an integration of a service
+#[derive(Debug, Serialize)]
+pub struct UpdateIntegration<'a> {
+    #[serde(skip_serializing)]
+    service_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    integration: IntegrationFields<'a>,
+}
+
+impl<'a> UpdateIntegration<'a> {
+    /// Create a request applying `fields` to integration `id` of service `service_id`
+    pub fn new<S>(service_id: S, id: S, fields: IntegrationFields<'a>) -> UpdateIntegration<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateIntegration {
+            service_id: service_id.into(),
+            id: id.into(),
+            integration: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateIntegration<'a> {
+    type Response = Response<Integration>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/services/{}/integrations/{}",
+                BASE_URL,
+                segment(&self.service_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Integration>> {
+        get_response(status, headers, body, "integration")
+    }
+}
+
+/// List services
+pub fn list(auth: &AuthToken, request: &ListServices) -> request::Result<Response<Page<Service>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single service
+pub fn get(auth: &AuthToken, request: &GetService) -> request::Result<Response<Service>> {
+    request::perform(auth, request)
+}
+
+/// Create a service
+pub fn create(auth: &AuthToken, request: &CreateService) -> request::Result<Response<Service>> {
+    request::perform(auth, request)
+}
+
+/// Update a service
+pub fn update(auth: &AuthToken, request: &UpdateService) -> request::Result<Response<Service>> {
+    request::perform(auth, request)
+}
+
+/// Delete a service
+pub fn delete(auth: &AuthToken, request: &DeleteService) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Create an integration on a service
+pub fn create_integration(auth: &AuthToken, request: &CreateIntegration)
+    -> request::Result<Response<Integration>>
+{
+    request::perform(auth, request)
+}
+
+/// Get an integration of a service
+pub fn get_integration(auth: &AuthToken,
+                       request: &GetIntegration) -> request::Result<Response<Integration>> {
+    request::perform(auth, request)
+}
+
+/// Update an integration of a service
+pub fn update_integration(auth: &AuthToken, request: &UpdateIntegration)
+    -> request::Result<Response<Integration>>
+{
+    request::perform(auth, request)
+}
+
+/// The outcome of looking up the routing key of a service by its name
+#[derive(Debug, PartialEq, Eq)]
+pub enum RoutingKey {
+    /// The key of an Events API v2 integration the service already had
+    Existing { service: Service, key: String },
+
+    /// The key of an Events API v2 integration created on the service
+    Created { service: Service, key: String },
+
+    /// An integration was created on the service, but was returned without its key
+    CreatedWithoutKey { service: Service },
+
+    /// No service has exactly the name
+    NotFound,
+}
+
+impl RoutingKey {
+    /// The routing key, if the service was found and has one
+    pub fn key(&self) -> Option<&str> {
+        match *self {
+            RoutingKey::Existing { ref key, .. } | RoutingKey::Created { ref key, .. } => Some(key),
+            RoutingKey::CreatedWithoutKey { .. } | RoutingKey::NotFound => None,
+        }
+    }
+}
+
+/// Get the routing key for sending Events API v2 events to the service named `name`
+///
+/// The key of the service's first Events API v2 integration is returned, and one is created if
+/// the service has none, which requires a token with write access.
+///
+/// # Example
+///
+/// ```no_run
+/// use pagerduty::AuthToken;
+/// use pagerduty::events_v2::{self, Severity, TriggerEvent};
+/// use pagerduty::rest::Response;
+/// use pagerduty::rest::services::{self, RoutingKey};
+///
+/// let auth = AuthToken::new("token");
+///
+/// match services::routing_key(&auth, "My Web App").unwrap() {
+///     Response::Success(RoutingKey::Existing { key, .. }) |
+///     Response::Success(RoutingKey::Created { key, .. }) => {
+///         let event = TriggerEvent::new(key, "Disk full".into(), "db01".into(),
+///                                       Severity::Warning);
+///         events_v2::trigger(&auth, &event).unwrap();
+///     },
+///     Response::Success(RoutingKey::NotFound) => println!("No service named My Web App"),
+///     other => println!("No routing key: {:?}", other),
+/// }
+/// ```
+pub fn routing_key(auth: &AuthToken, name: &str) -> request::Result<Response<RoutingKey>> {
+    let request = ListServices::new().set_query(name).add_include("integrations");
+
+    let mut found = None;
+    for res in request.paginate(auth) {
+        match res?.into_result() {
+            Ok(service) => {
+                if service.name == name {
+                    found = Some(service);
+                    break;
+                }
+            },
+            Err(res) => return Ok(res),
+        }
+    }
+
+    let service = match found {
+        Some(service) => service,
+        None => return Ok(Response::Success(RoutingKey::NotFound)),
+    };
+
+    if let Some(key) = service.routing_key().map(|key| key.to_owned()) {
+        return Ok(Response::Success(RoutingKey::Existing { service, key }));
+    }
+
+    let fields = IntegrationFields::new(EVENTS_API_V2_INTEGRATION).set_name("Events API v2");
+    let res = request::perform(auth, &CreateIntegration::new(&*service.id, fields))?;
+    Ok(res.map(|integration| {
+        match integration.integration_key {
+            Some(key) => RoutingKey::Created { service, key },
+            None => RoutingKey::CreatedWithoutKey { service },
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{AlertCreation, CreateIntegration, CreateService, GetService, IncidentUrgency,
+                IncidentUrgencyRule, IntegrationFields, ListServices, ServiceFields,
+                ServiceStatus, SupportHours};
+
+    #[test]
+    fn create_integration_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "integration": {
+                "type": "events_api_v2_inbound_integration",
+                "name": "Datadog"
+            }
+        })).expect("expected is valid json");
+
+        let fields = IntegrationFields::new("events_api_v2_inbound_integration")
+                         .set_name("Datadog");
+        let request = CreateIntegration::new("PIJ90N7", fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(), "https://api.pagerduty.com/services/PIJ90N7/integrations");
+
+        let body = stringify!({
+            "integration": {
+                "id": "PE1U9CH",
+                "type": "events_api_v2_inbound_integration",
+                "summary": "Datadog",
+                "name": "Datadog",
+                "service": { "id": "PIJ90N7", "type": "service_reference" },
+                "created_at": "2015-10-14T13:47:09-04:00",
+                "integration_key": "f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b"
+            }
+        });
+
+        let res = CreateIntegration::get_response(StatusCode::Created, &Headers::new(), body);
+        let integration = res.unwrap().success().unwrap();
+
+        assert_eq!(integration.integration_key,
+                   Some("f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b".to_owned()));
+        assert_eq!(integration.service.unwrap().id, "PIJ90N7");
+    }
+
+    #[test]
+    fn create_service_to_json() {
+        let expected: Json = from_str(stringify!({
+            "service": {
+                "type": "service",
+                "name": "My Web App",
+                "escalation_policy": { "id": "PWIP6CQ", "type": "escalation_policy_reference" },
+                "auto_resolve_timeout": null,
+                "acknowledgement_timeout": 600,
+                "alert_creation": "create_alerts_and_incidents",
+                "alert_grouping": "time",
+                "alert_grouping_timeout": 2,
+                "incident_urgency_rule": {
+                    "type": "use_support_hours",
+                    "during_support_hours": { "type": "constant", "urgency": "high" },
+                    "outside_support_hours": { "type": "constant", "urgency": "low" }
+                },
+                "support_hours": {
+                    "type": "fixed_time_per_day",
+                    "time_zone": "America/Lima",
+                    "start_time": "09:00:00",
+                    "end_time": "17:00:00",
+                    "days_of_week": [1, 2, 3, 4, 5]
+                }
+            }
+        })).expect("expected is valid json");
+
+        let urgency_rule = IncidentUrgencyRule::use_support_hours(IncidentUrgency::High,
+                                                                  IncidentUrgency::Low);
+        let support_hours = SupportHours::new("America/Lima",
+                                              "09:00:00",
+                                              "17:00:00",
+                                              vec![1, 2, 3, 4, 5]);
+        let fields = ServiceFields::new()
+                         .set_name("My Web App")
+                         .set_escalation_policy("PWIP6CQ")
+                         .set_auto_resolve_timeout(None)
+                         .set_acknowledgement_timeout(Some(Duration::from_secs(600)))
+                         .set_alert_creation(AlertCreation::CreateAlertsAndIncidents)
+                         .set_alert_grouping(Some("time"))
+                         .set_alert_grouping_timeout(Some(Duration::from_secs(2)))
+                         .set_incident_urgency_rule(urgency_rule)
+                         .set_support_hours(support_hours);
+        let actual: Json = from_str(&CreateService::new(fields).body()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn list_services_url() {
+        let request = ListServices::new()
+                          .set_query("mail")
+                          .add_team_id("PQ9K7I8")
+                          .add_include("integrations");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/services?query=mail&team_ids%5B%5D=PQ9K7I8&\
+                    include%5B%5D=integrations");
+    }
+
+    #[test]
+    fn service_from_json() {
+        let body = stringify!({
+            "service": {
+                "id": "PIJ90N7",
+                "summary": "My Application Service",
+                "type": "service",
+                "name": "My Application Service",
+                "auto_resolve_timeout": 14400,
+                "acknowledgement_timeout": null,
+                "created_at": "2015-11-06T11:12:51-05:00",
+                "status": "active",
+                "alert_creation": "create_alerts_and_incidents",
+                "incident_urgency_rule": { "type": "constant", "urgency": "severity_based" },
+                "escalation_policy": { "id": "PT20YPA", "type": "escalation_policy_reference" },
+                "integrations": [
+                    { "id": "PQ12345", "type": "generic_email_inbound_integration_reference" }
+                ],
+                "teams": []
+            }
+        });
+
+        let res = GetService::get_response(StatusCode::Ok, &Headers::new(), body);
+        let service = res.unwrap().success().unwrap();
+
+        assert_eq!(service.status, ServiceStatus::Active);
+        assert_eq!(service.auto_resolve_timeout, Some(Duration::from_secs(14400)));
+        assert_eq!(service.acknowledgement_timeout, None);
+        assert_eq!(service.alert_creation, Some(AlertCreation::CreateAlertsAndIncidents));
+        assert_eq!(service.escalation_policy.id, "PT20YPA");
+        assert_eq!(service.integrations.len(), 1);
+        assert_eq!(service.incident_urgency_rule,
+                   Some(IncidentUrgencyRule::constant(IncidentUrgency::SeverityBased)));
+        assert_eq!(service.routing_key(), None);
+    }
+
+    #[test]
+    fn routing_key_of_events_api_v2_integration() {
+        let body = stringify!({
+            "services": [
+                {
+                    "id": "PIJ90N7",
+                    "type": "service",
+                    "name": "My Application Service",
+                    "status": "active",
+                    "escalation_policy": { "id": "PT20YPA", "type": "escalation_policy_reference" },
+                    "integrations": [
+                        {
+                            "id": "PQ12345",
+                            "type": "generic_email_inbound_integration",
+                            "integration_email": "my-app@example.pagerduty.com"
+                        },
+                        {
+                            "id": "PE1U9CH",
+                            "type": "events_api_v2_inbound_integration",
+                            "integration_key": "f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b"
+                        }
+                    ]
+                }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false
+        });
+
+        let res = ListServices::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].routing_key(), Some("f9c2b0c8c0c54a2e9f7b7e3e5c1a8d2b"));
+    }
+}
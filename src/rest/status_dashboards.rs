@@ -0,0 +1,291 @@
+//! Status Dashboards
+//!
+//! A status dashboard shows the current impact of incidents on a selection of business services.
+//! Dashboards are identified either by ID or by the slug of their URL in the web application.
+//!
+//! The impacted business services of a dashboard are listed with
+//! [`list_impacts`](fn.list_impacts.html), and are the same
+//! [`Impact`](../business_services/struct.Impact.html) objects returned for business services.
+//! These endpoints are in early access, and this library sends the header PagerDuty requires to
+//! use them.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_page, get_response, segment, Page, Query, Response, BASE_URL};
+use super::business_services::Impact;
+
+/// Value of the `X-EARLY-ACCESS` header required by the status dashboard endpoints
+const DASHBOARDS_EARLY_ACCESS: &str = "status-dashboards-preview";
+
+fn dashboard_headers() -> Headers {
+    let mut headers = super::headers(None);
+    headers.set_raw("X-EARLY-ACCESS", vec![DASHBOARDS_EARLY_ACCESS.as_bytes().to_vec()]);
+    headers
+}
+
+/// A dashboard of the impact of incidents on business services
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct StatusDashboard {
+    /// The dashboard's ID
+    pub id: String,
+
+    /// The name of the dashboard
+    pub name: String,
+
+    /// The slug of the dashboard's URL in the web application
+    pub url_slug: String,
+}
+
+/// How a request identifies a dashboard
+#[derive(Debug)]
+enum DashboardKey<'a> {
+    Id(Cow<'a, str>),
+    UrlSlug(Cow<'a, str>),
+}
+
+impl<'a> DashboardKey<'a> {
+    fn path(&self) -> String {
+        match *self {
+            DashboardKey::Id(ref id) => format!("/status_dashboards/{}", segment(id)),
+            DashboardKey::UrlSlug(ref slug) => format!("/status_dashboards/url_slugs/{}",
+                                                       segment(slug)),
+        }
+    }
+}
+
+/// List status dashboards
+#[derive(Debug, Default)]
+pub struct ListStatusDashboards;
+
+impl ListStatusDashboards {
+    /// Create a request listing all status dashboards
+    pub fn new() -> ListStatusDashboards {
+        ListStatusDashboards
+    }
+}
+
+impl Requestable for ListStatusDashboards {
+    type Response = Response<Vec<StatusDashboard>>;
+
+    fn headers(&self) -> Headers {
+        dashboard_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/status_dashboards", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<StatusDashboard>>> {
+        get_response(status, headers, body, "status_dashboards")
+    }
+}
+
+/// Get details about a status dashboard
+#[derive(Debug)]
+pub struct GetStatusDashboard<'a> {
+    key: DashboardKey<'a>,
+}
+
+impl<'a> GetStatusDashboard<'a> {
+    /// Create a request for status dashboard `id`
+    pub fn new<S>(id: S) -> GetStatusDashboard<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetStatusDashboard {
+            key: DashboardKey::Id(id.into()),
+        }
+    }
+
+    /// Create a request for the status dashboard whose URL slug is `url_slug`
+    pub fn by_url_slug<S>(url_slug: S) -> GetStatusDashboard<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetStatusDashboard {
+            key: DashboardKey::UrlSlug(url_slug.into()),
+        }
+    }
+}
+
+impl<'a> Requestable for GetStatusDashboard<'a> {
+    type Response = Response<StatusDashboard>;
+
+    fn headers(&self) -> Headers {
+        dashboard_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}{}", BASE_URL, self.key.path()).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<StatusDashboard>> {
+        get_response(status, headers, body, "status_dashboard")
+    }
+}
+
+/// List the business services on a status dashboard along with their current impact
+#[derive(Debug)]
+pub struct ListDashboardImpacts<'a> {
+    key: DashboardKey<'a>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ListDashboardImpacts<'a> {
+    /// Create a request listing the impacts on status dashboard `id`
+    pub fn new<S>(id: S) -> ListDashboardImpacts<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListDashboardImpacts {
+            key: DashboardKey::Id(id.into()),
+            include: Vec::new(),
+        }
+    }
+
+    /// Create a request listing the impacts on the status dashboard whose URL slug is `url_slug`
+    pub fn by_url_slug<S>(url_slug: S) -> ListDashboardImpacts<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListDashboardImpacts {
+            key: DashboardKey::UrlSlug(url_slug.into()),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional details in the response, such as
+    /// `"services.highest_impacting_priority"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListDashboardImpacts<'a> {
+    type Response = Response<Page<Impact>>;
+
+    fn headers(&self) -> Headers {
+        dashboard_headers()
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("additional_fields", &self.include);
+
+        query.url(&format!("{}/service_impacts", self.key.path())).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Impact>>> {
+        get_page(status, headers, body, "services")
+    }
+}
+
+/// List status dashboards
+pub fn list(auth: &AuthToken,
+            request: &ListStatusDashboards) -> request::Result<Response<Vec<StatusDashboard>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single status dashboard
+pub fn get(auth: &AuthToken,
+           request: &GetStatusDashboard) -> request::Result<Response<StatusDashboard>> {
+    request::perform(auth, request)
+}
+
+/// List the impacted business services of a status dashboard
+pub fn list_impacts(auth: &AuthToken,
+                    request: &ListDashboardImpacts) -> request::Result<Response<Page<Impact>>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use rest::business_services::ImpactStatus;
+    use super::{GetStatusDashboard, ListDashboardImpacts};
+
+    #[test]
+    fn dashboard_by_url_slug() {
+        let request = GetStatusDashboard::by_url_slug("checkout");
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/status_dashboards/url_slugs/checkout");
+        assert!(request.headers().get_raw("X-EARLY-ACCESS").is_some());
+    }
+
+    #[test]
+    fn dashboard_impacts_from_json() {
+        let request = ListDashboardImpacts::new("PBCDEFG")
+            .add_include("services.highest_impacting_priority");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/status_dashboards/PBCDEFG/service_impacts\
+                    ?additional_fields%5B%5D=services.highest_impacting_priority");
+
+        let body = stringify!({
+            "services": [
+                {
+                    "id": "P7ZRB5X",
+                    "name": "Checkout",
+                    "type": "business_service",
+                    "status": "impacted",
+                    "additional_fields": {
+                        "highest_impacting_priority": { "id": "PSO75BM", "order": 256 }
+                    }
+                },
+                {
+                    "id": "PD1234",
+                    "name": "Search",
+                    "type": "business_service",
+                    "status": "not_impacted"
+                }
+            ],
+            "limit": 100,
+            "more": false
+        });
+
+        let res = ListDashboardImpacts::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].status, ImpactStatus::Impacted);
+        assert_eq!(page.items[1].status, ImpactStatus::NotImpacted);
+        assert!(!page.more);
+    }
+}
@@ -0,0 +1,615 @@
+//! Tags
+//!
+//! Tags are labels which can be attached to users, teams, and escalation policies, for example to
+//! record the cost center responsible for them. Entities can then be listed by tag.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::{from_str, to_string, Value as Json};
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// A label attached to users, teams, and escalation policies
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Tag {
+    /// The tag's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the tag
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the tag is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the tag is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The label of the tag
+    pub label: String,
+}
+
+/// A kind of entity which can be tagged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaggableKind {
+    User,
+    Team,
+    EscalationPolicy,
+}
+
+impl TaggableKind {
+    /// The path segment of entities of this kind, such as `users`
+    fn collection(&self) -> &'static str {
+        match *self {
+            TaggableKind::User => "users",
+            TaggableKind::Team => "teams",
+            TaggableKind::EscalationPolicy => "escalation_policies",
+        }
+    }
+}
+
+/// An entity which can be tagged
+#[derive(Debug, Clone)]
+pub enum Taggable<'a> {
+    User(Cow<'a, str>),
+    Team(Cow<'a, str>),
+    EscalationPolicy(Cow<'a, str>),
+}
+
+impl<'a> Taggable<'a> {
+    /// User `id`
+    pub fn user<S>(id: S) -> Taggable<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Taggable::User(id.into())
+    }
+
+    /// Team `id`
+    pub fn team<S>(id: S) -> Taggable<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Taggable::Team(id.into())
+    }
+
+    /// Escalation policy `id`
+    pub fn escalation_policy<S>(id: S) -> Taggable<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        Taggable::EscalationPolicy(id.into())
+    }
+
+    /// The kind of entity
+    pub fn kind(&self) -> TaggableKind {
+        match *self {
+            Taggable::User(_) => TaggableKind::User,
+            Taggable::Team(_) => TaggableKind::Team,
+            Taggable::EscalationPolicy(_) => TaggableKind::EscalationPolicy,
+        }
+    }
+
+    /// The entity's ID
+    pub fn id(&self) -> &str {
+        match *self {
+            Taggable::User(ref id) |
+            Taggable::Team(ref id) |
+            Taggable::EscalationPolicy(ref id) => id,
+        }
+    }
+
+    fn path(&self) -> String {
+        format!("/{}/{}", self.kind().collection(), segment(self.id()))
+    }
+}
+
+/// List tags, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListTags<'a> {
+    query: Option<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListTags<'a> {
+    /// Create a request listing all tags
+    pub fn new() -> ListTags<'a> {
+        ListTags::default()
+    }
+
+    /// Only return tags whose labels contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListTags<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListTags<'a> {
+    type Response = Response<Page<Tag>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/tags").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Tag>>> {
+        get_page(status, headers, body, "tags")
+    }
+}
+
+/// Get details about an existing tag
+#[derive(Debug)]
+pub struct GetTag<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetTag<'a> {
+    /// Create a request for tag `id`
+    pub fn new<S>(id: S) -> GetTag<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetTag {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetTag<'a> {
+    type Response = Response<Tag>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/tags/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Tag>> {
+        get_response(status, headers, body, "tag")
+    }
+}
+
+/// A tag being created, or attached to an entity by ID or label
+#[derive(Debug, Serialize)]
+struct TagRef<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    id: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    label: Option<Cow<'a, str>>,
+}
+
+impl<'a> TagRef<'a> {
+    fn id(id: Cow<'a, str>) -> TagRef<'a> {
+        TagRef {
+            kind: "tag_reference",
+            id: Some(id),
+            label: None,
+        }
+    }
+
+    fn label(label: Cow<'a, str>) -> TagRef<'a> {
+        TagRef {
+            kind: "tag",
+            id: None,
+            label: Some(label),
+        }
+    }
+}
+
+/// Create a new tag
+#[derive(Debug, Serialize)]
+pub struct CreateTag<'a> {
+    tag: TagRef<'a>,
+}
+
+impl<'a> CreateTag<'a> {
+    /// Create a request for a new tag with `label`
+    pub fn new<S>(label: S) -> CreateTag<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateTag {
+            tag: TagRef::label(label.into()),
+        }
+    }
+}
+
+impl<'a> Requestable for CreateTag<'a> {
+    type Response = Response<Tag>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/tags", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Tag>> {
+        get_response(status, headers, body, "tag")
+    }
+}
+
+/// Delete an existing tag
+///
+/// The tag is removed from all entities it is attached to.
+#[derive(Debug)]
+pub struct DeleteTag<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteTag<'a> {
+    /// Create a request deleting tag `id`
+    pub fn new<S>(id: S) -> DeleteTag<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteTag {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteTag<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/tags/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List the entities of a kind which have a tag
+#[derive(Debug)]
+pub struct ListTaggedEntities<'a> {
+    tag_id: Cow<'a, str>,
+    kind: TaggableKind,
+    pagination: Pagination,
+}
+
+impl<'a> ListTaggedEntities<'a> {
+    /// Create a request listing the entities of `kind` with tag `tag_id`
+    pub fn new<S>(tag_id: S, kind: TaggableKind) -> ListTaggedEntities<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListTaggedEntities {
+            tag_id: tag_id.into(),
+            kind,
+            pagination: Pagination::default(),
+        }
+    }
+}
+
+impl<'a> ListRequest for ListTaggedEntities<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListTaggedEntities<'a> {
+    type Response = Response<Page<Reference>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url(&format!("/tags/{}/{}", segment(&self.tag_id), self.kind.collection())).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Reference>>> {
+        // Results are keyed by the kind of entity, which isn't known here
+        let key = from_str::<BTreeMap<String, Json>>(body)
+            .ok()
+            .and_then(|fields| {
+                ["users", "teams", "escalation_policies"]
+                    .iter()
+                    .find(|key| fields.contains_key(**key))
+                    .cloned()
+            })
+            .unwrap_or("users");
+
+        get_page(status, headers, body, key)
+    }
+}
+
+/// List the tags attached to an entity
+#[derive(Debug)]
+pub struct ListEntityTags<'a> {
+    entity: Taggable<'a>,
+    pagination: Pagination,
+}
+
+impl<'a> ListEntityTags<'a> {
+    /// Create a request listing the tags of `entity`
+    pub fn new(entity: Taggable<'a>) -> ListEntityTags<'a> {
+        ListEntityTags {
+            entity,
+            pagination: Pagination::default(),
+        }
+    }
+}
+
+impl<'a> ListRequest for ListEntityTags<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListEntityTags<'a> {
+    type Response = Response<Page<Tag>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        self.pagination.push_to(&mut query);
+        query.url(&format!("{}/tags", self.entity.path())).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Tag>>> {
+        get_page(status, headers, body, "tags")
+    }
+}
+
+/// Attach tags to and remove tags from an entity
+#[derive(Debug, Serialize)]
+pub struct ChangeTags<'a> {
+    #[serde(skip_serializing)]
+    entity: Taggable<'a>,
+
+    add: Vec<TagRef<'a>>,
+    remove: Vec<TagRef<'a>>,
+}
+
+impl<'a> ChangeTags<'a> {
+    /// Create a request changing the tags of `entity`
+    pub fn new(entity: Taggable<'a>) -> ChangeTags<'a> {
+        ChangeTags {
+            entity,
+            add: Vec::new(),
+            remove: Vec::new(),
+        }
+    }
+
+    /// Attach existing tag `id`; may be called more than once
+    pub fn add_tag<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.add.push(TagRef::id(id.into()));
+        self
+    }
+
+    /// Attach a tag with `label`, creating it if it doesn't exist; may be called more than once
+    pub fn add_label<S>(mut self, label: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.add.push(TagRef::label(label.into()));
+        self
+    }
+
+    /// Remove tag `id`; may be called more than once
+    pub fn remove_tag<S>(mut self, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.remove.push(TagRef::id(id.into()));
+        self
+    }
+}
+
+impl<'a> Requestable for ChangeTags<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}{}/change_tags", BASE_URL, self.entity.path()).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List tags
+pub fn list(auth: &AuthToken, request: &ListTags) -> request::Result<Response<Page<Tag>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single tag
+pub fn get(auth: &AuthToken, request: &GetTag) -> request::Result<Response<Tag>> {
+    request::perform(auth, request)
+}
+
+/// Create a tag
+pub fn create(auth: &AuthToken, request: &CreateTag) -> request::Result<Response<Tag>> {
+    request::perform(auth, request)
+}
+
+/// Delete a tag
+pub fn delete(auth: &AuthToken, request: &DeleteTag) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List the entities with a tag
+pub fn list_entities(auth: &AuthToken,
+                     request: &ListTaggedEntities) -> request::Result<Response<Page<Reference>>> {
+    request::perform(auth, request)
+}
+
+/// List the tags of an entity
+pub fn list_entity_tags(auth: &AuthToken,
+                        request: &ListEntityTags) -> request::Result<Response<Page<Tag>>> {
+    request::perform(auth, request)
+}
+
+/// Change the tags of an entity
+pub fn change_tags(auth: &AuthToken, request: &ChangeTags) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{ChangeTags, ListTaggedEntities, Taggable, TaggableKind};
+
+    #[test]
+    fn change_tags_body() {
+        let expected: Json = from_str(stringify!({
+            "add": [
+                { "type": "tag_reference", "id": "P5IYCNZ" },
+                { "type": "tag", "label": "cost-center-42" }
+            ],
+            "remove": [
+                { "type": "tag_reference", "id": "P3EAFS9" }
+            ]
+        })).expect("expected is valid json");
+
+        let request = ChangeTags::new(Taggable::escalation_policy("PANZZEQ"))
+            .add_tag("P5IYCNZ")
+            .add_label("cost-center-42")
+            .remove_tag("P3EAFS9");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/escalation_policies/PANZZEQ/change_tags");
+    }
+
+    #[test]
+    fn tagged_entities_from_json() {
+        let request = ListTaggedEntities::new("P5IYCNZ", TaggableKind::Team);
+        assert_eq!(request.url(), "https://api.pagerduty.com/tags/P5IYCNZ/teams");
+
+        let body = stringify!({
+            "teams": [
+                { "id": "PQ9K7I8", "type": "team", "summary": "Engineering", "name": "Engineering" }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false,
+            "total": null
+        });
+
+        let res = ListTaggedEntities::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].id, "PQ9K7I8");
+        assert_eq!(page.items[0].kind, "team");
+    }
+}
@@ -0,0 +1,378 @@
+//! Teams
+//!
+//! A team is a collection of users and escalation policies that represent a group of people
+//! within an organization. Members of a team have a role on it, which limits what they may do
+//! with the team's resources.
+
+use std::borrow::Cow;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, segment, ListRequest, Page, Pagination, Query, Reference,
+            Response, BASE_URL};
+
+/// A user's role on a team
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamRole {
+    Observer,
+    Responder,
+    Manager,
+}
+
+/// A member of a team
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Member {
+    /// The user
+    pub user: Reference,
+
+    /// The user's role on the team
+    pub role: TeamRole,
+}
+
+/// List the members of a team
+#[derive(Debug)]
+pub struct ListTeamMembers<'a> {
+    team_id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListTeamMembers<'a> {
+    /// Create a request listing the members of team `team_id`
+    pub fn new<S>(team_id: S) -> ListTeamMembers<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListTeamMembers {
+            team_id: team_id.into(),
+            include: Vec::new(),
+            pagination: Pagination::default(),
+        }
+    }
+
+    /// Include additional models in the response, such as `"users"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListTeamMembers<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListTeamMembers<'a> {
+    type Response = Response<Page<Member>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        self.pagination.push_to(&mut query);
+
+        query.url(&format!("/teams/{}/members", segment(&self.team_id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<Member>>> {
+        get_page(status, headers, body, "members")
+    }
+}
+
+/// Add a user to a team, or change their role on it
+#[derive(Debug, Serialize)]
+pub struct AddTeamUser<'a> {
+    #[serde(skip_serializing)]
+    team_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    user_id: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    role: Option<TeamRole>,
+}
+
+impl<'a> AddTeamUser<'a> {
+    /// Create a request adding user `user_id` to team `team_id`
+    ///
+    /// Users are added with the role `TeamRole::Manager` unless another is set.
+    pub fn new<S>(team_id: S, user_id: S) -> AddTeamUser<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AddTeamUser {
+            team_id: team_id.into(),
+            user_id: user_id.into(),
+            role: None,
+        }
+    }
+
+    /// Set the user's role on the team
+    pub fn set_role(mut self, role: TeamRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+}
+
+impl<'a> Requestable for AddTeamUser<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/teams/{}/users/{}",
+                BASE_URL,
+                segment(&self.team_id),
+                segment(&self.user_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Remove a user from a team
+#[derive(Debug)]
+pub struct RemoveTeamUser<'a> {
+    team_id: Cow<'a, str>,
+    user_id: Cow<'a, str>,
+}
+
+impl<'a> RemoveTeamUser<'a> {
+    /// Create a request removing user `user_id` from team `team_id`
+    pub fn new<S>(team_id: S, user_id: S) -> RemoveTeamUser<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        RemoveTeamUser {
+            team_id: team_id.into(),
+            user_id: user_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for RemoveTeamUser<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/teams/{}/users/{}",
+                BASE_URL,
+                segment(&self.team_id),
+                segment(&self.user_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Add an escalation policy to a team
+#[derive(Debug)]
+pub struct AddTeamEscalationPolicy<'a> {
+    team_id: Cow<'a, str>,
+    escalation_policy_id: Cow<'a, str>,
+}
+
+impl<'a> AddTeamEscalationPolicy<'a> {
+    /// Create a request adding escalation policy `escalation_policy_id` to team `team_id`
+    pub fn new<S>(team_id: S, escalation_policy_id: S) -> AddTeamEscalationPolicy<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        AddTeamEscalationPolicy {
+            team_id: team_id.into(),
+            escalation_policy_id: escalation_policy_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for AddTeamEscalationPolicy<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/teams/{}/escalation_policies/{}",
+                BASE_URL,
+                segment(&self.team_id),
+                segment(&self.escalation_policy_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Remove an escalation policy from a team
+#[derive(Debug)]
+pub struct RemoveTeamEscalationPolicy<'a> {
+    team_id: Cow<'a, str>,
+    escalation_policy_id: Cow<'a, str>,
+}
+
+impl<'a> RemoveTeamEscalationPolicy<'a> {
+    /// Create a request removing escalation policy `escalation_policy_id` from team `team_id`
+    pub fn new<S>(team_id: S, escalation_policy_id: S) -> RemoveTeamEscalationPolicy<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        RemoveTeamEscalationPolicy {
+            team_id: team_id.into(),
+            escalation_policy_id: escalation_policy_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for RemoveTeamEscalationPolicy<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/teams/{}/escalation_policies/{}",
+                BASE_URL,
+                segment(&self.team_id),
+                segment(&self.escalation_policy_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List the members of a team
+pub fn list_members(auth: &AuthToken,
+                    request: &ListTeamMembers) -> request::Result<Response<Page<Member>>> {
+    request::perform(auth, request)
+}
+
+/// Add a user to a team
+pub fn add_user(auth: &AuthToken, request: &AddTeamUser) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Remove a user from a team
+pub fn remove_user(auth: &AuthToken, request: &RemoveTeamUser) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Add an escalation policy to a team
+pub fn add_escalation_policy(auth: &AuthToken,
+                             request: &AddTeamEscalationPolicy) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Remove an escalation policy from a team
+pub fn remove_escalation_policy(auth: &AuthToken, request: &RemoveTeamEscalationPolicy)
+    -> request::Result<Response<()>>
+{
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use request::Requestable;
+    use super::{AddTeamUser, ListTeamMembers, TeamRole};
+
+    #[test]
+    fn add_team_user() {
+        let request = AddTeamUser::new("PQ9K7I8", "PXPGF42").set_role(TeamRole::Responder);
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/teams/PQ9K7I8/users/PXPGF42");
+        assert_eq!(request.body(), r#"{"role":"responder"}"#);
+        assert_eq!(AddTeamUser::new("PQ9K7I8", "PXPGF42").body(), "{}");
+    }
+
+    #[test]
+    fn team_members_from_json() {
+        let body = stringify!({
+            "members": [
+                {
+                    "user": { "id": "PXPGF42", "type": "user_reference", "summary": "Earline" },
+                    "role": "manager"
+                }
+            ],
+            "limit": 25,
+            "offset": 0,
+            "more": false,
+            "total": null
+        });
+
+        let res = ListTeamMembers::get_response(StatusCode::Ok, &Headers::new(), body);
+        let page = res.unwrap().success().unwrap();
+
+        assert_eq!(page.items[0].user.id, "PXPGF42");
+        assert_eq!(page.items[0].role, TeamRole::Manager);
+    }
+}
@@ -0,0 +1,1806 @@
+//! Users
+//!
+//! Users are members of a PagerDuty account who have the ability to interact with incidents and
+//! other data on the account.
+//!
+//! Each user has contact methods, the email addresses, phone numbers and devices through which
+//! PagerDuty reaches them, and notification rules deciding which contact methods are used, and
+//! when, once the user is assigned an incident. Their sessions can be listed and revoked, for
+//! example to sign out a compromised account.
+
+use std::borrow::Cow;
+use std::time::Duration;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Reference, Response, BASE_URL};
+
+/// A user's role, which determines what they may do in the account
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    LimitedUser,
+    Observer,
+    Owner,
+    ReadOnlyUser,
+    ReadOnlyLimitedUser,
+    RestrictedAccess,
+    User,
+
+    /// A role not known to this library; never sent to PagerDuty
+    #[serde(other)]
+    Unknown,
+}
+
+/// A user of the account
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct User {
+    /// The user's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the user
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the user is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the user is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the user
+    pub name: String,
+
+    /// The user's email address
+    pub email: String,
+
+    /// The user's role
+    pub role: Role,
+
+    /// The preferred time zone name, such as `America/New_York`
+    #[serde(default)]
+    pub time_zone: Option<String>,
+
+    /// The user's title
+    #[serde(default)]
+    pub job_title: Option<String>,
+
+    /// The bio of the user
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The schedule color
+    #[serde(default)]
+    pub color: Option<String>,
+
+    /// The URL of the user's avatar
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+
+    /// Whether the user has been invited to the account but not yet signed up
+    #[serde(default)]
+    pub invitation_sent: bool,
+
+    /// Teams the user belongs to
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// Ways in which the user can be contacted
+    #[serde(default)]
+    pub contact_methods: Vec<Reference>,
+
+    /// Rules determining how the user is notified of incidents
+    #[serde(default)]
+    pub notification_rules: Vec<Reference>,
+}
+
+/// List users of the account, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListUsers<'a> {
+    query: Option<Cow<'a, str>>,
+    team_ids: Vec<Cow<'a, str>>,
+    include: Vec<Cow<'a, str>>,
+    pagination: Pagination,
+}
+
+impl<'a> ListUsers<'a> {
+    /// Create a request listing all users
+    pub fn new() -> ListUsers<'a> {
+        ListUsers::default()
+    }
+
+    /// Only return users whose names or email addresses contain `query`
+    pub fn set_query<S>(mut self, query: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Only return users belonging to team `team_id`; may be called more than once
+    pub fn add_team_id<S>(mut self, team_id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.team_ids.push(team_id.into());
+        self
+    }
+
+    /// Include additional models in the response, such as `"contact_methods"` or `"teams"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> ListRequest for ListUsers<'a> {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl<'a> Requestable for ListUsers<'a> {
+    type Response = Response<Page<User>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(ref q) = self.query {
+            query.push("query", q);
+        }
+        query.push_array("team_ids", &self.team_ids);
+        query.push_array("include", &self.include);
+        self.pagination.push_to(&mut query);
+
+        query.url("/users").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<User>>> {
+        get_page(status, headers, body, "users")
+    }
+}
+
+/// Get details about an existing user
+#[derive(Debug)]
+pub struct GetUser<'a> {
+    id: Cow<'a, str>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetUser<'a> {
+    /// Create a request for user `id`
+    pub fn new<S>(id: S) -> GetUser<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetUser {
+            id: id.into(),
+            include: Vec::new(),
+        }
+    }
+
+    /// Include additional models in the response, such as `"contact_methods"` or `"teams"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetUser<'a> {
+    type Response = Response<User>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url(&format!("/users/{}", segment(&self.id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<User>> {
+        get_response(status, headers, body, "user")
+    }
+}
+
+/// Get the user whose token is making the request
+///
+/// Only works with user-level API tokens and OAuth tokens; account-level tokens don't belong to a
+/// user, and get a `BadRequest` response.
+#[derive(Debug, Default)]
+pub struct GetCurrentUser<'a> {
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> GetCurrentUser<'a> {
+    /// Create a request for the current user
+    pub fn new() -> GetCurrentUser<'a> {
+        GetCurrentUser::default()
+    }
+
+    /// Include additional models in the response, such as `"contact_methods"` or `"teams"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for GetCurrentUser<'a> {
+    type Response = Response<User>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        query.push_array("include", &self.include);
+        query.url("/users/me").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<User>> {
+        get_response(status, headers, body, "user")
+    }
+}
+
+/// Writable fields of a user
+///
+/// Used to describe a new user with `CreateUser`, or changes to an existing one with `UpdateUser`.
+/// Fields which aren't set are left out of the request.
+#[derive(Debug, Serialize)]
+pub struct UserFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    name: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    email: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    role: Option<Role>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    time_zone: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    job_title: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    color: Option<Cow<'a, str>>,
+}
+
+impl<'a> Default for UserFields<'a> {
+    fn default() -> UserFields<'a> {
+        UserFields::new()
+    }
+}
+
+impl<'a> UserFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> UserFields<'a> {
+        UserFields {
+            kind: "user",
+            name: None,
+            email: None,
+            role: None,
+            time_zone: None,
+            job_title: None,
+            description: None,
+            color: None,
+        }
+    }
+
+    /// Set the name of the user
+    pub fn set_name<S>(mut self, name: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the user's email address
+    pub fn set_email<S>(mut self, email: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.email = Some(email.into());
+        self
+    }
+
+    /// Set the user's role
+    ///
+    /// `Role::Unknown` can't be sent to PagerDuty, so it unsets the role instead, leaving the role
+    /// of an updated user unchanged.
+    pub fn set_role(mut self, role: Role) -> Self {
+        self.role = match role {
+            Role::Unknown => None,
+            role => Some(role),
+        };
+        self
+    }
+
+    /// Set the preferred time zone name, such as `America/New_York`
+    pub fn set_time_zone<S>(mut self, time_zone: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.time_zone = Some(time_zone.into());
+        self
+    }
+
+    /// Set the user's title
+    pub fn set_job_title<S>(mut self, job_title: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.job_title = Some(job_title.into());
+        self
+    }
+
+    /// Set the bio of the user
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the schedule color
+    pub fn set_color<S>(mut self, color: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+/// Create a new user
+#[derive(Debug, Serialize)]
+pub struct CreateUser<'a> {
+    #[serde(skip_serializing)]
+    from: Option<Cow<'a, str>>,
+
+    user: UserFields<'a>,
+}
+
+impl<'a> CreateUser<'a> {
+    /// Create a request for a new user described by `fields`
+    ///
+    /// PagerDuty requires at least a name and email address.
+    pub fn new(fields: UserFields<'a>) -> CreateUser<'a> {
+        CreateUser {
+            from: None,
+            user: fields,
+        }
+    }
+
+    /// Set the email address of the user creating the new user
+    ///
+    /// This is sent as the `From` header, and is required when using an account-level API token.
+    pub fn set_from<S>(mut self, from: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.from = Some(from.into());
+        self
+    }
+}
+
+impl<'a> Requestable for CreateUser<'a> {
+    type Response = Response<User>;
+
+    fn headers(&self) -> Headers {
+        super::headers(self.from.as_ref().map(|from| from.as_ref()))
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<User>> {
+        get_response(status, headers, body, "user")
+    }
+}
+
+/// Update an existing user
+#[derive(Debug, Serialize)]
+pub struct UpdateUser<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    user: UserFields<'a>,
+}
+
+impl<'a> UpdateUser<'a> {
+    /// Create a request applying `fields` to user `id`
+    pub fn new<S>(id: S, fields: UserFields<'a>) -> UpdateUser<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateUser {
+            id: id.into(),
+            user: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateUser<'a> {
+    type Response = Response<User>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<User>> {
+        get_response(status, headers, body, "user")
+    }
+}
+
+/// Remove an existing user from the account
+///
+/// Users who are the only target of an escalation rule, or who are on call for an open incident,
+/// can't be deleted.
+#[derive(Debug)]
+pub struct DeleteUser<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteUser<'a> {
+    /// Create a request deleting user `id`
+    pub fn new<S>(id: S) -> DeleteUser<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteUser {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteUser<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// The kind of a contact method
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ContactMethodKind {
+    #[serde(rename = "email_contact_method", alias = "email_contact_method_reference")]
+    Email,
+
+    #[serde(rename = "phone_contact_method", alias = "phone_contact_method_reference")]
+    Phone,
+
+    #[serde(rename = "sms_contact_method", alias = "sms_contact_method_reference")]
+    Sms,
+
+    #[serde(rename = "push_notification_contact_method",
+            alias = "push_notification_contact_method_reference")]
+    PushNotification,
+
+    /// A kind of contact method this library doesn't know about, which PagerDuty rejects when sent
+    #[serde(other)]
+    Unknown,
+}
+
+impl ContactMethodKind {
+    fn reference_type(&self) -> &'static str {
+        match *self {
+            ContactMethodKind::Email => "email_contact_method_reference",
+            ContactMethodKind::Phone => "phone_contact_method_reference",
+            ContactMethodKind::Sms => "sms_contact_method_reference",
+            ContactMethodKind::PushNotification => "push_notification_contact_method_reference",
+            ContactMethodKind::Unknown => "contact_method_reference",
+        }
+    }
+}
+
+/// A method of contacting a user
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ContactMethod {
+    /// The contact method's ID
+    pub id: String,
+
+    /// The kind of contact method
+    #[serde(rename = "type")]
+    pub kind: ContactMethodKind,
+
+    /// A short-form, server-generated string describing the contact method
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the contact method is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the contact method is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The label, such as "Work" or "Mobile"
+    #[serde(default)]
+    pub label: Option<String>,
+
+    /// The email address, phone number without country code, or device token
+    pub address: String,
+
+    /// The country code of a phone or SMS contact method
+    #[serde(default)]
+    pub country_code: Option<u32>,
+
+    /// Whether a phone or SMS contact method has been blacklisted after abuse
+    #[serde(default)]
+    pub blacklisted: bool,
+
+    /// Whether an SMS contact method can receive messages
+    #[serde(default)]
+    pub enabled: Option<bool>,
+
+    /// Whether an email contact method is sent shorter emails
+    #[serde(default)]
+    pub send_short_email: Option<bool>,
+
+    /// The device type of a push notification contact method, `ios` or `android`
+    #[serde(default)]
+    pub device_type: Option<String>,
+}
+
+/// Writable fields of a contact method
+///
+/// Push notification contact methods are registered by the mobile app and can't be created
+/// through the API, though they may be updated and deleted.
+#[derive(Debug, Serialize)]
+pub struct ContactMethodFields<'a> {
+    #[serde(rename = "type")]
+    kind: ContactMethodKind,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    label: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    address: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    country_code: Option<u32>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    send_short_email: Option<bool>,
+}
+
+impl<'a> ContactMethodFields<'a> {
+    /// Create an empty set of fields for a contact method of kind `kind`
+    pub fn new(kind: ContactMethodKind) -> ContactMethodFields<'a> {
+        ContactMethodFields {
+            kind,
+            label: None,
+            address: None,
+            country_code: None,
+            send_short_email: None,
+        }
+    }
+
+    /// Set the label, such as "Work" or "Mobile"
+    pub fn set_label<S>(mut self, label: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the email address, or the phone number without country code
+    pub fn set_address<S>(mut self, address: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.address = Some(address.into());
+        self
+    }
+
+    /// Set the country code of a phone or SMS contact method
+    pub fn set_country_code(mut self, country_code: u32) -> Self {
+        self.country_code = Some(country_code);
+        self
+    }
+
+    /// Set whether an email contact method is sent shorter emails
+    pub fn set_send_short_email(mut self, send_short_email: bool) -> Self {
+        self.send_short_email = Some(send_short_email);
+        self
+    }
+}
+
+/// List a user's contact methods
+#[derive(Debug)]
+pub struct ListContactMethods<'a> {
+    user_id: Cow<'a, str>,
+}
+
+impl<'a> ListContactMethods<'a> {
+    /// Create a request listing the contact methods of user `user_id`
+    pub fn new<S>(user_id: S) -> ListContactMethods<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListContactMethods {
+            user_id: user_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for ListContactMethods<'a> {
+    type Response = Response<Vec<ContactMethod>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/contact_methods", BASE_URL, segment(&self.user_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<ContactMethod>>> {
+        get_response(status, headers, body, "contact_methods")
+    }
+}
+
+/// Get one of a user's contact methods
+#[derive(Debug)]
+pub struct GetContactMethod<'a> {
+    user_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetContactMethod<'a> {
+    /// Create a request for contact method `id` of user `user_id`
+    pub fn new<S>(user_id: S, id: S) -> GetContactMethod<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetContactMethod {
+            user_id: user_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetContactMethod<'a> {
+    type Response = Response<ContactMethod>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/contact_methods/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<ContactMethod>> {
+        get_response(status, headers, body, "contact_method")
+    }
+}
+
+/// Add a contact method to a user
+#[derive(Debug, Serialize)]
+pub struct CreateContactMethod<'a> {
+    #[serde(skip_serializing)]
+    user_id: Cow<'a, str>,
+
+    contact_method: ContactMethodFields<'a>,
+}
+
+impl<'a> CreateContactMethod<'a> {
+    /// Create a request adding the contact method described by `fields` to user `user_id`
+    ///
+    /// PagerDuty requires at least an address, and a country code for phone and SMS contact
+    /// methods.
+    pub fn new<S>(user_id: S, fields: ContactMethodFields<'a>) -> CreateContactMethod<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateContactMethod {
+            user_id: user_id.into(),
+            contact_method: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateContactMethod<'a> {
+    type Response = Response<ContactMethod>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/contact_methods", BASE_URL, segment(&self.user_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<ContactMethod>> {
+        get_response(status, headers, body, "contact_method")
+    }
+}
+
+/// Update one of a user's contact methods
+#[derive(Debug, Serialize)]
+pub struct UpdateContactMethod<'a> {
+    #[serde(skip_serializing)]
+    user_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    contact_method: ContactMethodFields<'a>,
+}
+
+impl<'a> UpdateContactMethod<'a> {
+    /// Create a request applying `fields` to contact method `id` of user `user_id`
+    pub fn new<S>(user_id: S, id: S, fields: ContactMethodFields<'a>) -> UpdateContactMethod<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateContactMethod {
+            user_id: user_id.into(),
+            id: id.into(),
+            contact_method: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateContactMethod<'a> {
+    type Response = Response<ContactMethod>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/contact_methods/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<ContactMethod>> {
+        get_response(status, headers, body, "contact_method")
+    }
+}
+
+/// Remove one of a user's contact methods
+#[derive(Debug)]
+pub struct DeleteContactMethod<'a> {
+    user_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteContactMethod<'a> {
+    /// Create a request deleting contact method `id` of user `user_id`
+    pub fn new<S>(user_id: S, id: S) -> DeleteContactMethod<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteContactMethod {
+            user_id: user_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteContactMethod<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/contact_methods/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// The urgency of incidents a notification rule applies to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Urgency {
+    High,
+    Low,
+}
+
+impl Urgency {
+    /// The urgency as it's named in the API
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Urgency::High => "high",
+            Urgency::Low => "low",
+        }
+    }
+}
+
+/// A rule for contacting a user when they are assigned an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct NotificationRule {
+    /// The notification rule's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the notification rule
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the notification rule is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the notification rule is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// How long after the incident is assigned the rule is applied
+    #[serde(rename = "start_delay_in_minutes", with = "super::duration::minutes")]
+    pub start_delay: Duration,
+
+    /// The contact method used by the rule
+    pub contact_method: Reference,
+
+    /// Which incidents the rule applies to
+    pub urgency: Urgency,
+}
+
+/// Writable fields of a notification rule
+#[derive(Debug, Serialize)]
+pub struct NotificationRuleFields {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(rename = "start_delay_in_minutes", with = "super::duration::minutes")]
+    start_delay: Duration,
+
+    contact_method: Reference,
+
+    urgency: Urgency,
+}
+
+impl NotificationRuleFields {
+    /// Contact the user through contact method `contact_method_id`, of kind `kind`, once
+    /// `start_delay` has passed since they were assigned an incident of urgency `urgency`
+    ///
+    /// The API counts the delay in minutes, so it is rounded up to a whole number of minutes.
+    pub fn new<S>(start_delay: Duration,
+                  contact_method_id: S,
+                  kind: ContactMethodKind,
+                  urgency: Urgency) -> NotificationRuleFields
+        where S: Into<String>
+    {
+        NotificationRuleFields {
+            kind: "assignment_notification_rule",
+            start_delay,
+            contact_method: Reference::new(contact_method_id, kind.reference_type()),
+            urgency,
+        }
+    }
+}
+
+/// List a user's notification rules
+#[derive(Debug)]
+pub struct ListNotificationRules<'a> {
+    user_id: Cow<'a, str>,
+    urgency: Option<Urgency>,
+    include: Vec<Cow<'a, str>>,
+}
+
+impl<'a> ListNotificationRules<'a> {
+    /// Create a request listing the notification rules of user `user_id`
+    ///
+    /// Only rules for high urgency incidents are returned unless another urgency is set.
+    pub fn new<S>(user_id: S) -> ListNotificationRules<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListNotificationRules {
+            user_id: user_id.into(),
+            urgency: None,
+            include: Vec::new(),
+        }
+    }
+
+    /// Only return rules for incidents of urgency `urgency`
+    pub fn set_urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = Some(urgency);
+        self
+    }
+
+    /// Include additional models in the response, such as `"contact_methods"`
+    pub fn add_include<S>(mut self, include: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.include.push(include.into());
+        self
+    }
+}
+
+impl<'a> Requestable for ListNotificationRules<'a> {
+    type Response = Response<Vec<NotificationRule>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        if let Some(urgency) = self.urgency {
+            query.push("urgency", urgency.as_str());
+        }
+        query.push_array("include", &self.include);
+
+        query.url(&format!("/users/{}/notification_rules", segment(&self.user_id))).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<NotificationRule>>> {
+        get_response(status, headers, body, "notification_rules")
+    }
+}
+
+/// Get one of a user's notification rules
+#[derive(Debug)]
+pub struct GetNotificationRule<'a> {
+    user_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetNotificationRule<'a> {
+    /// Create a request for notification rule `id` of user `user_id`
+    pub fn new<S>(user_id: S, id: S) -> GetNotificationRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetNotificationRule {
+            user_id: user_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetNotificationRule<'a> {
+    type Response = Response<NotificationRule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/notification_rules/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<NotificationRule>> {
+        get_response(status, headers, body, "notification_rule")
+    }
+}
+
+/// Add a notification rule to a user
+#[derive(Debug, Serialize)]
+pub struct CreateNotificationRule<'a> {
+    #[serde(skip_serializing)]
+    user_id: Cow<'a, str>,
+
+    notification_rule: NotificationRuleFields,
+}
+
+impl<'a> CreateNotificationRule<'a> {
+    /// Create a request adding the notification rule described by `fields` to user `user_id`
+    pub fn new<S>(user_id: S, fields: NotificationRuleFields) -> CreateNotificationRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        CreateNotificationRule {
+            user_id: user_id.into(),
+            notification_rule: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateNotificationRule<'a> {
+    type Response = Response<NotificationRule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/notification_rules", BASE_URL, segment(&self.user_id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<NotificationRule>> {
+        get_response(status, headers, body, "notification_rule")
+    }
+}
+
+/// Replace one of a user's notification rules
+#[derive(Debug, Serialize)]
+pub struct UpdateNotificationRule<'a> {
+    #[serde(skip_serializing)]
+    user_id: Cow<'a, str>,
+
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    notification_rule: NotificationRuleFields,
+}
+
+impl<'a> UpdateNotificationRule<'a> {
+    /// Create a request replacing notification rule `id` of user `user_id` with `fields`
+    pub fn new<S>(user_id: S,
+                  id: S,
+                  fields: NotificationRuleFields) -> UpdateNotificationRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateNotificationRule {
+            user_id: user_id.into(),
+            id: id.into(),
+            notification_rule: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateNotificationRule<'a> {
+    type Response = Response<NotificationRule>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/notification_rules/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<NotificationRule>> {
+        get_response(status, headers, body, "notification_rule")
+    }
+}
+
+/// Remove one of a user's notification rules
+#[derive(Debug)]
+pub struct DeleteNotificationRule<'a> {
+    user_id: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteNotificationRule<'a> {
+    /// Create a request deleting notification rule `id` of user `user_id`
+    pub fn new<S>(user_id: S, id: S) -> DeleteNotificationRule<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteNotificationRule {
+            user_id: user_id.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteNotificationRule<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/notification_rules/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// A session in which a user is signed in to PagerDuty
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct UserSession {
+    /// The session's ID
+    pub id: String,
+
+    /// The ID of the user the session belongs to
+    pub user_id: String,
+
+    /// The kind of session, such as `web` or `mobile`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// A short-form, server-generated string describing the session
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The time at which the session was created
+    pub created_at: String,
+}
+
+/// List a user's active sessions
+#[derive(Debug)]
+pub struct ListUserSessions<'a> {
+    user_id: Cow<'a, str>,
+}
+
+impl<'a> ListUserSessions<'a> {
+    /// Create a request listing the sessions of user `user_id`
+    pub fn new<S>(user_id: S) -> ListUserSessions<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        ListUserSessions {
+            user_id: user_id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for ListUserSessions<'a> {
+    type Response = Response<Vec<UserSession>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/sessions", BASE_URL, segment(&self.user_id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Vec<UserSession>>> {
+        get_response(status, headers, body, "user_sessions")
+    }
+}
+
+/// Get one of a user's sessions
+#[derive(Debug)]
+pub struct GetUserSession<'a> {
+    user_id: Cow<'a, str>,
+    kind: Cow<'a, str>,
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetUserSession<'a> {
+    /// Create a request for session `id` of kind `kind` belonging to user `user_id`
+    pub fn new<S>(user_id: S, kind: S, id: S) -> GetUserSession<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetUserSession {
+            user_id: user_id.into(),
+            kind: kind.into(),
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetUserSession<'a> {
+    type Response = Response<UserSession>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/users/{}/sessions/{}/{}",
+                BASE_URL,
+                segment(&self.user_id),
+                segment(&self.kind),
+                segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<UserSession>> {
+        get_response(status, headers, body, "user_session")
+    }
+}
+
+/// Revoke a user's sessions, signing them out
+///
+/// Revokes every session of the user, unless narrowed to a single session with `set_session`.
+#[derive(Debug)]
+pub struct DeleteUserSessions<'a> {
+    user_id: Cow<'a, str>,
+    session: Option<(Cow<'a, str>, Cow<'a, str>)>,
+}
+
+impl<'a> DeleteUserSessions<'a> {
+    /// Create a request revoking the sessions of user `user_id`
+    pub fn new<S>(user_id: S) -> DeleteUserSessions<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteUserSessions {
+            user_id: user_id.into(),
+            session: None,
+        }
+    }
+
+    /// Only revoke session `id` of kind `kind`
+    pub fn set_session<S>(mut self, kind: S, id: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.session = Some((kind.into(), id.into()));
+        self
+    }
+}
+
+impl<'a> Requestable for DeleteUserSessions<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        match self.session {
+            Some((ref kind, ref id)) => {
+                format!("{}/users/{}/sessions/{}/{}",
+                        BASE_URL,
+                        segment(&self.user_id),
+                        segment(kind),
+                        segment(id)).into()
+            },
+            None => format!("{}/users/{}/sessions", BASE_URL, segment(&self.user_id)).into(),
+        }
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// List users
+pub fn list(auth: &AuthToken, request: &ListUsers) -> request::Result<Response<Page<User>>> {
+    request::perform(auth, request)
+}
+
+/// Get a single user
+pub fn get(auth: &AuthToken, request: &GetUser) -> request::Result<Response<User>> {
+    request::perform(auth, request)
+}
+
+/// Get the user whose token is making the request
+pub fn me(auth: &AuthToken, request: &GetCurrentUser) -> request::Result<Response<User>> {
+    request::perform(auth, request)
+}
+
+/// Create a user
+pub fn create(auth: &AuthToken, request: &CreateUser) -> request::Result<Response<User>> {
+    request::perform(auth, request)
+}
+
+/// Update a user
+pub fn update(auth: &AuthToken, request: &UpdateUser) -> request::Result<Response<User>> {
+    request::perform(auth, request)
+}
+
+/// Delete a user
+pub fn delete(auth: &AuthToken, request: &DeleteUser) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List a user's contact methods
+pub fn list_contact_methods(auth: &AuthToken, request: &ListContactMethods)
+    -> request::Result<Response<Vec<ContactMethod>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get one of a user's contact methods
+pub fn get_contact_method(auth: &AuthToken,
+                          request: &GetContactMethod) -> request::Result<Response<ContactMethod>> {
+    request::perform(auth, request)
+}
+
+/// Add a contact method to a user
+pub fn create_contact_method(auth: &AuthToken, request: &CreateContactMethod)
+    -> request::Result<Response<ContactMethod>>
+{
+    request::perform(auth, request)
+}
+
+/// Update one of a user's contact methods
+pub fn update_contact_method(auth: &AuthToken, request: &UpdateContactMethod)
+    -> request::Result<Response<ContactMethod>>
+{
+    request::perform(auth, request)
+}
+
+/// Delete one of a user's contact methods
+pub fn delete_contact_method(auth: &AuthToken,
+                             request: &DeleteContactMethod) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List a user's notification rules
+pub fn list_notification_rules(auth: &AuthToken, request: &ListNotificationRules)
+    -> request::Result<Response<Vec<NotificationRule>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get one of a user's notification rules
+pub fn get_notification_rule(auth: &AuthToken, request: &GetNotificationRule)
+    -> request::Result<Response<NotificationRule>>
+{
+    request::perform(auth, request)
+}
+
+/// Add a notification rule to a user
+pub fn create_notification_rule(auth: &AuthToken, request: &CreateNotificationRule)
+    -> request::Result<Response<NotificationRule>>
+{
+    request::perform(auth, request)
+}
+
+/// Replace one of a user's notification rules
+pub fn update_notification_rule(auth: &AuthToken, request: &UpdateNotificationRule)
+    -> request::Result<Response<NotificationRule>>
+{
+    request::perform(auth, request)
+}
+
+/// Delete one of a user's notification rules
+pub fn delete_notification_rule(auth: &AuthToken,
+                                request: &DeleteNotificationRule) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// List a user's active sessions
+pub fn list_sessions(auth: &AuthToken,
+                     request: &ListUserSessions) -> request::Result<Response<Vec<UserSession>>> {
+    request::perform(auth, request)
+}
+
+/// Get one of a user's sessions
+pub fn get_session(auth: &AuthToken,
+                   request: &GetUserSession) -> request::Result<Response<UserSession>> {
+    request::perform(auth, request)
+}
+
+/// Revoke a user's sessions
+pub fn delete_sessions(auth: &AuthToken,
+                       request: &DeleteUserSessions) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use super::{ContactMethodFields, ContactMethodKind, CreateContactMethod,
+                CreateNotificationRule, CreateUser, DeleteUserSessions, GetCurrentUser,
+                GetNotificationRule, GetUser, ListContactMethods, ListUserSessions, ListUsers,
+                NotificationRuleFields, Role, UpdateUser, Urgency, UserFields};
+
+    #[test]
+    fn user_sessions() {
+        let body = stringify!({
+            "user_sessions": [
+                {
+                    "id": "1234",
+                    "user_id": "PXPGF42",
+                    "type": "mobile",
+                    "summary": "iPhone 11",
+                    "created_at": "2019-12-09T18:30:22Z"
+                }
+            ]
+        });
+
+        let res = ListUserSessions::get_response(StatusCode::Ok, &Headers::new(), body);
+        let sessions = res.unwrap().success().unwrap();
+        assert_eq!(sessions[0].kind, "mobile");
+
+        assert_eq!(DeleteUserSessions::new("PXPGF42").url(),
+                   "https://api.pagerduty.com/users/PXPGF42/sessions");
+        assert_eq!(DeleteUserSessions::new("PXPGF42").set_session("mobile", "1234").url(),
+                   "https://api.pagerduty.com/users/PXPGF42/sessions/mobile/1234");
+    }
+
+    #[test]
+    fn notification_rules_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "notification_rule": {
+                "type": "assignment_notification_rule",
+                "start_delay_in_minutes": 5,
+                "contact_method": { "id": "PXKVS6Q", "type": "sms_contact_method_reference" },
+                "urgency": "high"
+            }
+        })).expect("expected is valid json");
+
+        let fields = NotificationRuleFields::new(Duration::from_secs(5 * 60),
+                                                 "PXKVS6Q",
+                                                 ContactMethodKind::Sms,
+                                                 Urgency::High);
+        let request = CreateNotificationRule::new("PXPGF42", fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "notification_rule": {
+                "id": "PXPGF42",
+                "type": "assignment_notification_rule",
+                "summary": "0 minutes: channel PXKVS6Q",
+                "start_delay_in_minutes": 0,
+                "contact_method": { "id": "PXKVS6Q", "type": "email_contact_method_reference" },
+                "urgency": "low"
+            }
+        });
+
+        let res = GetNotificationRule::get_response(StatusCode::Ok, &Headers::new(), body);
+        let rule = res.unwrap().success().unwrap();
+
+        assert_eq!(rule.start_delay, Duration::from_secs(0));
+        assert_eq!(rule.contact_method.id, "PXKVS6Q");
+        assert_eq!(rule.urgency, Urgency::Low);
+    }
+
+    #[test]
+    fn contact_methods_round_trip() {
+        let expected: Json = from_str(stringify!({
+            "contact_method": {
+                "type": "sms_contact_method",
+                "label": "Mobile",
+                "address": "5558675309",
+                "country_code": 1
+            }
+        })).expect("expected is valid json");
+
+        let fields = ContactMethodFields::new(ContactMethodKind::Sms)
+                         .set_label("Mobile")
+                         .set_address("5558675309")
+                         .set_country_code(1);
+        let request = CreateContactMethod::new("PXPGF42", fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "contact_methods": [
+                {
+                    "id": "PTDVERC",
+                    "type": "email_contact_method",
+                    "label": "Work",
+                    "address": "betty@example.com",
+                    "send_short_email": false
+                },
+                {
+                    "id": "PXKVS6Q",
+                    "type": "phone_contact_method_reference",
+                    "label": "Mobile",
+                    "address": "5558675309",
+                    "country_code": 1,
+                    "blacklisted": false
+                },
+                {
+                    "id": "PZMO0JF",
+                    "type": "chat_contact_method",
+                    "label": "Chat",
+                    "address": "betty"
+                }
+            ]
+        });
+
+        let res = ListContactMethods::get_response(StatusCode::Ok, &Headers::new(), body);
+        let methods = res.unwrap().success().unwrap();
+
+        assert_eq!(methods[0].kind, ContactMethodKind::Email);
+        assert_eq!(methods[1].kind, ContactMethodKind::Phone);
+        assert_eq!(methods[1].country_code, Some(1));
+        assert_eq!(methods[2].kind, ContactMethodKind::Unknown);
+    }
+
+    #[test]
+    fn create_user_to_json() {
+        let expected: Json = from_str(stringify!({
+            "user": {
+                "type": "user",
+                "name": "Earline Greenholt",
+                "email": "125.greenholt.earline@graham.name",
+                "role": "limited_user",
+                "time_zone": "America/Lima"
+            }
+        })).expect("expected is valid json");
+
+        let fields = UserFields::new()
+                         .set_name("Earline Greenholt")
+                         .set_email("125.greenholt.earline@graham.name")
+                         .set_role(Role::LimitedUser)
+                         .set_time_zone("America/Lima");
+        let request = CreateUser::new(fields).set_from("admin@example.com");
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(request.headers().get_raw("From"), Some(&[b"admin@example.com".to_vec()][..]));
+    }
+
+    #[test]
+    fn unknown_role_is_not_sent() {
+        let fields = UserFields::new()
+                         .set_role(Role::Admin)
+                         .set_role(Role::Unknown)
+                         .set_job_title("Engineer");
+        let actual: Json = from_str(&UpdateUser::new("PXPGF42", fields).body()).unwrap();
+
+        assert_eq!(actual["user"]["job_title"], "Engineer");
+        assert!(actual["user"].get("role").is_none());
+    }
+
+    #[test]
+    fn list_users_url() {
+        let request = ListUsers::new()
+                          .set_query("earline")
+                          .add_team_id("PQ9K7I8")
+                          .add_include("contact_methods");
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/users?query=earline&team_ids%5B%5D=PQ9K7I8&\
+                    include%5B%5D=contact_methods");
+    }
+
+    #[test]
+    fn current_user_url() {
+        assert_eq!(GetCurrentUser::new().add_include("teams").url(),
+                   "https://api.pagerduty.com/users/me?include%5B%5D=teams");
+    }
+
+    #[test]
+    fn user_from_json() {
+        let body = stringify!({
+            "user": {
+                "id": "PXPGF42",
+                "type": "user",
+                "summary": "Earline Greenholt",
+                "name": "Earline Greenholt",
+                "email": "125.greenholt.earline@graham.name",
+                "time_zone": "America/Lima",
+                "color": "green",
+                "role": "admin",
+                "invitation_sent": false,
+                "contact_methods": [
+                    { "id": "PTDVERC", "type": "email_contact_method_reference" }
+                ],
+                "notification_rules": [
+                    { "id": "P8GRWKK", "type": "assignment_notification_rule_reference" }
+                ],
+                "teams": [{ "id": "PQ9K7I8", "type": "team_reference" }]
+            }
+        });
+
+        let res = GetUser::get_response(StatusCode::Ok, &Headers::new(), body);
+        let user = res.unwrap().success().unwrap();
+
+        assert_eq!(user.name, "Earline Greenholt");
+        assert_eq!(user.role, Role::Admin);
+        assert_eq!(user.time_zone, Some("America/Lima".to_owned()));
+        assert_eq!(user.contact_methods[0].id, "PTDVERC");
+        assert_eq!(user.teams.len(), 1);
+    }
+}
@@ -0,0 +1,805 @@
+//! Webhook Subscriptions
+//!
+//! A webhook subscription sends V3 webhooks for events on an account, team, or service to an HTTP
+//! endpoint. The events sent are chosen with [`EventType`](enum.EventType.html), and which
+//! objects they're sent for with [`SubscriptionFilter`](enum.SubscriptionFilter.html).
+//!
+//! The secret used to sign webhooks is only returned when a subscription is created.
+//!
+//! A subscription can be tested end to end with [`ping`](fn.ping.html), which sends it a
+//! `pagey.ping` webhook. PagerDuty temporarily disables subscriptions whose deliveries keep
+//! failing; once the endpoint is fixed, they're re-enabled with [`enable`](fn.enable.html).
+
+use std::borrow::Cow;
+use std::fmt;
+
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::status::StatusCode;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::to_string;
+
+use AuthToken;
+use request::{self, Requestable};
+use super::{get_empty_response, get_page, get_response, segment, ListRequest, Page, Pagination,
+            Query, Response, BASE_URL};
+
+/// A kind of event webhooks are sent for
+///
+/// Event types this library doesn't know about are kept as `Other`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventType {
+    IncidentAcknowledged,
+    IncidentAnnotated,
+    IncidentConferenceBridgeUpdated,
+    IncidentCustomFieldValuesUpdated,
+    IncidentDelegated,
+    IncidentEscalated,
+    IncidentPriorityUpdated,
+    IncidentReassigned,
+    IncidentReopened,
+    IncidentResolved,
+    IncidentResponderAdded,
+    IncidentResponderReplied,
+    IncidentStatusUpdatePublished,
+    IncidentTriggered,
+    IncidentUnacknowledged,
+    IncidentWorkflowCompleted,
+    IncidentWorkflowStarted,
+    ServiceCreated,
+    ServiceDeleted,
+    ServiceUpdated,
+
+    /// Sent when a subscription is tested with `ping`
+    PageyPing,
+
+    Other(String),
+}
+
+impl EventType {
+    /// The name of the event type, such as `incident.triggered`
+    pub fn as_str(&self) -> &str {
+        match *self {
+            EventType::IncidentAcknowledged => "incident.acknowledged",
+            EventType::IncidentAnnotated => "incident.annotated",
+            EventType::IncidentConferenceBridgeUpdated => "incident.conference_bridge.updated",
+            EventType::IncidentCustomFieldValuesUpdated => "incident.custom_field_values.updated",
+            EventType::IncidentDelegated => "incident.delegated",
+            EventType::IncidentEscalated => "incident.escalated",
+            EventType::IncidentPriorityUpdated => "incident.priority_updated",
+            EventType::IncidentReassigned => "incident.reassigned",
+            EventType::IncidentReopened => "incident.reopened",
+            EventType::IncidentResolved => "incident.resolved",
+            EventType::IncidentResponderAdded => "incident.responder.added",
+            EventType::IncidentResponderReplied => "incident.responder.replied",
+            EventType::IncidentStatusUpdatePublished => "incident.status_update_published",
+            EventType::IncidentTriggered => "incident.triggered",
+            EventType::IncidentUnacknowledged => "incident.unacknowledged",
+            EventType::IncidentWorkflowCompleted => "incident.workflow.completed",
+            EventType::IncidentWorkflowStarted => "incident.workflow.started",
+            EventType::ServiceCreated => "service.created",
+            EventType::ServiceDeleted => "service.deleted",
+            EventType::ServiceUpdated => "service.updated",
+            EventType::PageyPing => "pagey.ping",
+            EventType::Other(ref name) => name,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for EventType {
+    fn from(name: &'a str) -> EventType {
+        match name {
+            "incident.acknowledged" => EventType::IncidentAcknowledged,
+            "incident.annotated" => EventType::IncidentAnnotated,
+            "incident.conference_bridge.updated" => EventType::IncidentConferenceBridgeUpdated,
+            "incident.custom_field_values.updated" => EventType::IncidentCustomFieldValuesUpdated,
+            "incident.delegated" => EventType::IncidentDelegated,
+            "incident.escalated" => EventType::IncidentEscalated,
+            "incident.priority_updated" => EventType::IncidentPriorityUpdated,
+            "incident.reassigned" => EventType::IncidentReassigned,
+            "incident.reopened" => EventType::IncidentReopened,
+            "incident.resolved" => EventType::IncidentResolved,
+            "incident.responder.added" => EventType::IncidentResponderAdded,
+            "incident.responder.replied" => EventType::IncidentResponderReplied,
+            "incident.status_update_published" => EventType::IncidentStatusUpdatePublished,
+            "incident.triggered" => EventType::IncidentTriggered,
+            "incident.unacknowledged" => EventType::IncidentUnacknowledged,
+            "incident.workflow.completed" => EventType::IncidentWorkflowCompleted,
+            "incident.workflow.started" => EventType::IncidentWorkflowStarted,
+            "service.created" => EventType::ServiceCreated,
+            "service.deleted" => EventType::ServiceDeleted,
+            "service.updated" => EventType::ServiceUpdated,
+            "pagey.ping" => EventType::PageyPing,
+            other => EventType::Other(other.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for EventType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for EventType {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EventType {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<EventType, D::Error>
+        where D: Deserializer<'de>
+    {
+        let name = String::deserialize(deserializer)?;
+        Ok(EventType::from(&name[..]))
+    }
+}
+
+/// The objects a subscription sends webhooks for
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum SubscriptionFilter {
+    /// Every object in the account
+    #[serde(rename = "account_reference")]
+    Account,
+
+    /// Objects of service `id`
+    #[serde(rename = "service_reference")]
+    Service { id: String },
+
+    /// Objects of team `id`
+    #[serde(rename = "team_reference")]
+    Team { id: String },
+}
+
+/// A header sent along with each webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// Where and how webhooks are sent
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct DeliveryMethod {
+    /// The kind of delivery method, `http_delivery_method`
+    #[serde(rename = "type")]
+    pub kind: String,
+
+    /// The URL webhooks are sent to
+    pub url: String,
+
+    /// Headers sent along with each webhook
+    #[serde(default)]
+    pub custom_headers: Vec<CustomHeader>,
+
+    /// The secret webhooks are signed with; only returned when the subscription is created
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Whether delivery was suspended after repeated failures
+    #[serde(default)]
+    pub temporarily_disabled: bool,
+}
+
+/// A subscription to webhooks
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct WebhookSubscription {
+    /// The subscription's ID
+    pub id: String,
+
+    /// Whether webhooks are sent
+    pub active: bool,
+
+    /// Where and how webhooks are sent
+    pub delivery_method: DeliveryMethod,
+
+    /// A description of the subscription
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The kinds of events webhooks are sent for
+    #[serde(default)]
+    pub events: Vec<EventType>,
+
+    /// The objects webhooks are sent for
+    pub filter: SubscriptionFilter,
+}
+
+/// List webhook subscriptions, optionally filtered
+#[derive(Debug, Default)]
+pub struct ListWebhookSubscriptions {
+    filter: Option<SubscriptionFilter>,
+    pagination: Pagination,
+}
+
+impl ListWebhookSubscriptions {
+    /// Create a request listing all webhook subscriptions
+    pub fn new() -> ListWebhookSubscriptions {
+        ListWebhookSubscriptions::default()
+    }
+
+    /// Only return subscriptions with `filter`
+    pub fn set_filter(mut self, filter: SubscriptionFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+}
+
+impl ListRequest for ListWebhookSubscriptions {
+    fn pagination_mut(&mut self) -> &mut Pagination {
+        &mut self.pagination
+    }
+}
+
+impl Requestable for ListWebhookSubscriptions {
+    type Response = Response<Page<WebhookSubscription>>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        let mut query = Query::default();
+        match self.filter {
+            Some(SubscriptionFilter::Account) => query.push("filter_type", "account_reference"),
+            Some(SubscriptionFilter::Service { ref id }) => {
+                query.push("filter_type", "service_reference");
+                query.push("filter_id", id);
+            },
+            Some(SubscriptionFilter::Team { ref id }) => {
+                query.push("filter_type", "team_reference");
+                query.push("filter_id", id);
+            },
+            None => (),
+        }
+        self.pagination.push_to(&mut query);
+
+        query.url("/webhook_subscriptions").into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<Page<WebhookSubscription>>> {
+        get_page(status, headers, body, "webhook_subscriptions")
+    }
+}
+
+/// Get details about an existing webhook subscription
+#[derive(Debug)]
+pub struct GetWebhookSubscription<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> GetWebhookSubscription<'a> {
+    /// Create a request for webhook subscription `id`
+    pub fn new<S>(id: S) -> GetWebhookSubscription<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        GetWebhookSubscription {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for GetWebhookSubscription<'a> {
+    type Response = Response<WebhookSubscription>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/webhook_subscriptions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Get
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WebhookSubscription>> {
+        get_response(status, headers, body, "webhook_subscription")
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DeliveryMethodFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    url: Cow<'a, str>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    custom_headers: Vec<CustomHeader>,
+}
+
+/// Fields of a webhook subscription to create or update
+///
+/// The delivery method of a subscription can't be changed once it is created.
+#[derive(Debug, Default, Serialize)]
+pub struct WebhookSubscriptionFields<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    delivery_method: Option<DeliveryMethodFields<'a>>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    description: Option<Cow<'a, str>>,
+
+    #[serde(skip_serializing_if="Vec::is_empty")]
+    events: Vec<EventType>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    filter: Option<SubscriptionFilter>,
+
+    #[serde(skip_serializing_if="Option::is_none")]
+    active: Option<bool>,
+}
+
+impl<'a> WebhookSubscriptionFields<'a> {
+    /// Create an empty set of fields
+    pub fn new() -> WebhookSubscriptionFields<'a> {
+        WebhookSubscriptionFields {
+            kind: "webhook_subscription",
+            ..WebhookSubscriptionFields::default()
+        }
+    }
+
+    /// Send webhooks to `url`
+    pub fn set_url<S>(mut self, url: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        let headers = self.delivery_method.take().map(|method| method.custom_headers);
+        self.delivery_method = Some(DeliveryMethodFields {
+            kind: "http_delivery_method",
+            url: url.into(),
+            custom_headers: headers.unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Send header `name` with each webhook; may be called more than once
+    ///
+    /// # Panics
+    ///
+    /// If called before `set_url`.
+    pub fn add_custom_header<N, V>(mut self, name: N, value: V) -> Self
+        where N: Into<String>,
+              V: Into<String>
+    {
+        self.delivery_method.as_mut()
+            .expect("set_url must be called before add_custom_header")
+            .custom_headers
+            .push(CustomHeader { name: name.into(), value: value.into() });
+        self
+    }
+
+    /// Set the description of the subscription
+    pub fn set_description<S>(mut self, description: S) -> Self
+        where S: Into<Cow<'a, str>>
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Send webhooks for events of `event_type`; may be called more than once
+    pub fn add_event(mut self, event_type: EventType) -> Self {
+        self.events.push(event_type);
+        self
+    }
+
+    /// Set the objects webhooks are sent for
+    pub fn set_filter(mut self, filter: SubscriptionFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Set whether webhooks are sent
+    pub fn set_active(mut self, active: bool) -> Self {
+        self.active = Some(active);
+        self
+    }
+}
+
+/// Create a new webhook subscription
+#[derive(Debug, Serialize)]
+pub struct CreateWebhookSubscription<'a> {
+    webhook_subscription: WebhookSubscriptionFields<'a>,
+}
+
+impl<'a> CreateWebhookSubscription<'a> {
+    /// Create a request for a new webhook subscription described by `fields`
+    ///
+    /// PagerDuty requires a URL, at least one event type, and a filter.
+    pub fn new(fields: WebhookSubscriptionFields<'a>) -> CreateWebhookSubscription<'a> {
+        CreateWebhookSubscription {
+            webhook_subscription: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for CreateWebhookSubscription<'a> {
+    type Response = Response<WebhookSubscription>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/webhook_subscriptions", BASE_URL).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WebhookSubscription>> {
+        get_response(status, headers, body, "webhook_subscription")
+    }
+}
+
+/// Update an existing webhook subscription
+#[derive(Debug, Serialize)]
+pub struct UpdateWebhookSubscription<'a> {
+    #[serde(skip_serializing)]
+    id: Cow<'a, str>,
+
+    webhook_subscription: WebhookSubscriptionFields<'a>,
+}
+
+impl<'a> UpdateWebhookSubscription<'a> {
+    /// Create a request applying `fields` to webhook subscription `id`
+    pub fn new<S>(id: S, fields: WebhookSubscriptionFields<'a>) -> UpdateWebhookSubscription<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        UpdateWebhookSubscription {
+            id: id.into(),
+            webhook_subscription: fields,
+        }
+    }
+}
+
+impl<'a> Requestable for UpdateWebhookSubscription<'a> {
+    type Response = Response<WebhookSubscription>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/webhook_subscriptions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        to_string(&self).unwrap()
+    }
+
+    fn method(&self) -> Method {
+        Method::Put
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WebhookSubscription>> {
+        get_response(status, headers, body, "webhook_subscription")
+    }
+}
+
+/// Delete an existing webhook subscription
+#[derive(Debug)]
+pub struct DeleteWebhookSubscription<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> DeleteWebhookSubscription<'a> {
+    /// Create a request deleting webhook subscription `id`
+    pub fn new<S>(id: S) -> DeleteWebhookSubscription<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        DeleteWebhookSubscription {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for DeleteWebhookSubscription<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/webhook_subscriptions/{}", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Delete
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Send a test webhook to a webhook subscription
+#[derive(Debug)]
+pub struct PingWebhookSubscription<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> PingWebhookSubscription<'a> {
+    /// Create a request sending a `pagey.ping` webhook to subscription `id`
+    pub fn new<S>(id: S) -> PingWebhookSubscription<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        PingWebhookSubscription {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for PingWebhookSubscription<'a> {
+    type Response = Response<()>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/webhook_subscriptions/{}/ping", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<()>> {
+        get_empty_response(status, headers, body)
+    }
+}
+
+/// Re-enable a webhook subscription disabled after repeated delivery failures
+#[derive(Debug)]
+pub struct EnableWebhookSubscription<'a> {
+    id: Cow<'a, str>,
+}
+
+impl<'a> EnableWebhookSubscription<'a> {
+    /// Create a request enabling webhook subscription `id`
+    pub fn new<S>(id: S) -> EnableWebhookSubscription<'a>
+        where S: Into<Cow<'a, str>>
+    {
+        EnableWebhookSubscription {
+            id: id.into(),
+        }
+    }
+}
+
+impl<'a> Requestable for EnableWebhookSubscription<'a> {
+    type Response = Response<WebhookSubscription>;
+
+    fn headers(&self) -> Headers {
+        super::headers(None)
+    }
+
+    fn url<'b>(&'b self) -> Cow<'b, str> {
+        format!("{}/webhook_subscriptions/{}/enable", BASE_URL, segment(&self.id)).into()
+    }
+
+    fn body(&self) -> String {
+        String::new()
+    }
+
+    fn method(&self) -> Method {
+        Method::Post
+    }
+
+    fn get_response(status: StatusCode,
+                    headers: &Headers,
+                    body: &str) -> request::Result<Response<WebhookSubscription>> {
+        get_response(status, headers, body, "webhook_subscription")
+    }
+}
+
+/// List webhook subscriptions
+pub fn list(auth: &AuthToken, request: &ListWebhookSubscriptions)
+    -> request::Result<Response<Page<WebhookSubscription>>>
+{
+    request::perform(auth, request)
+}
+
+/// Get a single webhook subscription
+pub fn get(auth: &AuthToken, request: &GetWebhookSubscription)
+    -> request::Result<Response<WebhookSubscription>>
+{
+    request::perform(auth, request)
+}
+
+/// Create a webhook subscription
+pub fn create(auth: &AuthToken, request: &CreateWebhookSubscription)
+    -> request::Result<Response<WebhookSubscription>>
+{
+    request::perform(auth, request)
+}
+
+/// Update a webhook subscription
+pub fn update(auth: &AuthToken, request: &UpdateWebhookSubscription)
+    -> request::Result<Response<WebhookSubscription>>
+{
+    request::perform(auth, request)
+}
+
+/// Delete a webhook subscription
+pub fn delete(auth: &AuthToken,
+              request: &DeleteWebhookSubscription) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Send a test webhook to a webhook subscription
+pub fn ping(auth: &AuthToken, request: &PingWebhookSubscription) -> request::Result<Response<()>> {
+    request::perform(auth, request)
+}
+
+/// Re-enable a temporarily disabled webhook subscription
+pub fn enable(auth: &AuthToken, request: &EnableWebhookSubscription)
+    -> request::Result<Response<WebhookSubscription>>
+{
+    request::perform(auth, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::Headers;
+    use hyper::status::StatusCode;
+
+    use serde_json::{from_str, Value as Json};
+
+    use request::Requestable;
+    use rest::ListRequest;
+    use super::{CreateWebhookSubscription, EnableWebhookSubscription, EventType,
+                ListWebhookSubscriptions, PingWebhookSubscription, SubscriptionFilter,
+                WebhookSubscriptionFields};
+
+    #[test]
+    fn create_webhook_subscription() {
+        let expected: Json = from_str(stringify!({
+            "webhook_subscription": {
+                "type": "webhook_subscription",
+                "delivery_method": {
+                    "type": "http_delivery_method",
+                    "url": "https://example.com/pagerduty",
+                    "custom_headers": [ { "name": "X-Token", "value": "secret" } ]
+                },
+                "description": "Incident feed",
+                "events": ["incident.triggered", "incident.resolved"],
+                "filter": { "type": "service_reference", "id": "PIJ90N7" }
+            }
+        })).unwrap();
+
+        let fields = WebhookSubscriptionFields::new()
+            .set_url("https://example.com/pagerduty")
+            .add_custom_header("X-Token", "secret")
+            .set_description("Incident feed")
+            .add_event(EventType::IncidentTriggered)
+            .add_event(EventType::IncidentResolved)
+            .set_filter(SubscriptionFilter::Service { id: "PIJ90N7".to_owned() });
+        let request = CreateWebhookSubscription::new(fields);
+        let actual: Json = from_str(&request.body()).unwrap();
+
+        assert_eq!(actual, expected);
+
+        let body = stringify!({
+            "webhook_subscription": {
+                "id": "PY1OQ9R",
+                "type": "webhook_subscription",
+                "active": true,
+                "delivery_method": {
+                    "id": "PF9KMXH",
+                    "type": "http_delivery_method",
+                    "url": "https://example.com/pagerduty",
+                    "custom_headers": [ { "name": "X-Token", "value": "secret" } ],
+                    "secret": "vkw2pKAWOd5PN0bsZ6xm1Va2XNwUAQHf",
+                    "temporarily_disabled": false
+                },
+                "description": "Incident feed",
+                "events": ["incident.triggered", "incident.resolved", "incident.paused"],
+                "filter": { "id": "PIJ90N7", "type": "service_reference" }
+            }
+        });
+
+        let res = CreateWebhookSubscription::get_response(StatusCode::Created,
+                                                          &Headers::new(),
+                                                          body);
+        let subscription = res.unwrap().success().unwrap();
+
+        assert_eq!(subscription.events[2], EventType::Other("incident.paused".to_owned()));
+        assert_eq!(subscription.filter, SubscriptionFilter::Service { id: "PIJ90N7".to_owned() });
+        assert!(subscription.delivery_method.secret.is_some());
+    }
+
+    #[test]
+    fn list_by_filter() {
+        let request = ListWebhookSubscriptions::new()
+            .set_filter(SubscriptionFilter::Team { id: "PQ9K7I8".to_owned() })
+            .set_limit(10);
+
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/webhook_subscriptions\
+                    ?filter_type=team_reference&filter_id=PQ9K7I8&limit=10");
+
+        let account: SubscriptionFilter = from_str(r#"{"type":"account_reference"}"#).unwrap();
+        assert_eq!(account, SubscriptionFilter::Account);
+    }
+
+    #[test]
+    fn ping_and_enable() {
+        let request = PingWebhookSubscription::new("PY1OQ9R");
+
+        assert_eq!(request.url(), "https://api.pagerduty.com/webhook_subscriptions/PY1OQ9R/ping");
+        assert!(PingWebhookSubscription::get_response(StatusCode::Accepted, &Headers::new(), "")
+            .unwrap()
+            .success()
+            .is_some());
+
+        let request = EnableWebhookSubscription::new("PY1OQ9R");
+        assert_eq!(request.url(),
+                   "https://api.pagerduty.com/webhook_subscriptions/PY1OQ9R/enable");
+
+        let body = stringify!({
+            "webhook_subscription": {
+                "id": "PY1OQ9R",
+                "active": true,
+                "delivery_method": {
+                    "type": "http_delivery_method",
+                    "url": "https://example.com/pagerduty",
+                    "temporarily_disabled": false
+                },
+                "events": ["incident.triggered"],
+                "filter": { "type": "account_reference" }
+            }
+        });
+
+        let res = EnableWebhookSubscription::get_response(StatusCode::Ok, &Headers::new(), body);
+        let subscription = res.unwrap().success().unwrap();
+
+        assert!(!subscription.delivery_method.temporarily_disabled);
+    }
+}
@@ -0,0 +1,115 @@
+//! Timestamps
+//!
+//! PagerDuty represents times as ISO 8601 strings. Setters taking a time accept any
+//! [`Timestamp`](trait.Timestamp.html), which is implemented for strings already in ISO 8601
+//! and, with the `chrono` feature, for `chrono::DateTime`.
+//!
+//! With the `chrono` feature, [`parse`](fn.parse.html) reads the timestamps PagerDuty returns,
+//! and types such as incidents and webhook events have accessors returning their times as
+//! `DateTime<Utc>`.
+//!
+//! # Example
+//!
+//! ```
+//! use pagerduty::rest::log_entries::ListLogEntries;
+//!
+//! let request = ListLogEntries::new().set_since("2015-11-07T00:00:00Z");
+//! # let _ = request;
+//! ```
+
+use std::borrow::Cow;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, TimeZone, Utc};
+#[cfg(feature = "chrono")]
+use std::fmt::Display;
+
+/// A time which can be sent to PagerDuty
+pub trait Timestamp<'a> {
+    /// The time in ISO 8601
+    fn into_timestamp(self) -> Cow<'a, str>;
+}
+
+impl<'a> Timestamp<'a> for &'a str {
+    fn into_timestamp(self) -> Cow<'a, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> Timestamp<'a> for &'a String {
+    fn into_timestamp(self) -> Cow<'a, str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<'a> Timestamp<'a> for String {
+    fn into_timestamp(self) -> Cow<'a, str> {
+        Cow::Owned(self)
+    }
+}
+
+impl<'a> Timestamp<'a> for Cow<'a, str> {
+    fn into_timestamp(self) -> Cow<'a, str> {
+        self
+    }
+}
+
+/// Formatted in RFC 3339, keeping the time's offset
+#[cfg(feature = "chrono")]
+impl<'a, Tz> Timestamp<'a> for DateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Display
+{
+    fn into_timestamp(self) -> Cow<'a, str> {
+        Cow::Owned(self.to_rfc3339())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, Tz> Timestamp<'a> for &DateTime<Tz>
+    where Tz: TimeZone,
+          Tz::Offset: Display
+{
+    fn into_timestamp(self) -> Cow<'a, str> {
+        Cow::Owned(self.to_rfc3339())
+    }
+}
+
+/// Parse a timestamp returned by PagerDuty, converting it to UTC
+///
+/// This requires the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub fn parse(timestamp: &str) -> Result<DateTime<Utc>, ParseError> {
+    DateTime::parse_from_rfc3339(timestamp).map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::Timestamp;
+
+    #[test]
+    fn strings_are_unchanged() {
+        assert_eq!("2015-11-07T00:00:00Z".into_timestamp(), "2015-11-07T00:00:00Z");
+        assert_eq!(String::from("2015-11-07T00:00:00Z").into_timestamp(),
+                   Cow::Borrowed("2015-11-07T00:00:00Z"));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_round_trip() {
+        use chrono::{FixedOffset, TimeZone, Utc};
+        use super::parse;
+
+        let time = FixedOffset::west_opt(5 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2015, 11, 8, 0, 0, 0)
+            .unwrap();
+
+        assert_eq!(time.into_timestamp(), "2015-11-08T00:00:00-05:00");
+        assert_eq!(parse("2015-11-08T00:00:00-05:00").unwrap(),
+                   Utc.with_ymd_and_hms(2015, 11, 8, 5, 0, 0).unwrap());
+        assert!(parse("yesterday").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! De-duplicating webhooks
+//!
+//! PagerDuty may deliver a webhook more than once, such as when a response to an earlier delivery
+//! timed out. [`SeenEvents`](struct.SeenEvents.html) remembers the IDs of the most recently seen
+//! events so redeliveries can be recognized; give one to a
+//! [`Dispatcher`](../dispatch/struct.Dispatcher.html) and its handler sees each event at most
+//! once.
+//!
+//! The IDs can be saved to a file and loaded again so they survive restarts.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// The IDs of the most recently seen events
+///
+/// At most `capacity` IDs are remembered. Once full, the least recently seen ID is forgotten to
+/// make room for a new one.
+#[derive(Debug, Clone)]
+pub struct SeenEvents {
+    capacity: usize,
+
+    /// The stamp of the last time each ID was seen
+    ids: HashMap<String, u64>,
+
+    /// IDs in the order they were seen, including stale entries of IDs seen again since
+    order: VecDeque<(u64, String)>,
+
+    next_stamp: u64,
+}
+
+impl SeenEvents {
+    /// Create an empty set remembering up to `capacity` IDs
+    pub fn new(capacity: usize) -> SeenEvents {
+        SeenEvents {
+            capacity,
+            ids: HashMap::new(),
+            order: VecDeque::new(),
+            next_stamp: 0,
+        }
+    }
+
+    /// Load the IDs saved to `path` with [`save`](#method.save)
+    ///
+    /// An empty set is returned if `path` doesn't exist. If the file holds more than `capacity`
+    /// IDs, only the most recently seen are kept.
+    pub fn load<P>(path: P, capacity: usize) -> io::Result<SeenEvents>
+        where P: AsRef<Path>
+    {
+        let mut seen = SeenEvents::new(capacity);
+
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(seen),
+            Err(err) => return Err(err),
+        };
+
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.is_empty() {
+                seen.insert(line);
+            }
+        }
+
+        Ok(seen)
+    }
+
+    /// Save the IDs to `path`, one per line from least to most recently seen
+    pub fn save<P>(&self, path: P) -> io::Result<()>
+        where P: AsRef<Path>
+    {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        for &(stamp, ref id) in &self.order {
+            if self.ids.get(id) == Some(&stamp) {
+                writeln!(file, "{}", id)?;
+            }
+        }
+
+        file.flush()
+    }
+
+    /// Whether event `id` has been seen
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.contains_key(id)
+    }
+
+    /// Record that event `id` was seen, returning whether it hadn't been before
+    pub fn insert<S>(&mut self, id: S) -> bool
+        where S: Into<String>
+    {
+        let id = id.into();
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+
+        let new = self.ids.insert(id.clone(), stamp).is_none();
+        self.order.push_back((stamp, id));
+
+        while self.ids.len() > self.capacity {
+            let (stamp, id) = self.order.pop_front().expect("every ID has an entry in order");
+            if self.ids.get(&id) == Some(&stamp) {
+                self.ids.remove(&id);
+            }
+        }
+
+        // Drop the stale entries left by IDs seen more than once
+        if self.order.len() > 2 * self.capacity {
+            let ids = &self.ids;
+            self.order.retain(|&(stamp, ref id)| ids.get(id) == Some(&stamp));
+        }
+
+        new
+    }
+
+    /// Forget event `id`, returning whether it had been seen
+    ///
+    /// This lets the redelivery of an event whose handling failed be handled again.
+    pub fn remove(&mut self, id: &str) -> bool {
+        self.ids.remove(id).is_some()
+    }
+
+    /// The number of IDs remembered
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether no IDs are remembered
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The maximum number of IDs remembered
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use super::SeenEvents;
+
+    #[test]
+    fn forget_least_recently_seen() {
+        let mut seen = SeenEvents::new(2);
+
+        assert!(seen.insert("a"));
+        assert!(seen.insert("b"));
+        assert!(!seen.insert("a"));
+        assert!(seen.insert("c"));
+
+        assert!(seen.contains("a"));
+        assert!(!seen.contains("b"));
+        assert!(seen.contains("c"));
+        assert_eq!(seen.len(), 2);
+
+        assert!(seen.remove("c"));
+        assert!(!seen.remove("c"));
+        assert!(seen.insert("c"));
+
+        // Unique to this process, so concurrent test runs don't share the file
+        let name = format!("pagerduty-seen-events-{}-forget_least_recently_seen", process::id());
+        let path = env::temp_dir().join(name);
+        seen.save(&path).unwrap();
+
+        let loaded = SeenEvents::load(&path, 1).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(!loaded.contains("a"));
+        assert!(loaded.contains("c"));
+        assert!(SeenEvents::load(&path, 1).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,370 @@
+//! Dispatching webhooks to handlers
+//!
+//! A [`Dispatcher`](struct.Dispatcher.html) takes the body and headers of an incoming V3 webhook,
+//! verifies its [signature](../signature/index.html), parses it, and calls the method of its
+//! [`WebhookHandler`](trait.WebhookHandler.html) matching the event's data. Webhooks which fail
+//! any of these steps are reported as an [`Error`](enum.Error.html), so the receiver can answer
+//! with an error status.
+//!
+//! Redelivered webhooks are recognized by giving the dispatcher a
+//! [`SeenEvents`](../dedup/struct.SeenEvents.html), in which case they're reported as
+//! `Error::Duplicate` instead of being handled again.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # extern crate hyper;
+//! # extern crate pagerduty;
+//! use hyper::header::Headers;
+//! use pagerduty::webhooks::dispatch::{Dispatcher, WebhookHandler};
+//! use pagerduty::webhooks::v3::{Event, Incident};
+//!
+//! struct Printer;
+//!
+//! impl WebhookHandler for Printer {
+//!     fn on_incident(&mut self, event: &Event, incident: &Incident) {
+//!         println!("{}: #{}", event.event_type, incident.number);
+//!     }
+//! }
+//!
+//! # fn main() {
+//! # let (body, headers) = (Vec::new(), Headers::new());
+//! let mut dispatcher = Dispatcher::new("subscription secret", Printer);
+//! dispatcher.dispatch(&body, &headers).unwrap();
+//! # }
+//! ```
+
+use std::fmt;
+use std::str;
+
+use hyper::header::Headers;
+use serde_json;
+
+use rest::webhook_subscriptions::EventType;
+use super::dedup::SeenEvents;
+use super::signature::{self, SIGNATURE_HEADER};
+use super::v3::{ConferenceBridge, Event, EventData, Incident, IncidentNote, IncidentResponder,
+                Ping, Service, StatusUpdate, Webhook};
+
+/// Handles the events of webhooks
+///
+/// Each method handles the events whose data is of one type, and does nothing by default.
+pub trait WebhookHandler {
+    /// An incident changed, such as by being triggered, acknowledged, or reprioritized
+    fn on_incident(&mut self, _event: &Event, _incident: &Incident) {}
+
+    /// A note was added to an incident
+    fn on_incident_note(&mut self, _event: &Event, _note: &IncidentNote) {}
+
+    /// A responder was requested for an incident, or replied to the request
+    fn on_incident_responder(&mut self, _event: &Event, _responder: &IncidentResponder) {}
+
+    /// A status update was published on an incident
+    fn on_status_update(&mut self, _event: &Event, _update: &StatusUpdate) {}
+
+    /// The conference bridge of an incident changed
+    fn on_conference_bridge(&mut self, _event: &Event, _bridge: &ConferenceBridge) {}
+
+    /// A service was created, updated, or deleted
+    fn on_service(&mut self, _event: &Event, _service: &Service) {}
+
+    /// The webhook subscription was pinged
+    fn on_ping(&mut self, _event: &Event, _ping: &Ping) {}
+}
+
+/// Verifies, parses, and routes webhooks to a handler
+pub struct Dispatcher<H> {
+    secret: Vec<u8>,
+    handler: H,
+    seen: Option<SeenEvents>,
+}
+
+impl<H: fmt::Debug> fmt::Debug for Dispatcher<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dispatcher")
+            .field("secret", &"[REDACTED]")
+            .field("handler", &self.handler)
+            .field("seen", &self.seen)
+            .finish()
+    }
+}
+
+impl<H: WebhookHandler> Dispatcher<H> {
+    /// Create a dispatcher of webhooks signed with `secret` to `handler`
+    ///
+    /// The secret is returned when the webhook subscription is created.
+    pub fn new<S>(secret: S, handler: H) -> Dispatcher<H>
+        where S: Into<Vec<u8>>
+    {
+        Dispatcher {
+            secret: secret.into(),
+            handler,
+            seen: None,
+        }
+    }
+
+    /// Skip events in `seen`, and add the events handled to it
+    pub fn set_seen_events(mut self, seen: SeenEvents) -> Self {
+        self.seen = Some(seen);
+        self
+    }
+
+    /// The events seen, to be saved before exiting
+    pub fn seen_events(&self) -> Option<&SeenEvents> {
+        self.seen.as_ref()
+    }
+
+    /// The events seen, such as to forget an event whose handling failed after being dispatched
+    pub fn seen_events_mut(&mut self) -> Option<&mut SeenEvents> {
+        self.seen.as_mut()
+    }
+
+    /// The handler webhooks are dispatched to
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// The handler webhooks are dispatched to
+    pub fn handler_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+
+    /// Consume the dispatcher, returning its handler
+    pub fn into_handler(self) -> H {
+        self.handler
+    }
+
+    /// Dispatch the webhook with `body` and `headers` to the handler
+    ///
+    /// The event is returned once handled. Events of types this library doesn't know about are
+    /// returned as `Error::UnknownEventType` without being handled, as are events already seen as
+    /// `Error::Duplicate`.
+    pub fn dispatch(&mut self, body: &[u8], headers: &Headers) -> Result<Event> {
+        let event = parse(&self.secret, body, headers)?;
+
+        if self.seen.as_ref().is_some_and(|seen| seen.contains(&event.id)) {
+            return Err(Error::Duplicate(event.id));
+        }
+
+        route(&mut self.handler, &event)?;
+
+        // Only events which were handled are recorded, so redeliveries of the others aren't
+        // mistaken for duplicates.
+        if let Some(ref mut seen) = self.seen {
+            seen.insert(&event.id[..]);
+        }
+
+        Ok(event)
+    }
+}
+
+/// Verify the signature of the webhook with `body` and `headers`, and parse its event
+///
+/// Unlike [`Dispatcher::dispatch`](struct.Dispatcher.html#method.dispatch), events of any type
+/// are returned.
+pub fn parse(secret: &[u8], body: &[u8], headers: &Headers) -> Result<Event> {
+    verify(secret, body, headers)?;
+
+    Ok(Webhook::from_slice(body)?.event)
+}
+
+/// Check that the signature headers of a webhook hold a signature of `body` by `secret`
+fn verify(secret: &[u8], body: &[u8], headers: &Headers) -> Result<()> {
+    let values = match headers.get_raw(SIGNATURE_HEADER) {
+        Some(values) => values,
+        None => return Err(Error::MissingSignature),
+    };
+
+    let valid = values.iter()
+        .filter_map(|value| str::from_utf8(value).ok())
+        .any(|value| signature::verify(secret, body, value));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
+fn route<H: WebhookHandler>(handler: &mut H, event: &Event) -> Result<()> {
+    if let EventType::Other(ref kind) = event.event_type {
+        return Err(Error::UnknownEventType(kind.clone()));
+    }
+
+    match event.data {
+        EventData::Incident(ref incident) => handler.on_incident(event, incident),
+        EventData::IncidentNote(ref note) => handler.on_incident_note(event, note),
+        EventData::IncidentResponder(ref responder) => {
+            handler.on_incident_responder(event, responder)
+        },
+        EventData::StatusUpdate(ref update) => handler.on_status_update(event, update),
+        EventData::ConferenceBridge(ref bridge) => handler.on_conference_bridge(event, bridge),
+        EventData::Service(ref service) => handler.on_service(event, service),
+        EventData::Ping(ref ping) => handler.on_ping(event, ping),
+        EventData::Other(_) => {
+            return Err(Error::UnknownEventType(event.event_type.as_str().to_owned()));
+        },
+    }
+
+    Ok(())
+}
+
+/// Reasons a webhook couldn't be dispatched
+#[derive(Debug)]
+pub enum Error {
+    /// The webhook has no signature header
+    MissingSignature,
+
+    /// None of the signatures of the webhook were made with the secret
+    InvalidSignature,
+
+    /// The body of the webhook isn't a V3 webhook
+    Deserialize(serde_json::Error),
+
+    /// The event is of a type, or has data of a type, this library doesn't know about
+    UnknownEventType(String),
+
+    /// The event with this ID was already dispatched
+    ///
+    /// The webhook is a redelivery, and should still be answered with a success status so
+    /// PagerDuty stops retrying it.
+    Duplicate(String),
+}
+
+impl ::std::error::Error for Error {
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        match *self {
+            Error::Deserialize(ref err) => Some(err),
+            Error::MissingSignature |
+            Error::InvalidSignature |
+            Error::UnknownEventType(_) |
+            Error::Duplicate(_) => None,
+        }
+    }
+
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        match *self {
+            Error::MissingSignature => "Missing webhook signature",
+            Error::InvalidSignature => "Invalid webhook signature",
+            Error::Deserialize(ref err) => err.description(),
+            Error::UnknownEventType(_) => "Unknown webhook event type",
+            Error::Duplicate(_) => "Duplicate webhook event",
+        }
+    }
+}
+
+impl ::std::fmt::Display for Error {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            Error::MissingSignature => {
+                write!(f, "Webhook has no {} header", SIGNATURE_HEADER)
+            },
+            Error::InvalidSignature => write!(f, "Webhook signature doesn't match the secret"),
+            Error::Deserialize(ref err) => {
+                write!(f, "Error deserializing webhook as JSON: {}", err)
+            },
+            Error::UnknownEventType(ref kind) => write!(f, "Unknown webhook event type {}", kind),
+            Error::Duplicate(ref id) => write!(f, "Webhook event {} was already dispatched", id),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(val: serde_json::Error) -> Error {
+        Error::Deserialize(val)
+    }
+}
+
+/// A result from dispatching a webhook
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use hmac::{Hmac, Mac};
+    use hyper::header::Headers;
+    use sha2::Sha256;
+
+    use webhooks::dedup::SeenEvents;
+    use webhooks::v3::{Event, Incident};
+    use super::{Dispatcher, Error, WebhookHandler};
+
+    #[derive(Default)]
+    struct Counter {
+        incidents: Vec<u64>,
+    }
+
+    impl WebhookHandler for Counter {
+        fn on_incident(&mut self, _event: &Event, incident: &Incident) {
+            self.incidents.push(incident.number);
+        }
+    }
+
+    fn signed(secret: &[u8], body: &[u8]) -> Headers {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(body);
+
+        let signature: String = mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        let mut headers = Headers::new();
+        headers.set_raw("X-PagerDuty-Signature", vec![format!("v1={}", signature).into_bytes()]);
+        headers
+    }
+
+    #[test]
+    fn dispatch_to_handler() {
+        let body = stringify!({
+            "event": {
+                "id": "5ac64822",
+                "event_type": "incident.triggered",
+                "resource_type": "incident",
+                "occurred_at": "2023-01-03T10:00:00Z",
+                "data": {
+                    "id": "PT4KHLK",
+                    "type": "incident",
+                    "number": 1234,
+                    "status": "triggered",
+                    "created_at": "2023-01-03T10:00:00Z"
+                }
+            }
+        });
+
+        let mut dispatcher = Dispatcher::new("secret", Counter::default())
+            .set_seen_events(SeenEvents::new(10));
+
+        match dispatcher.dispatch(body.as_bytes(), &Headers::new()) {
+            Err(Error::MissingSignature) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        match dispatcher.dispatch(body.as_bytes(), &signed(b"other", body.as_bytes())) {
+            Err(Error::InvalidSignature) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let event = dispatcher.dispatch(body.as_bytes(), &signed(b"secret", body.as_bytes()));
+        assert_eq!(event.unwrap().id, "5ac64822");
+        assert_eq!(dispatcher.handler().incidents, vec![1234]);
+
+        match dispatcher.dispatch(body.as_bytes(), &signed(b"secret", body.as_bytes())) {
+            Err(Error::Duplicate(ref id)) if id == "5ac64822" => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(dispatcher.handler().incidents, vec![1234]);
+
+        let unknown = body.replace("5ac64822", "5ac64823")
+            .replace("incident.triggered", "incident.paused");
+        for _ in 0..2 {
+            let headers = signed(b"secret", unknown.as_bytes());
+            match dispatcher.dispatch(unknown.as_bytes(), &headers) {
+                Err(Error::UnknownEventType(ref kind)) if kind == "incident.paused" => (),
+                other => panic!("unexpected result: {:?}", other),
+            }
+        }
+        assert!(!dispatcher.seen_events().unwrap().contains("5ac64823"));
+    }
+}
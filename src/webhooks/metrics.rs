@@ -0,0 +1,301 @@
+//! Incident metrics
+//!
+//! [`IncidentMetrics`](struct.IncidentMetrics.html) follows incidents as they're opened and
+//! resolved, and reports them to a [`MetricsSink`](trait.MetricsSink.html), such as a client of
+//! StatsD or Prometheus, tagged by service and priority. Resolved incidents are reported with
+//! how long they were open, for tracking time to resolve against an error budget.
+//!
+//! Incidents can come from V3 webhooks, by dispatching them to the emitter as a
+//! [`WebhookHandler`](../dispatch/trait.WebhookHandler.html), or from polling
+//! [`rest::incidents::list`](../../rest/incidents/fn.list.html) and passing each incident to
+//! [`observe`](struct.IncidentMetrics.html#method.observe).
+//!
+//! This module requires the `chrono` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # extern crate hyper;
+//! # extern crate pagerduty;
+//! use std::time::Duration;
+//!
+//! use hyper::header::Headers;
+//! use pagerduty::webhooks::dispatch::Dispatcher;
+//! use pagerduty::webhooks::metrics::{IncidentMetrics, MetricsSink, Tags};
+//!
+//! struct Printer;
+//!
+//! impl MetricsSink for Printer {
+//!     fn incident_opened(&mut self, tags: &Tags) {
+//!         println!("incidents.opened {:?}", tags);
+//!     }
+//!
+//!     fn incident_resolved(&mut self, duration: Duration, tags: &Tags) {
+//!         println!("incidents.time_to_resolve {}s {:?}", duration.as_secs(), tags);
+//!     }
+//! }
+//!
+//! # fn main() {
+//! # let (body, headers) = (Vec::new(), Headers::new());
+//! let mut dispatcher = Dispatcher::new("subscription secret", IncidentMetrics::new(Printer));
+//! dispatcher.dispatch(&body, &headers).unwrap();
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use rest::Reference;
+use rest::incidents::{Incident as RestIncident, IncidentStatus as RestIncidentStatus};
+use rest::webhook_subscriptions::EventType;
+use timestamp;
+use super::dispatch::WebhookHandler;
+use super::v3::{Event, Incident, IncidentStatus};
+
+/// Receives the metrics of incidents
+pub trait MetricsSink {
+    /// An incident was opened
+    fn incident_opened(&mut self, tags: &Tags);
+
+    /// An incident was resolved, having been open for `duration`
+    fn incident_resolved(&mut self, duration: Duration, tags: &Tags);
+}
+
+/// What an incident's metrics are tagged with
+///
+/// Each tag is the summary of the object, or its ID when the incident doesn't include a summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tags {
+    /// The service the incident belongs to
+    pub service: Option<String>,
+
+    /// The priority of the incident, if it has one
+    pub priority: Option<String>,
+}
+
+impl Tags {
+    fn new(service: Option<&Reference>, priority: Option<&Reference>) -> Tags {
+        Tags {
+            service: service.map(tag),
+            priority: priority.map(tag),
+        }
+    }
+}
+
+fn tag(reference: &Reference) -> String {
+    reference.summary.clone().unwrap_or_else(|| reference.id.clone())
+}
+
+/// Reports incidents to a sink as they're opened and resolved
+///
+/// The IDs of the incidents seen open are remembered, so that each incident is reported as opened
+/// once however many events or polls it's seen in, until it's resolved.
+#[derive(Debug)]
+pub struct IncidentMetrics<S> {
+    sink: S,
+    open: HashSet<String>,
+}
+
+impl<S: MetricsSink> IncidentMetrics<S> {
+    /// Create an emitter of metrics to `sink`
+    pub fn new(sink: S) -> IncidentMetrics<S> {
+        IncidentMetrics {
+            sink,
+            open: HashSet::new(),
+        }
+    }
+
+    /// The sink metrics are reported to
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// The sink metrics are reported to
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Consume the emitter, returning its sink
+    pub fn into_sink(self) -> S {
+        self.sink
+    }
+
+    /// The number of incidents seen open and not yet resolved
+    pub fn open_incidents(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Report an incident returned by polling the incidents of the account
+    ///
+    /// Polled incidents are only reported as resolved if an earlier poll saw them open, so
+    /// incidents still resolved in later polls aren't reported again. Polls should include
+    /// resolved incidents, or incidents resolved between polls are never reported as resolved.
+    /// An incident whose times can't be parsed isn't reported as resolved.
+    pub fn observe(&mut self, incident: &RestIncident) {
+        let tags = Tags::new(Some(&incident.service), incident.priority.as_ref());
+
+        match incident.status {
+            RestIncidentStatus::Triggered | RestIncidentStatus::Acknowledged => {
+                self.opened(&incident.id, &tags);
+            },
+            RestIncidentStatus::Resolved => {
+                if !self.open.remove(&incident.id) {
+                    return;
+                }
+
+                if let Some(ref resolved_at) = incident.last_status_change_at {
+                    self.resolved(&incident.created_at, resolved_at, &tags);
+                }
+            },
+        }
+    }
+
+    fn opened(&mut self, id: &str, tags: &Tags) {
+        if self.open.insert(id.to_owned()) {
+            self.sink.incident_opened(tags);
+        }
+    }
+
+    fn resolved(&mut self, created_at: &str, resolved_at: &str, tags: &Tags) {
+        let duration = match (timestamp::parse(created_at), timestamp::parse(resolved_at)) {
+            (Ok(created_at), Ok(resolved_at)) => resolved_at.signed_duration_since(created_at),
+            _ => return,
+        };
+
+        // Times from before the incident was created, from clocks out of step, count as zero.
+        self.sink.incident_resolved(duration.to_std().unwrap_or_default(), tags);
+    }
+}
+
+/// Reports the incidents of `incident.*` events
+///
+/// Incidents resolved by `incident.resolved` events are reported as resolved whether or not they
+/// were seen open, since a dispatcher with [`SeenEvents`](../dedup/struct.SeenEvents.html) only
+/// handles each event once.
+impl<S: MetricsSink> WebhookHandler for IncidentMetrics<S> {
+    fn on_incident(&mut self, event: &Event, incident: &Incident) {
+        let tags = Tags::new(incident.service.as_ref(), incident.priority.as_ref());
+
+        match incident.status {
+            IncidentStatus::Triggered | IncidentStatus::Acknowledged => {
+                self.opened(&incident.id, &tags);
+            },
+            IncidentStatus::Resolved if event.event_type == EventType::IncidentResolved => {
+                self.open.remove(&incident.id);
+                self.resolved(&incident.created_at, &event.occurred_at, &tags);
+            },
+            IncidentStatus::Resolved | IncidentStatus::Unknown => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json;
+
+    use rest::incidents::Incident as RestIncident;
+    use webhooks::dispatch::WebhookHandler;
+    use webhooks::v3::{EventData, Webhook};
+    use super::{IncidentMetrics, MetricsSink, Tags};
+
+    #[derive(Debug, Default)]
+    struct Recorder {
+        opened: Vec<Tags>,
+        resolved: Vec<(Duration, Tags)>,
+    }
+
+    impl MetricsSink for Recorder {
+        fn incident_opened(&mut self, tags: &Tags) {
+            self.opened.push(tags.clone());
+        }
+
+        fn incident_resolved(&mut self, duration: Duration, tags: &Tags) {
+            self.resolved.push((duration, tags.clone()));
+        }
+    }
+
+    fn tags(service: &str, priority: Option<&str>) -> Tags {
+        Tags {
+            service: Some(service.to_owned()),
+            priority: priority.map(|priority| priority.to_owned()),
+        }
+    }
+
+    fn handle(metrics: &mut IncidentMetrics<Recorder>, event_type: &str, status: &str, at: &str) {
+        let body = format!(r#"{{"event": {{
+            "id": "{}-{}",
+            "event_type": "{}",
+            "resource_type": "incident",
+            "occurred_at": "{}",
+            "data": {{
+                "id": "PT4KHLK",
+                "type": "incident",
+                "number": 1234,
+                "status": "{}",
+                "created_at": "2023-01-03T10:00:00Z",
+                "service": {{ "id": "PIJ90N7", "type": "service_reference", "summary": "Web" }},
+                "priority": {{ "id": "PSO75BM", "type": "priority", "summary": "P1" }}
+            }}
+        }}}}"#, event_type, at, event_type, at, status);
+
+        let event = body.parse::<Webhook>().unwrap().event;
+        match event.data {
+            EventData::Incident(ref incident) => metrics.on_incident(&event, incident),
+            ref other => panic!("unexpected data: {:?}", other),
+        }
+    }
+
+    fn polled(status: &str, last_status_change_at: &str) -> RestIncident {
+        serde_json::from_str(&format!(r#"{{
+            "id": "PT4KHLK",
+            "incident_number": 1234,
+            "created_at": "2015-10-06T21:30:42Z",
+            "status": "{}",
+            "service": {{ "id": "PIJ90N7", "type": "service_reference" }},
+            "assignments": [],
+            "last_status_change_at": "{}",
+            "priority": null,
+            "urgency": "high"
+        }}"#, status, last_status_change_at)).unwrap()
+    }
+
+    #[test]
+    fn webhooks_report_time_to_resolve() {
+        let mut metrics = IncidentMetrics::new(Recorder::default());
+
+        handle(&mut metrics, "incident.triggered", "triggered", "2023-01-03T10:00:00Z");
+        handle(&mut metrics, "incident.acknowledged", "acknowledged", "2023-01-03T10:05:00Z");
+        assert_eq!(metrics.open_incidents(), 1);
+
+        handle(&mut metrics, "incident.priority_updated", "resolved", "2023-01-03T10:20:00Z");
+        assert!(metrics.sink().resolved.is_empty());
+
+        handle(&mut metrics, "incident.resolved", "resolved", "2023-01-03T10:30:00Z");
+        assert_eq!(metrics.open_incidents(), 0);
+
+        let recorder = metrics.into_sink();
+        assert_eq!(recorder.opened, vec![tags("Web", Some("P1"))]);
+        assert_eq!(recorder.resolved,
+                   vec![(Duration::from_secs(30 * 60), tags("Web", Some("P1")))]);
+    }
+
+    #[test]
+    fn polls_report_incidents_seen_open() {
+        let mut metrics = IncidentMetrics::new(Recorder::default());
+
+        metrics.observe(&polled("resolved", "2015-10-06T21:40:42Z"));
+        assert!(metrics.sink().resolved.is_empty());
+
+        metrics.observe(&polled("triggered", "2015-10-06T21:30:42Z"));
+        metrics.observe(&polled("acknowledged", "2015-10-06T21:35:42Z"));
+        metrics.observe(&polled("resolved", "2015-10-06T21:45:42Z"));
+        metrics.observe(&polled("resolved", "2015-10-06T21:45:42Z"));
+
+        let recorder = metrics.into_sink();
+        assert_eq!(recorder.opened, vec![tags("PIJ90N7", None)]);
+        assert_eq!(recorder.resolved,
+                   vec![(Duration::from_secs(15 * 60), tags("PIJ90N7", None))]);
+    }
+}
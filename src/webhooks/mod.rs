@@ -0,0 +1,30 @@
+//! Webhooks
+//!
+//! Types for the webhooks PagerDuty sends to receivers. These are the payloads of incoming HTTP
+//! requests rather than API responses, so they're parsed with the `from_slice` and `from_str`
+//! constructors of each version's types.
+//!
+//! * [`v2`](v2/index.html): webhooks of the deprecated V2 extensions, sent in batches of messages
+//! * [`v3`](v3/index.html): webhooks of webhook subscriptions, one event per request
+//!
+//! Webhooks are sent to endpoints configured with
+//! [`rest::webhook_subscriptions`](../rest/webhook_subscriptions/index.html) (V3) or as
+//! extensions (V2).
+//!
+//! V3 webhooks are signed with the secret of their subscription, which is checked with
+//! [`signature`](signature/index.html). [`dispatch`](dispatch/index.html) does the checking and
+//! parsing, and hands each event to the matching method of a handler, skipping redeliveries
+//! remembered by [`dedup`](dedup/index.html). With the `server` feature,
+//! [`server`](server/index.html) receives webhooks over HTTP and sends their events over a channel.
+//! With the `chrono` feature, [`metrics`](metrics/index.html) reports how long incidents were open
+//! to a metrics sink.
+
+pub mod dedup;
+pub mod dispatch;
+#[cfg(feature = "chrono")]
+pub mod metrics;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod signature;
+pub mod v2;
+pub mod v3;
@@ -0,0 +1,278 @@
+//! Receiving webhooks with hyper
+//!
+//! A [`WebhookService`](struct.WebhookService.html) is a hyper `Handler` which accepts V3
+//! webhooks, verifies their signatures, and sends their events over a channel. Requests which
+//! aren't signed with the secret are refused, so only genuine events reach the channel. This is
+//! enough for a small daemon to receive webhooks without a web framework.
+//!
+//! Webhooks go through a [`Dispatcher`](../dispatch/struct.Dispatcher.html), so redeliveries can
+//! be skipped by giving the service a [`SeenEvents`](../dedup/struct.SeenEvents.html).
+//!
+//! This module requires the `server` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use pagerduty::webhooks::server;
+//! use pagerduty::webhooks::v3::EventData;
+//!
+//! let (_listening, events) = server::listen("0.0.0.0:8080", "subscription secret").unwrap();
+//!
+//! for event in events {
+//!     if let EventData::Incident(incident) = event.data {
+//!         println!("{}: #{}", event.event_type, incident.number);
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+use std::io::Read;
+use std::net::ToSocketAddrs;
+use std::sync::Mutex;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use hyper;
+use hyper::header::Headers;
+use hyper::method::Method;
+use hyper::server::{Handler, Listening, Request, Response, Server};
+use hyper::status::StatusCode;
+
+use super::dedup::SeenEvents;
+use super::dispatch::{Dispatcher, Error, WebhookHandler};
+use super::v3::Event;
+
+/// Default maximum size of webhook bodies, in bytes
+pub const DEFAULT_MAX_BODY_SIZE: u64 = 1024 * 1024;
+
+/// A hyper handler forwarding the events of signed webhooks over a channel
+///
+/// Responses are:
+///
+/// * `204 No Content` once the event is sent over the channel, or if it was already sent or is
+///   of a type this library doesn't know about; neither is sent
+/// * `400 Bad Request` if the body isn't a V3 webhook
+/// * `401 Unauthorized` if the webhook isn't signed with the secret
+/// * `405 Method Not Allowed` for requests other than `POST`
+/// * `413 Payload Too Large` if the body is larger than the maximum size
+/// * `503 Service Unavailable` if the receiver of the channel was dropped, so PagerDuty retries
+pub struct WebhookService {
+    dispatcher: Mutex<Dispatcher<Forward>>,
+    sender: Mutex<Sender<Event>>,
+    max_body_size: u64,
+}
+
+/// The events are sent over the channel once dispatched, rather than by the handler, so sending
+/// failures can be answered with an error status.
+#[derive(Debug)]
+struct Forward;
+
+impl WebhookHandler for Forward {}
+
+impl fmt::Debug for WebhookService {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WebhookService")
+            .field("dispatcher", &self.dispatcher)
+            .field("max_body_size", &self.max_body_size)
+            .finish()
+    }
+}
+
+impl WebhookService {
+    /// Create a service sending the events of webhooks signed with `secret` to `sender`
+    pub fn new<S>(secret: S, sender: Sender<Event>) -> WebhookService
+        where S: Into<Vec<u8>>
+    {
+        WebhookService {
+            dispatcher: Mutex::new(Dispatcher::new(secret, Forward)),
+            sender: Mutex::new(sender),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    /// Refuse webhooks with bodies larger than `size` bytes
+    pub fn set_max_body_size(mut self, size: u64) -> Self {
+        self.max_body_size = size;
+        self
+    }
+
+    /// Skip events in `seen`, and add the events sent over the channel to it
+    ///
+    /// Give the service to `hyper::server::Server::handle` rather than using `listen` to enable
+    /// this.
+    pub fn set_seen_events(self, seen: SeenEvents) -> Self {
+        let dispatcher = self.dispatcher.into_inner().unwrap_or_else(|err| err.into_inner());
+
+        WebhookService {
+            dispatcher: Mutex::new(dispatcher.set_seen_events(seen)),
+            sender: self.sender,
+            max_body_size: self.max_body_size,
+        }
+    }
+
+    fn receive<R>(&self, method: &Method, headers: &Headers, req: R) -> StatusCode
+        where R: Read
+    {
+        if *method != Method::Post {
+            return StatusCode::MethodNotAllowed;
+        }
+
+        let mut body = Vec::new();
+        if req.take(self.max_body_size + 1).read_to_end(&mut body).is_err() {
+            return StatusCode::BadRequest;
+        }
+
+        if body.len() as u64 > self.max_body_size {
+            return StatusCode::PayloadTooLarge;
+        }
+
+        let mut dispatcher = self.dispatcher.lock().unwrap_or_else(|err| err.into_inner());
+
+        let event = match dispatcher.dispatch(&body, headers) {
+            Ok(event) => event,
+            Err(Error::Duplicate(_)) | Err(Error::UnknownEventType(_)) => {
+                return StatusCode::NoContent;
+            },
+            Err(Error::MissingSignature) | Err(Error::InvalidSignature) => {
+                return StatusCode::Unauthorized;
+            },
+            Err(Error::Deserialize(_)) => return StatusCode::BadRequest,
+        };
+
+        let sender = self.sender.lock().unwrap_or_else(|err| err.into_inner());
+        match sender.send(event) {
+            Ok(()) => StatusCode::NoContent,
+            Err(err) => {
+                // Let the redelivery through once there's a receiver again
+                if let Some(seen) = dispatcher.seen_events_mut() {
+                    seen.remove(&err.0.id);
+                }
+
+                StatusCode::ServiceUnavailable
+            },
+        }
+    }
+}
+
+impl Handler for WebhookService {
+    fn handle(&self, mut req: Request, mut res: Response) {
+        let method = req.method.clone();
+        let headers = req.headers.clone();
+        *res.status_mut() = self.receive(&method, &headers, &mut req);
+    }
+}
+
+/// Listen for webhooks signed with `secret` on `addr`
+///
+/// Events are received from the returned channel. As with any hyper server, dropping the
+/// `Listening` blocks until the server's thread exits, while closing it leaves the server running
+/// in the background.
+pub fn listen<A, S>(addr: A, secret: S) -> hyper::Result<(Listening, Receiver<Event>)>
+    where A: ToSocketAddrs,
+          S: Into<Vec<u8>>
+{
+    let (sender, receiver) = channel();
+    let listening = Server::http(addr)?.handle(WebhookService::new(secret, sender))?;
+
+    Ok((listening, receiver))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::channel;
+
+    use hmac::{Hmac, Mac};
+    use hyper::header::Headers;
+    use hyper::method::Method;
+    use hyper::status::StatusCode;
+    use sha2::Sha256;
+
+    use webhooks::dedup::SeenEvents;
+    use webhooks::v3::EventData;
+    use super::WebhookService;
+
+    const BODY: &str = r#"{
+        "event": {
+            "id": "01BZ5VK2ZOJ4TAR31AZTCFMPNK",
+            "event_type": "pagey.ping",
+            "resource_type": "pagey",
+            "occurred_at": "2023-01-03T10:00:00Z",
+            "data": { "type": "ping", "message": "Hello from your friend Pagey!" }
+        }
+    }"#;
+
+    fn signed(secret: &[u8], body: &[u8]) -> Headers {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(body);
+
+        let hex: String = mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        let mut headers = Headers::new();
+        headers.set_raw("X-PagerDuty-Signature", vec![format!("v1={}", hex).into_bytes()]);
+        headers
+    }
+
+    #[test]
+    fn forward_signed_webhooks() {
+        let (sender, events) = channel();
+        let service = WebhookService::new("secret", sender);
+        let body = BODY.as_bytes();
+
+        assert_eq!(service.receive(&Method::Get, &signed(b"secret", body), body),
+                   StatusCode::MethodNotAllowed);
+        assert_eq!(service.receive(&Method::Post, &signed(b"other", body), body),
+                   StatusCode::Unauthorized);
+        assert_eq!(service.receive(&Method::Post, &signed(b"secret", b"{}"), &b"{}"[..]),
+                   StatusCode::BadRequest);
+        assert_eq!(service.receive(&Method::Post, &signed(b"secret", body), body),
+                   StatusCode::NoContent);
+
+        match events.recv().unwrap().data {
+            EventData::Ping(ping) => assert_eq!(ping.message, "Hello from your friend Pagey!"),
+            other => panic!("unexpected data: {:?}", other),
+        }
+
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn refuse_large_bodies() {
+        let (sender, _events) = channel();
+        let service = WebhookService::new("secret", sender).set_max_body_size(16);
+        let body = BODY.as_bytes();
+
+        assert_eq!(service.receive(&Method::Post, &signed(b"secret", body), body),
+                   StatusCode::PayloadTooLarge);
+    }
+
+    #[test]
+    fn retry_when_receiver_is_gone() {
+        let (sender, events) = channel();
+        let service = WebhookService::new("secret", sender).set_seen_events(SeenEvents::new(8));
+        let body = BODY.as_bytes();
+        let headers = signed(b"secret", body);
+
+        drop(events);
+        assert_eq!(service.receive(&Method::Post, &headers, body),
+                   StatusCode::ServiceUnavailable);
+        assert_eq!(service.receive(&Method::Post, &headers, body),
+                   StatusCode::ServiceUnavailable);
+    }
+
+    #[test]
+    fn skip_redeliveries() {
+        let (sender, events) = channel();
+        let service = WebhookService::new("secret", sender).set_seen_events(SeenEvents::new(8));
+        let body = BODY.as_bytes();
+        let headers = signed(b"secret", body);
+
+        assert_eq!(service.receive(&Method::Post, &headers, body), StatusCode::NoContent);
+        assert_eq!(service.receive(&Method::Post, &headers, body), StatusCode::NoContent);
+
+        assert!(events.try_recv().is_ok());
+        assert!(events.try_recv().is_err());
+    }
+}
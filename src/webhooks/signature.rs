@@ -0,0 +1,70 @@
+//! Webhook signatures
+//!
+//! V3 webhooks are signed with the secret of their webhook subscription. The
+//! `X-PagerDuty-Signature` header holds one or more comma-separated signatures of the form
+//! `v1=<hex>`, each a hex-encoded HMAC-SHA256 of the raw request body. There's more than one
+//! signature while a secret is being rotated, and a webhook is genuine if any of them matches.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Name of the header holding the signatures of a webhook
+pub const SIGNATURE_HEADER: &str = "X-PagerDuty-Signature";
+
+/// Check that `header`, the value of the signature header, holds a signature of `body` by `secret`
+///
+/// Signatures of versions other than `v1`, and ones which aren't valid hex, are ignored.
+pub fn verify(secret: &[u8], body: &[u8], header: &str) -> bool {
+    header.split(',')
+        .filter_map(|signature| {
+            let mut parts = signature.trim().splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some("v1"), Some(hex)) => decode_hex(hex),
+                _ => None,
+            }
+        })
+        .any(|signature| {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+                .expect("HMAC accepts keys of any length");
+            mac.update(body);
+            mac.verify_slice(&signature).is_ok()
+        })
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16);
+            let low = (pair[1] as char).to_digit(16);
+            match (high, low) {
+                (Some(high), Some(low)) => Some((high * 16 + low) as u8),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify;
+
+    #[test]
+    fn verify_signatures() {
+        let body = br#"{"event":{}}"#;
+        let current = "v1=2c984a26b96792f49173390b114619a17f5699a0bb42f13aff472b980b3080b5";
+        let old = "v1=2e1f8ac4275fbc9d5a4e7d438013ae65f819e6647583c7830bac68fc590a55db";
+
+        assert!(verify(b"secret", body, current));
+        assert!(verify(b"secret", body, &format!("{}, {}", old, current)));
+        assert!(!verify(b"secret", body, old));
+        assert!(!verify(b"secret", b"{}", current));
+        assert!(!verify(b"secret", body, &current.replace("v1", "v0")));
+        assert!(!verify(b"secret", body, "v1=zz"));
+        assert!(!verify(b"secret", body, ""));
+    }
+}
@@ -0,0 +1,361 @@
+//! V2 Webhooks
+//!
+//! A V2 webhook is a batch of [`Message`](struct.Message.html)s, each describing an event on an
+//! incident, such as it being triggered or resolved. Messages embed the incident as it was after
+//! the event, and the log entries recording the event.
+//!
+//! # Example
+//!
+//! ```
+//! use pagerduty::webhooks::v2::{Event, Webhook};
+//!
+//! # let body = br#"{"messages":[]}"#;
+//! let webhook = Webhook::from_slice(body).unwrap();
+//! for message in &webhook.messages {
+//!     if message.event == Event::Trigger {
+//!         println!("#{} triggered: {}", message.incident.incident_number,
+//!                  message.incident.title.as_ref().map(|t| &t[..]).unwrap_or(""));
+//!     }
+//! }
+//! ```
+
+use std::str::FromStr;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, Utc};
+
+use serde_json::{self, Value as Json};
+
+use rest::Reference;
+use rest::log_entries::LogEntry;
+use rest::users::Urgency;
+
+/// A batch of messages sent in one request
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Webhook {
+    pub messages: Vec<Message>,
+}
+
+impl Webhook {
+    /// Parse a webhook from the body of a request
+    pub fn from_slice(body: &[u8]) -> serde_json::Result<Webhook> {
+        serde_json::from_slice(body)
+    }
+}
+
+/// Parse a webhook from the body of a request
+impl FromStr for Webhook {
+    type Err = serde_json::Error;
+
+    fn from_str(body: &str) -> serde_json::Result<Webhook> {
+        serde_json::from_str(body)
+    }
+}
+
+/// The kind of event a message describes
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum Event {
+    #[serde(rename = "incident.trigger")]
+    Trigger,
+
+    #[serde(rename = "incident.acknowledge")]
+    Acknowledge,
+
+    /// The incident returned to triggered after its acknowledgement timed out
+    #[serde(rename = "incident.unacknowledge")]
+    Unacknowledge,
+
+    #[serde(rename = "incident.resolve")]
+    Resolve,
+
+    /// The incident was manually assigned to another user
+    #[serde(rename = "incident.assign")]
+    Assign,
+
+    /// The incident was escalated to another level of its escalation policy
+    #[serde(rename = "incident.escalate")]
+    Escalate,
+
+    /// The incident was reassigned to another escalation policy
+    #[serde(rename = "incident.delegate")]
+    Delegate,
+
+    /// A note was added to the incident
+    #[serde(rename = "incident.annotate")]
+    Annotate,
+
+    /// An event this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// A single event on an incident
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Message {
+    /// The message's ID
+    pub id: String,
+
+    /// The kind of event
+    pub event: Event,
+
+    /// The time at which the event occurred
+    pub created_on: String,
+
+    /// The incident as it was after the event
+    pub incident: Incident,
+
+    /// The extension which sent the webhook
+    #[serde(default)]
+    pub webhook: Option<WebhookExtension>,
+
+    /// The log entries recording the event
+    #[serde(default)]
+    pub log_entries: Vec<LogEntry>,
+}
+
+#[cfg(feature = "chrono")]
+impl Message {
+    /// The time at which the event occurred, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_on_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_on)
+    }
+}
+
+/// The current state of an incident
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentStatus {
+    Triggered,
+    Acknowledged,
+    Resolved,
+
+    /// A status this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// The assignment of an incident to a user
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Assignment {
+    /// The time at which the incident was assigned
+    pub at: String,
+
+    /// The user the incident is assigned to
+    pub assignee: Reference,
+}
+
+/// The acknowledgement of an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Acknowledgement {
+    /// The time at which the incident was acknowledged
+    pub at: String,
+
+    /// The user or integration which acknowledged the incident
+    pub acknowledger: Reference,
+}
+
+/// An incident as embedded in a message
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Incident {
+    /// The incident's ID
+    pub id: String,
+
+    /// The incident's number, unique within the account
+    pub incident_number: u64,
+
+    /// A short-form, server-generated string describing the incident
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the incident is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the incident is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The title of the incident
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// The description of the incident
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The time at which the incident was created
+    pub created_at: String,
+
+    /// The current state of the incident
+    pub status: IncidentStatus,
+
+    /// The key incidents are deduplicated with
+    #[serde(default)]
+    pub incident_key: Option<String>,
+
+    /// The incident's urgency
+    #[serde(default)]
+    pub urgency: Option<Urgency>,
+
+    /// The service the incident belongs to
+    #[serde(default)]
+    pub service: Option<Reference>,
+
+    /// The escalation policy the incident is escalated along
+    #[serde(default)]
+    pub escalation_policy: Option<Reference>,
+
+    /// The teams the incident belongs to
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// The incident's priority
+    #[serde(default)]
+    pub priority: Option<Reference>,
+
+    /// The users the incident is currently assigned to
+    #[serde(default)]
+    pub assignments: Vec<Assignment>,
+
+    /// The current acknowledgements of the incident
+    #[serde(default)]
+    pub acknowledgements: Vec<Acknowledgement>,
+
+    /// The time at which the incident's status last changed
+    #[serde(default)]
+    pub last_status_change_at: Option<String>,
+
+    /// The user or integration which last changed the incident's status
+    #[serde(default)]
+    pub last_status_change_by: Option<Reference>,
+
+    /// The log entry of the incident being triggered
+    #[serde(default)]
+    pub first_trigger_log_entry: Option<Reference>,
+}
+
+#[cfg(feature = "chrono")]
+impl Incident {
+    /// The time at which the incident was created, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// The extension which sent a webhook
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct WebhookExtension {
+    /// The extension's ID
+    pub id: String,
+
+    /// The name of the extension
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// The URL the webhook was sent to
+    #[serde(default)]
+    pub endpoint_url: Option<String>,
+
+    /// The object the extension is attached to, such as a service
+    #[serde(default)]
+    pub webhook_object: Option<Reference>,
+
+    /// Configuration of the extension
+    #[serde(default)]
+    pub config: Option<Json>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rest::users::Urgency;
+    use super::{Event, IncidentStatus, Webhook};
+
+    #[test]
+    fn trigger_from_json() {
+        let body = stringify!({
+            "messages": [
+                {
+                    "id": "bb8b8fe0-e8d5-11e2-9c1e-22000afd16cf",
+                    "event": "incident.trigger",
+                    "created_on": "2023-01-03T10:00:00Z",
+                    "incident": {
+                        "id": "PT4KHLK",
+                        "type": "incident",
+                        "incident_number": 1234,
+                        "title": "Disk full on db01",
+                        "created_at": "2023-01-03T10:00:00Z",
+                        "status": "triggered",
+                        "incident_key": "db01/disk",
+                        "urgency": "high",
+                        "service": {
+                            "id": "PIJ90N7",
+                            "type": "service",
+                            "name": "Database",
+                            "summary": "Database"
+                        },
+                        "assignments": [
+                            {
+                                "at": "2023-01-03T10:00:00Z",
+                                "assignee": {
+                                    "id": "PXPGF42",
+                                    "type": "user_reference",
+                                    "summary": "Jane Doe"
+                                }
+                            }
+                        ],
+                        "acknowledgements": [],
+                        "teams": []
+                    },
+                    "webhook": {
+                        "id": "PWEVPB6",
+                        "type": "webhook",
+                        "name": "Chat bot",
+                        "endpoint_url": "https://example.com/pagerduty",
+                        "webhook_object": { "id": "PIJ90N7", "type": "service_reference" },
+                        "config": {}
+                    },
+                    "log_entries": [
+                        {
+                            "id": "R2XGXEI3W0FHMAUR8F9NRW5LN1",
+                            "type": "trigger_log_entry",
+                            "created_at": "2023-01-03T10:00:00Z",
+                            "channel": { "type": "api", "summary": "Disk full on db01" },
+                            "teams": []
+                        }
+                    ]
+                },
+                {
+                    "id": "8a1d6420-e8d5-11e2-9c1e-22000afd16cf",
+                    "event": "incident.custom_event",
+                    "created_on": "2023-01-03T10:05:00Z",
+                    "incident": {
+                        "id": "PT4KHLK",
+                        "incident_number": 1234,
+                        "created_at": "2023-01-03T10:00:00Z",
+                        "status": "paused"
+                    }
+                }
+            ]
+        });
+
+        let webhook = Webhook::from_str(body).unwrap();
+        let message = &webhook.messages[0];
+
+        assert_eq!(message.event, Event::Trigger);
+        assert_eq!(message.incident.incident_number, 1234);
+        assert_eq!(message.incident.status, IncidentStatus::Triggered);
+        assert_eq!(message.incident.urgency, Some(Urgency::High));
+        assert_eq!(message.incident.assignments[0].assignee.id, "PXPGF42");
+        assert_eq!(message.log_entries[0].kind, "trigger_log_entry");
+
+        assert_eq!(webhook.messages[1].event, Event::Unknown);
+        assert_eq!(webhook.messages[1].incident.status, IncidentStatus::Unknown);
+        assert_eq!(Webhook::from_slice(body.as_bytes()).unwrap(), webhook);
+    }
+}
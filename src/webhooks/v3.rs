@@ -0,0 +1,441 @@
+//! V3 Webhooks
+//!
+//! A V3 webhook describes a single event, such as an incident being triggered or a service being
+//! updated. The object the event is about is in `Event::data`, whose variant follows the type of
+//! the object.
+//!
+//! # Example
+//!
+//! ```
+//! use pagerduty::rest::webhook_subscriptions::EventType;
+//! use pagerduty::webhooks::v3::{EventData, Webhook};
+//!
+//! # let body = br#"{"event":{"id":"5ac64822","event_type":"incident.triggered",
+//! #     "resource_type":"incident","occurred_at":"2023-01-03T10:00:00Z",
+//! #     "data":{"id":"PT4KHLK","type":"incident","number":1234,"status":"triggered",
+//! #     "created_at":"2023-01-03T10:00:00Z"}}}"#;
+//! let webhook = Webhook::from_slice(body).unwrap();
+//! match (webhook.event.event_type, webhook.event.data) {
+//!     (EventType::IncidentTriggered, EventData::Incident(incident)) => {
+//!         println!("#{} triggered", incident.number);
+//!     },
+//!     _ => (),
+//! }
+//! ```
+
+use std::str::FromStr;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, ParseError, Utc};
+
+use serde::de::{Deserialize, Deserializer, Error};
+use serde_json::{self, from_value, Value as Json};
+
+use rest::Reference;
+use rest::users::Urgency;
+use rest::webhook_subscriptions::EventType;
+pub use super::v2::IncidentStatus;
+
+/// The body of a webhook
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Webhook {
+    pub event: Event,
+}
+
+impl Webhook {
+    /// Parse a webhook from the body of a request
+    pub fn from_slice(body: &[u8]) -> serde_json::Result<Webhook> {
+        serde_json::from_slice(body)
+    }
+}
+
+/// Parse a webhook from the body of a request
+impl FromStr for Webhook {
+    type Err = serde_json::Error;
+
+    fn from_str(body: &str) -> serde_json::Result<Webhook> {
+        serde_json::from_str(body)
+    }
+}
+
+/// The kind of object an event is about
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceType {
+    Incident,
+    Service,
+
+    /// Test events sent by `ping`
+    Pagey,
+
+    /// A resource type this library doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// An event on an account
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Event {
+    /// The event's ID
+    pub id: String,
+
+    /// The kind of event
+    pub event_type: EventType,
+
+    /// The kind of object the event is about
+    pub resource_type: ResourceType,
+
+    /// The time at which the event occurred
+    pub occurred_at: String,
+
+    /// The user or integration which caused the event, if any
+    #[serde(default)]
+    pub agent: Option<Reference>,
+
+    /// The object the event is about
+    pub data: EventData,
+}
+
+#[cfg(feature = "chrono")]
+impl Event {
+    /// The time at which the event occurred, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn occurred_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.occurred_at)
+    }
+}
+
+/// The object an event is about
+///
+/// Objects of types this library doesn't know about are kept as `Other`.
+#[derive(Debug, PartialEq)]
+pub enum EventData {
+    /// An incident, for most `incident.*` events including `incident.priority_updated`
+    Incident(Incident),
+
+    /// A note added to an incident, for `incident.annotated`
+    IncidentNote(IncidentNote),
+
+    /// A responder requested for an incident, for `incident.responder.*`
+    IncidentResponder(IncidentResponder),
+
+    /// A status update, for `incident.status_update_published`
+    StatusUpdate(StatusUpdate),
+
+    /// A conference bridge, for `incident.conference_bridge.updated`
+    ConferenceBridge(ConferenceBridge),
+
+    /// A service, for `service.*`
+    Service(Service),
+
+    /// A test event, for `pagey.ping`
+    Ping(Ping),
+
+    Other(Json),
+}
+
+impl<'de> Deserialize<'de> for EventData {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<EventData, D::Error>
+        where D: Deserializer<'de>
+    {
+        let data = Json::deserialize(deserializer)?;
+        let kind = data.get("type").and_then(Json::as_str).map(|kind| kind.to_owned());
+
+        let parsed = match kind.as_ref().map(|kind| &kind[..]) {
+            Some("incident") => from_value(data).map(EventData::Incident),
+            Some("incident_note") => from_value(data).map(EventData::IncidentNote),
+            Some("incident_responder") => from_value(data).map(EventData::IncidentResponder),
+            Some("status_update") => from_value(data).map(EventData::StatusUpdate),
+            Some("incident_conference_bridge") => {
+                from_value(data).map(EventData::ConferenceBridge)
+            },
+            Some("service") => from_value(data).map(EventData::Service),
+            Some("ping") => from_value(data).map(EventData::Ping),
+            _ => Ok(EventData::Other(data)),
+        };
+
+        parsed.map_err(D::Error::custom)
+    }
+}
+
+/// An incident as embedded in an event
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Incident {
+    /// The incident's ID
+    pub id: String,
+
+    /// The API URL at which the incident is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the incident is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The incident's number, unique within the account
+    pub number: u64,
+
+    /// The current state of the incident
+    pub status: IncidentStatus,
+
+    /// The title of the incident
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// The time at which the incident was created
+    pub created_at: String,
+
+    /// The key incidents are deduplicated with
+    #[serde(default)]
+    pub incident_key: Option<String>,
+
+    /// The incident's urgency
+    #[serde(default)]
+    pub urgency: Option<Urgency>,
+
+    /// The incident's priority
+    #[serde(default)]
+    pub priority: Option<Reference>,
+
+    /// The service the incident belongs to
+    #[serde(default)]
+    pub service: Option<Reference>,
+
+    /// The escalation policy the incident is escalated along
+    #[serde(default)]
+    pub escalation_policy: Option<Reference>,
+
+    /// The teams the incident belongs to
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+
+    /// The users the incident is currently assigned to
+    #[serde(default)]
+    pub assignees: Vec<Reference>,
+
+    /// Why the incident was resolved, when it was merged into another
+    #[serde(default)]
+    pub resolve_reason: Option<Json>,
+}
+
+#[cfg(feature = "chrono")]
+impl Incident {
+    /// The time at which the incident was created, in UTC
+    ///
+    /// This requires the `chrono` feature.
+    pub fn created_at_utc(&self) -> Result<DateTime<Utc>, ParseError> {
+        ::timestamp::parse(&self.created_at)
+    }
+}
+
+/// A note added to an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct IncidentNote {
+    /// The note's ID
+    pub id: String,
+
+    /// The content of the note, possibly trimmed
+    pub content: String,
+
+    /// Whether `content` was trimmed
+    #[serde(default)]
+    pub trimmed: bool,
+
+    /// The incident the note was added to
+    pub incident: Reference,
+}
+
+/// A request for a responder to join an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct IncidentResponder {
+    /// The incident the responder was requested for
+    pub incident: Reference,
+
+    /// The user requested
+    #[serde(default)]
+    pub user: Option<Reference>,
+
+    /// The escalation policy requested
+    #[serde(default)]
+    pub escalation_policy: Option<Reference>,
+
+    /// The message sent with the request
+    #[serde(default)]
+    pub message: Option<String>,
+
+    /// The state of the request, such as `pending` or `joined`
+    #[serde(default)]
+    pub state: Option<String>,
+}
+
+/// A status update published on an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct StatusUpdate {
+    /// The status update's ID
+    pub id: String,
+
+    /// The message of the status update
+    pub message: String,
+
+    /// The incident the status update was published on
+    pub incident: Reference,
+
+    /// The user who published the status update
+    #[serde(default)]
+    pub sender: Option<Reference>,
+}
+
+/// The conference bridge of an incident
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct ConferenceBridge {
+    /// The incident the conference bridge belongs to
+    pub incident: Reference,
+
+    /// The phone number of the conference bridge
+    #[serde(default)]
+    pub conference_number: Option<String>,
+
+    /// The URL of the conference bridge
+    #[serde(default)]
+    pub conference_url: Option<String>,
+}
+
+/// A service as embedded in an event
+///
+/// Only the ID is sent for `service.deleted`.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Service {
+    /// The service's ID
+    pub id: String,
+
+    /// A short-form, server-generated string describing the service
+    #[serde(default)]
+    pub summary: Option<String>,
+
+    /// The API URL at which the service is accessible
+    #[serde(rename = "self", default)]
+    pub self_url: Option<String>,
+
+    /// A URL at which the service is rendered in the web application
+    #[serde(default)]
+    pub html_url: Option<String>,
+
+    /// The name of the service
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// A description of the service
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// The current state of the service, such as `active` or `disabled`
+    #[serde(default)]
+    pub status: Option<String>,
+
+    /// The escalation policy of the service
+    #[serde(default)]
+    pub escalation_policy: Option<Reference>,
+
+    /// The teams the service belongs to
+    #[serde(default)]
+    pub teams: Vec<Reference>,
+}
+
+/// A test event
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct Ping {
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rest::webhook_subscriptions::EventType;
+    use super::{EventData, IncidentStatus, ResourceType, Webhook};
+
+    #[test]
+    fn priority_updated_from_json() {
+        let body = stringify!({
+            "event": {
+                "id": "5ac64822-4adc-4fda-ade0-410becf0de4f",
+                "event_type": "incident.priority_updated",
+                "resource_type": "incident",
+                "occurred_at": "2023-01-03T10:05:00.169Z",
+                "agent": { "id": "PXPGF42", "type": "user_reference", "summary": "Jane Doe" },
+                "client": null,
+                "data": {
+                    "id": "PT4KHLK",
+                    "type": "incident",
+                    "self": "https://api.pagerduty.com/incidents/PT4KHLK",
+                    "html_url": "https://acme.pagerduty.com/incidents/PT4KHLK",
+                    "number": 1234,
+                    "status": "acknowledged",
+                    "incident_key": "db01/disk",
+                    "created_at": "2023-01-03T10:00:00Z",
+                    "title": "Disk full on db01",
+                    "service": { "id": "PIJ90N7", "type": "service_reference" },
+                    "assignees": [ { "id": "PXPGF42", "type": "user_reference" } ],
+                    "escalation_policy": { "id": "PANZZEQ", "type": "escalation_policy" },
+                    "teams": [],
+                    "priority": { "id": "PSO75BM", "type": "priority", "summary": "P1" },
+                    "urgency": "high",
+                    "conference_bridge": null,
+                    "resolve_reason": null
+                }
+            }
+        });
+
+        let webhook = Webhook::from_str(body).unwrap();
+        let event = webhook.event;
+
+        assert_eq!(event.event_type, EventType::IncidentPriorityUpdated);
+        assert_eq!(event.resource_type, ResourceType::Incident);
+
+        match event.data {
+            EventData::Incident(incident) => {
+                assert_eq!(incident.number, 1234);
+                assert_eq!(incident.status, IncidentStatus::Acknowledged);
+                assert_eq!(incident.priority.unwrap().summary, Some("P1".to_owned()));
+            },
+            other => panic!("unexpected data: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn other_events_from_json() {
+        let body = stringify!({
+            "event": {
+                "id": "01BZ5VK2ZOJ4TAR31AZTCFMPNM",
+                "event_type": "service.deleted",
+                "resource_type": "service",
+                "occurred_at": "2023-01-03T10:00:00Z",
+                "data": { "id": "PIJ90N7", "type": "service" }
+            }
+        });
+
+        match Webhook::from_slice(body.as_bytes()).unwrap().event.data {
+            EventData::Service(service) => assert_eq!(service.id, "PIJ90N7"),
+            other => panic!("unexpected data: {:?}", other),
+        }
+
+        let body = stringify!({
+            "event": {
+                "id": "01BZ5VK2ZOJ4TAR31AZTCFMPNN",
+                "event_type": "incident.paused",
+                "resource_type": "incident",
+                "occurred_at": "2023-01-03T10:00:00Z",
+                "data": { "id": "PT4KHLK", "type": "incident_pause" }
+            }
+        });
+
+        let event = Webhook::from_str(body).unwrap().event;
+        assert_eq!(event.event_type, EventType::Other("incident.paused".to_owned()));
+
+        match event.data {
+            EventData::Other(data) => assert_eq!(data["type"], "incident_pause"),
+            other => panic!("unexpected data: {:?}", other),
+        }
+    }
+}